@@ -164,5 +164,6 @@ fn testnet_genesis(
             thing: Default::default(),
             alice: endowed_accounts.get(0).unwrap().clone(),
         }),
+        pallet_treasury: Some(Default::default()),
     }
 }
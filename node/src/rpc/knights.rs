@@ -0,0 +1,162 @@
+//! The `knights_*` RPC namespace.
+//!
+//! Wraps the `KnightsApi` runtime api and returns knights in a shape that is
+//! easy to consume from JavaScript: names as strings, DNA as hex and balances
+//! as `NumberOrHex`, since they can be larger than a JavaScript number holds.
+
+use std::sync::Arc;
+
+use jsonrpc_core::{Error as RpcError, ErrorCode, Result};
+use jsonrpc_derive::rpc;
+use node_template_runtime::{
+    opaque::Block, AccountId, BlockNumber, Knight, KnightIndex, KnightsApi as KnightsRuntimeApi,
+};
+use serde::{Deserialize, Serialize};
+use sp_api::ProvideRuntimeApi;
+use sp_blockchain::HeaderBackend;
+use sp_core::hexdisplay::HexDisplay;
+use sp_rpc::number::NumberOrHex;
+use sp_runtime::{generic::BlockId, traits::Block as BlockT};
+
+/// Error code returned when a runtime api call fails.
+const RUNTIME_ERROR: i64 = 1;
+
+/// A knight as returned over RPC.
+#[derive(Clone, Debug, PartialEq, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct KnightInfo {
+    /// The knight's id.
+    pub id: KnightIndex,
+    /// The account that owns the knight.
+    pub owner: AccountId,
+    /// The knight's name, lossily decoded as UTF-8.
+    pub name: String,
+    /// The knight's DNA as a `0x` prefixed hex string.
+    pub dna: String,
+    /// The knight's wealth.
+    pub wealth: NumberOrHex,
+    /// The knight's asking price. Zero means it is not for sale.
+    pub price: NumberOrHex,
+    /// The knight's generation.
+    pub gen: u64,
+    /// The block the knight was minted in.
+    pub birth_block: BlockNumber,
+    /// The ids of the knights this one was squired from, if any.
+    pub parents: Option<(KnightIndex, KnightIndex)>,
+}
+
+impl KnightInfo {
+    fn new(knight: Knight, owner: AccountId) -> Self {
+        KnightInfo {
+            id: knight.id,
+            owner,
+            name: String::from_utf8_lossy(&knight.name).into_owned(),
+            dna: format!("0x{}", HexDisplay::from(&knight.dna)),
+            wealth: knight.wealth.into(),
+            price: knight.price.into(),
+            gen: knight.gen,
+            birth_block: knight.birth_block,
+            parents: knight.parents,
+        }
+    }
+}
+
+/// Knights RPC methods.
+#[rpc]
+pub trait KnightsApi<BlockHash> {
+    /// Get a knight and its owner by id.
+    #[rpc(name = "knights_getKnight")]
+    fn knight(&self, knight_id: KnightIndex, at: Option<BlockHash>) -> Result<Option<KnightInfo>>;
+
+    /// Get all knights owned by an account.
+    #[rpc(name = "knights_getKnightsByOwner")]
+    fn knights_by_owner(&self, owner: AccountId, at: Option<BlockHash>) -> Result<Vec<KnightInfo>>;
+
+    /// Get the total number of knights.
+    #[rpc(name = "knights_getKnightCount")]
+    fn knight_count(&self, at: Option<BlockHash>) -> Result<u64>;
+
+    /// Get the id of the most recently minted knight.
+    #[rpc(name = "knights_getLatestKnightId")]
+    fn latest_knight_id(&self, at: Option<BlockHash>) -> Result<KnightIndex>;
+}
+
+/// Implements the `KnightsApi` RPC trait by calling into the runtime.
+pub struct Knights<C> {
+    client: Arc<C>,
+}
+
+impl<C> Knights<C> {
+    /// Create a new `Knights` RPC handler.
+    pub fn new(client: Arc<C>) -> Self {
+        Knights { client }
+    }
+}
+
+fn runtime_error(message: &str, e: impl std::fmt::Debug) -> RpcError {
+    RpcError {
+        code: ErrorCode::ServerError(RUNTIME_ERROR),
+        message: message.into(),
+        data: Some(format!("{:?}", e).into()),
+    }
+}
+
+impl<C> KnightsApi<<Block as BlockT>::Hash> for Knights<C>
+where
+    C: ProvideRuntimeApi<Block> + HeaderBackend<Block> + Send + Sync + 'static,
+    C::Api: KnightsRuntimeApi<Block>,
+{
+    fn knight(
+        &self,
+        knight_id: KnightIndex,
+        at: Option<<Block as BlockT>::Hash>,
+    ) -> Result<Option<KnightInfo>> {
+        let api = self.client.runtime_api();
+        let at = BlockId::hash(at.unwrap_or_else(|| self.client.info().best_hash));
+
+        let knight = api
+            .knight(&at, knight_id)
+            .map_err(|e| runtime_error("Unable to query knight.", e))?;
+        let owner = api
+            .knight_owner(&at, knight_id)
+            .map_err(|e| runtime_error("Unable to query knight owner.", e))?;
+
+        Ok(knight
+            .zip(owner)
+            .map(|(knight, owner)| KnightInfo::new(knight, owner)))
+    }
+
+    fn knights_by_owner(
+        &self,
+        owner: AccountId,
+        at: Option<<Block as BlockT>::Hash>,
+    ) -> Result<Vec<KnightInfo>> {
+        let api = self.client.runtime_api();
+        let at = BlockId::hash(at.unwrap_or_else(|| self.client.info().best_hash));
+
+        let knights = api
+            .knights_by_owner(&at, owner.clone())
+            .map_err(|e| runtime_error("Unable to query knights by owner.", e))?;
+
+        Ok(knights
+            .into_iter()
+            .map(|knight| KnightInfo::new(knight, owner.clone()))
+            .collect())
+    }
+
+    fn knight_count(&self, at: Option<<Block as BlockT>::Hash>) -> Result<u64> {
+        let api = self.client.runtime_api();
+        let at = BlockId::hash(at.unwrap_or_else(|| self.client.info().best_hash));
+
+        api.knight_count(&at)
+            .map_err(|e| runtime_error("Unable to query knight count.", e))
+    }
+
+    fn latest_knight_id(&self, at: Option<<Block as BlockT>::Hash>) -> Result<KnightIndex> {
+        let api = self.client.runtime_api();
+        let at = BlockId::hash(at.unwrap_or_else(|| self.client.info().best_hash));
+
+        api.latest_knight_id(&at)
+            .map_err(|e| runtime_error("Unable to query latest knight id.", e))
+    }
+}
@@ -2,13 +2,898 @@
 
 use super::*;
 
-use frame_benchmarking::{benchmarks, impl_benchmark_test_suite, whitelisted_caller};
+use frame_benchmarking::{account, benchmarks, impl_benchmark_test_suite, whitelisted_caller};
+use frame_support::traits::{Currency, Get};
 use frame_system::RawOrigin;
+use sp_core::H256;
+use sp_runtime::traits::{Bounded, Hash, One};
+use sp_runtime::Perbill;
 use sp_std::{boxed::Box, vec, vec::Vec};
 
 #[allow(unused)]
-use crate::Module as Knights;
+use crate::Module as KnightModule;
 
-benchmarks! {}
+const SEED: u32 = 0;
 
-impl_benchmark_test_suite!(Knights, crate::mock::new_test_ext(), crate::mock::Test,);
+fn name(length: u32) -> Vec<u8> {
+    vec![b'k'; length as usize]
+}
+
+/// Mints `count` knights to `owner` and returns the id of the last one.
+fn mint_knights<T: Config>(owner: &T::AccountId, count: u32) -> T::KnightIndex {
+    for _ in 0..count {
+        KnightModule::<T>::create_knight(
+            RawOrigin::Signed(owner.clone()).into(),
+            name(16),
+            Perbill::zero(),
+            None,
+        )
+        .expect("Creates Knight");
+    }
+
+    LatestKnightId::<T>::get()
+}
+
+fn fund<T: Config>(who: &T::AccountId) {
+    <pallet_balances::Pallet<T> as Currency<_>>::make_free_balance_be(
+        who,
+        T::Balance::max_value() / 4u32.into(),
+    );
+}
+
+fn order_rules<T: Config>() -> OrderRules<T::Balance> {
+    OrderRules {
+        dues: 100u32.into(),
+        min_level: 0,
+        class: None,
+    }
+}
+
+/// Founds order 0 with `founder` as its founder.
+fn found_order<T: Config>(founder: &T::AccountId) {
+    KnightModule::<T>::found_order(
+        RawOrigin::Signed(founder.clone()).into(),
+        name(16),
+        order_rules::<T>(),
+    )
+    .expect("Founds order");
+}
+
+/// Funds `owner`, mints them a knight and enrolls it in order 0.
+fn enroll<T: Config>(owner: &T::AccountId) -> T::KnightIndex {
+    fund::<T>(owner);
+    let knight_id = mint_knights::<T>(owner, 1);
+    KnightModule::<T>::enroll_knight(RawOrigin::Signed(owner.clone()).into(), 0, knight_id)
+        .expect("Enrolls knight");
+    knight_id
+}
+
+/// Mints a knight for `seller` and puts it up for a sealed-bid auction.
+fn sealed_auction_for<T: Config>(seller: &T::AccountId) -> T::KnightIndex {
+    let knight_id = mint_knights::<T>(seller, 1);
+    let commit_end = frame_system::Pallet::<T>::block_number() + T::MaxAuctionDuration::get();
+    KnightModule::<T>::start_sealed_auction(
+        RawOrigin::Signed(seller.clone()).into(),
+        knight_id,
+        100u32.into(),
+        commit_end,
+    )
+    .expect("Starts sealed auction");
+    knight_id
+}
+
+/// Funds `bidder` and commits a bid of `bid` with a zero salt.
+fn commit_sealed_bid<T: Config>(bidder: &T::AccountId, knight_id: T::KnightIndex, bid: T::Balance) {
+    fund::<T>(bidder);
+    let commitment = T::Hashing::hash_of(&(bid, H256::zero()));
+    KnightModule::<T>::commit_bid(
+        RawOrigin::Signed(bidder.clone()).into(),
+        knight_id,
+        commitment,
+        bid,
+    )
+    .expect("Commits bid");
+}
+
+fn registration_end<T: Config>() -> T::BlockNumber {
+    frame_system::Pallet::<T>::block_number() + T::MaxRegistrationPeriod::get()
+}
+
+/// Opens tournament `0` of `size` knights and enters `entrants` of them.
+fn tournament_with_entrants<T: Config>(organiser: &T::AccountId, size: u32, entrants: u32) {
+    KnightModule::<T>::open_tournament(
+        RawOrigin::Signed(organiser.clone()).into(),
+        100u32.into(),
+        size,
+        registration_end::<T>(),
+    )
+    .expect("Opens tournament");
+    for i in 0..entrants {
+        let entrant: T::AccountId = account("entrant", i, SEED);
+        fund::<T>(&entrant);
+        let knight_id = mint_knights::<T>(&entrant, 1);
+        KnightModule::<T>::enter_tournament(RawOrigin::Signed(entrant).into(), 0, knight_id)
+            .expect("Enters tournament");
+    }
+}
+
+benchmarks! {
+    create_knight {
+        let n in 1 .. T::MaxNameLength::get();
+        let caller: T::AccountId = whitelisted_caller();
+    }: _(RawOrigin::Signed(caller.clone()), name(n), T::MaxRoyalty::get(), Some(KnightClass::Warrior))
+    verify {
+        assert_eq!(OwnerToKnightCount::<T>::get(&caller), 1);
+    }
+
+    transfer_knight {
+        let caller: T::AccountId = whitelisted_caller();
+        let recipient: T::AccountId = account("recipient", 0, SEED);
+        let knight_id = mint_knights::<T>(&caller, 1);
+    }: _(RawOrigin::Signed(caller), knight_id, recipient.clone())
+    verify {
+        assert_eq!(KnightToOwner::<T>::get(knight_id), Some(recipient));
+    }
+
+    set_price {
+        let caller: T::AccountId = whitelisted_caller();
+        let knight_id = mint_knights::<T>(&caller, 1);
+        let price: T::Balance = 100u32.into();
+    }: _(RawOrigin::Signed(caller), knight_id, price)
+    verify {
+        assert_eq!(Knights::<T>::get(knight_id).unwrap().price, price);
+    }
+
+    buy_knight {
+        let seller: T::AccountId = account("seller", 0, SEED);
+        let caller: T::AccountId = whitelisted_caller();
+        let knight_id = mint_knights::<T>(&seller, 1);
+        fund::<T>(&seller);
+        fund::<T>(&caller);
+        KnightModule::<T>::set_price(RawOrigin::Signed(seller).into(), knight_id, 100u32.into())
+            .expect("Sets price");
+    }: _(RawOrigin::Signed(caller.clone()), knight_id, 100u32.into())
+    verify {
+        assert_eq!(KnightToOwner::<T>::get(knight_id), Some(caller));
+    }
+
+    knight_squire {
+        let n in 1 .. T::MaxNameLength::get();
+        let owner: T::AccountId = account("owner", 0, SEED);
+        let caller: T::AccountId = whitelisted_caller();
+        let knight_id_2 = mint_knights::<T>(&owner, 2);
+        let knight_id_1 = knight_id_2 - One::one();
+        // squiring with one-shot approvals is the most expensive path
+        SquireApprovals::<T>::insert(knight_id_1, &caller, true);
+        SquireApprovals::<T>::insert(knight_id_2, &caller, true);
+    }: _(RawOrigin::Signed(caller.clone()), name(n), knight_id_1, knight_id_2)
+    verify {
+        assert_eq!(OwnerToKnightCount::<T>::get(&caller), 1);
+    }
+
+    approve_squire {
+        let caller: T::AccountId = whitelisted_caller();
+        let approved: T::AccountId = account("approved", 0, SEED);
+        let knight_id = mint_knights::<T>(&caller, 1);
+    }: _(RawOrigin::Signed(caller), knight_id, approved.clone(), true)
+    verify {
+        assert_eq!(SquireApprovals::<T>::get(knight_id, approved), Some(true));
+    }
+
+    revoke_squire_approval {
+        let caller: T::AccountId = whitelisted_caller();
+        let approved: T::AccountId = account("approved", 0, SEED);
+        let knight_id = mint_knights::<T>(&caller, 1);
+        SquireApprovals::<T>::insert(knight_id, &approved, false);
+    }: _(RawOrigin::Signed(caller), knight_id, approved.clone())
+    verify {
+        assert_eq!(SquireApprovals::<T>::get(knight_id, approved), None);
+    }
+
+    list_sire {
+        let caller: T::AccountId = whitelisted_caller();
+        let knight_id = mint_knights::<T>(&caller, 1);
+        let fee: T::Balance = 100u32.into();
+    }: _(RawOrigin::Signed(caller), knight_id, fee)
+    verify {
+        assert_eq!(SireListings::<T>::get(knight_id), Some(fee));
+    }
+
+    delist_sire {
+        let caller: T::AccountId = whitelisted_caller();
+        let knight_id = mint_knights::<T>(&caller, 1);
+        SireListings::<T>::insert(knight_id, T::Balance::from(100u32));
+    }: _(RawOrigin::Signed(caller), knight_id)
+    verify {
+        assert_eq!(SireListings::<T>::get(knight_id), None);
+    }
+
+    squire_with_sire {
+        let n in 1 .. T::MaxNameLength::get();
+        let sire_owner: T::AccountId = account("sire_owner", 0, SEED);
+        let caller: T::AccountId = whitelisted_caller();
+        let sire_id = mint_knights::<T>(&sire_owner, 1);
+        let knight_id = mint_knights::<T>(&caller, 1);
+        fund::<T>(&sire_owner);
+        fund::<T>(&caller);
+        let fee: T::Balance = 100u32.into();
+        SireListings::<T>::insert(sire_id, fee);
+    }: _(RawOrigin::Signed(caller.clone()), name(n), knight_id, sire_id, fee)
+    verify {
+        assert_eq!(OwnerToKnightCount::<T>::get(&caller), 2);
+    }
+
+    burn_knight {
+        let caller: T::AccountId = whitelisted_caller();
+        let knight_id = mint_knights::<T>(&caller, 1);
+    }: _(RawOrigin::Signed(caller.clone()), knight_id)
+    verify {
+        assert_eq!(Knights::<T>::get(knight_id), None);
+        assert_eq!(OwnerToKnightCount::<T>::get(&caller), 0);
+    }
+
+    start_auction {
+        let caller: T::AccountId = whitelisted_caller();
+        let knight_id = mint_knights::<T>(&caller, 1);
+        let end = frame_system::Pallet::<T>::block_number() + T::MaxAuctionDuration::get();
+    }: _(RawOrigin::Signed(caller.clone()), knight_id, 100u32.into(), end)
+    verify {
+        assert!(Auctions::<T>::contains_key(knight_id));
+        assert_eq!(KnightLocks::<T>::get(knight_id), Some(KnightLock::Auction));
+    }
+
+    bid {
+        // outbidding someone else does the most work
+        let seller: T::AccountId = account("seller", 0, SEED);
+        let rival: T::AccountId = account("rival", 0, SEED);
+        let caller: T::AccountId = whitelisted_caller();
+        fund::<T>(&rival);
+        fund::<T>(&caller);
+        let knight_id = mint_knights::<T>(&seller, 1);
+        let end = frame_system::Pallet::<T>::block_number() + T::MaxAuctionDuration::get();
+        KnightModule::<T>::start_auction(
+            RawOrigin::Signed(seller).into(),
+            knight_id,
+            100u32.into(),
+            end,
+        )?;
+        KnightModule::<T>::bid(RawOrigin::Signed(rival).into(), knight_id, 100u32.into())?;
+    }: _(RawOrigin::Signed(caller.clone()), knight_id, 200u32.into())
+    verify {
+        let auction = Auctions::<T>::get(knight_id).unwrap();
+        assert_eq!(auction.highest_bid, Some((caller, 200u32.into())));
+    }
+
+    cancel_auction {
+        let caller: T::AccountId = whitelisted_caller();
+        let knight_id = mint_knights::<T>(&caller, 1);
+        let end = frame_system::Pallet::<T>::block_number() + T::MaxAuctionDuration::get();
+        KnightModule::<T>::start_auction(
+            RawOrigin::Signed(caller.clone()).into(),
+            knight_id,
+            100u32.into(),
+            end,
+        )?;
+    }: _(RawOrigin::Signed(caller), knight_id)
+    verify {
+        assert!(!Auctions::<T>::contains_key(knight_id));
+        assert_eq!(KnightLocks::<T>::get(knight_id), None);
+    }
+
+    settle_auction {
+        let seller: T::AccountId = account("seller", 0, SEED);
+        let caller: T::AccountId = whitelisted_caller();
+        fund::<T>(&caller);
+        let knight_id = mint_knights::<T>(&seller, 1);
+        let end = frame_system::Pallet::<T>::block_number() + T::MaxAuctionDuration::get();
+        KnightModule::<T>::start_auction(
+            RawOrigin::Signed(seller).into(),
+            knight_id,
+            100u32.into(),
+            end,
+        )?;
+        KnightModule::<T>::bid(RawOrigin::Signed(caller.clone()).into(), knight_id, 100u32.into())?;
+    }: {
+        KnightModule::<T>::_settle_auction(knight_id);
+    }
+    verify {
+        assert_eq!(KnightToOwner::<T>::get(knight_id), Some(caller));
+    }
+
+    start_dutch_auction {
+        let caller: T::AccountId = whitelisted_caller();
+        let knight_id = mint_knights::<T>(&caller, 1);
+    }: _(
+        RawOrigin::Signed(caller.clone()),
+        knight_id,
+        200u32.into(),
+        100u32.into(),
+        T::MaxAuctionDuration::get()
+    )
+    verify {
+        assert!(DutchAuctions::<T>::contains_key(knight_id));
+    }
+
+    buy_from_dutch_auction {
+        let seller: T::AccountId = account("seller", 0, SEED);
+        let caller: T::AccountId = whitelisted_caller();
+        fund::<T>(&caller);
+        let knight_id = mint_knights::<T>(&seller, 1);
+        KnightModule::<T>::start_dutch_auction(
+            RawOrigin::Signed(seller).into(),
+            knight_id,
+            200u32.into(),
+            100u32.into(),
+            T::MaxAuctionDuration::get(),
+        )?;
+    }: _(RawOrigin::Signed(caller.clone()), knight_id, 200u32.into())
+    verify {
+        assert_eq!(KnightToOwner::<T>::get(knight_id), Some(caller));
+    }
+
+    cancel_dutch_auction {
+        let caller: T::AccountId = whitelisted_caller();
+        let knight_id = mint_knights::<T>(&caller, 1);
+        KnightModule::<T>::start_dutch_auction(
+            RawOrigin::Signed(caller.clone()).into(),
+            knight_id,
+            200u32.into(),
+            100u32.into(),
+            T::MaxAuctionDuration::get(),
+        )?;
+    }: _(RawOrigin::Signed(caller), knight_id)
+    verify {
+        assert!(!DutchAuctions::<T>::contains_key(knight_id));
+        assert_eq!(KnightLocks::<T>::get(knight_id), None);
+    }
+
+    start_sealed_auction {
+        let caller: T::AccountId = whitelisted_caller();
+        let knight_id = mint_knights::<T>(&caller, 1);
+        let commit_end = frame_system::Pallet::<T>::block_number() + T::MaxAuctionDuration::get();
+    }: _(RawOrigin::Signed(caller.clone()), knight_id, 100u32.into(), commit_end)
+    verify {
+        assert!(SealedAuctions::<T>::contains_key(knight_id));
+    }
+
+    commit_bid {
+        let seller: T::AccountId = account("seller", 0, SEED);
+        let caller: T::AccountId = whitelisted_caller();
+        fund::<T>(&caller);
+        let knight_id = sealed_auction_for::<T>(&seller);
+        let commitment = T::Hashing::hash_of(&(T::Balance::from(100u32), H256::zero()));
+    }: _(RawOrigin::Signed(caller.clone()), knight_id, commitment, 100u32.into())
+    verify {
+        assert!(SealedBids::<T>::contains_key(knight_id, &caller));
+    }
+
+    reveal_bid {
+        // revealing over an earlier reveal updates both the highest and second bids
+        let seller: T::AccountId = account("seller", 0, SEED);
+        let rival: T::AccountId = account("rival", 0, SEED);
+        let caller: T::AccountId = whitelisted_caller();
+        let knight_id = sealed_auction_for::<T>(&seller);
+        commit_sealed_bid::<T>(&rival, knight_id, 100u32.into());
+        commit_sealed_bid::<T>(&caller, knight_id, 200u32.into());
+        let commit_end = SealedAuctions::<T>::get(knight_id).unwrap().commit_end;
+        frame_system::Pallet::<T>::set_block_number(commit_end);
+        KnightModule::<T>::reveal_bid(
+            RawOrigin::Signed(rival).into(),
+            knight_id,
+            100u32.into(),
+            H256::zero(),
+        )?;
+    }: _(RawOrigin::Signed(caller.clone()), knight_id, 200u32.into(), H256::zero())
+    verify {
+        let auction = SealedAuctions::<T>::get(knight_id).unwrap();
+        assert_eq!(auction.highest_bid, Some((caller, 200u32.into())));
+    }
+
+    cancel_sealed_auction {
+        let caller: T::AccountId = whitelisted_caller();
+        let knight_id = sealed_auction_for::<T>(&caller);
+    }: _(RawOrigin::Signed(caller), knight_id)
+    verify {
+        assert!(!SealedAuctions::<T>::contains_key(knight_id));
+    }
+
+    settle_sealed_auction {
+        let b in 1 .. T::MaxSealedBids::get();
+
+        let seller: T::AccountId = account("seller", 0, SEED);
+        let knight_id = sealed_auction_for::<T>(&seller);
+        let mut winner = seller.clone();
+        for i in 0 .. b {
+            winner = account("bidder", i, SEED);
+            commit_sealed_bid::<T>(&winner, knight_id, (100u32 + i).into());
+        }
+        let commit_end = SealedAuctions::<T>::get(knight_id).unwrap().commit_end;
+        frame_system::Pallet::<T>::set_block_number(commit_end);
+        for i in 0 .. b {
+            let bidder: T::AccountId = account("bidder", i, SEED);
+            KnightModule::<T>::reveal_bid(
+                RawOrigin::Signed(bidder).into(),
+                knight_id,
+                (100u32 + i).into(),
+                H256::zero(),
+            )?;
+        }
+    }: {
+        KnightModule::<T>::_settle_sealed_auction(knight_id);
+    }
+    verify {
+        assert_eq!(KnightToOwner::<T>::get(knight_id), Some(winner));
+    }
+
+    make_offer {
+        let owner: T::AccountId = account("owner", 0, SEED);
+        let caller: T::AccountId = whitelisted_caller();
+        fund::<T>(&caller);
+        let knight_id = mint_knights::<T>(&owner, 1);
+    }: _(RawOrigin::Signed(caller.clone()), knight_id, T::MinOfferAmount::get())
+    verify {
+        assert!(Offers::<T>::contains_key(knight_id, &caller));
+    }
+
+    cancel_offer {
+        let owner: T::AccountId = account("owner", 0, SEED);
+        let caller: T::AccountId = whitelisted_caller();
+        fund::<T>(&caller);
+        let knight_id = mint_knights::<T>(&owner, 1);
+        KnightModule::<T>::make_offer(
+            RawOrigin::Signed(caller.clone()).into(),
+            knight_id,
+            T::MinOfferAmount::get(),
+        )?;
+    }: _(RawOrigin::Signed(caller.clone()), knight_id)
+    verify {
+        assert!(!Offers::<T>::contains_key(knight_id, &caller));
+    }
+
+    accept_offer {
+        let buyer: T::AccountId = account("buyer", 0, SEED);
+        let caller: T::AccountId = whitelisted_caller();
+        fund::<T>(&buyer);
+        let knight_id = mint_knights::<T>(&caller, 1);
+        KnightModule::<T>::make_offer(
+            RawOrigin::Signed(buyer.clone()).into(),
+            knight_id,
+            T::MinOfferAmount::get(),
+        )?;
+    }: _(RawOrigin::Signed(caller), knight_id, buyer.clone())
+    verify {
+        assert_eq!(KnightToOwner::<T>::get(knight_id), Some(buyer));
+    }
+
+    expire_offer {
+        let owner: T::AccountId = account("owner", 0, SEED);
+        let buyer: T::AccountId = account("buyer", 0, SEED);
+        fund::<T>(&buyer);
+        let knight_id = mint_knights::<T>(&owner, 1);
+        KnightModule::<T>::make_offer(
+            RawOrigin::Signed(buyer.clone()).into(),
+            knight_id,
+            T::MinOfferAmount::get(),
+        )?;
+    }: {
+        KnightModule::<T>::_expire_offer(knight_id, buyer.clone());
+    }
+    verify {
+        assert!(!Offers::<T>::contains_key(knight_id, &buyer));
+    }
+
+    place_collection_bid {
+        let caller: T::AccountId = whitelisted_caller();
+        fund::<T>(&caller);
+    }: _(RawOrigin::Signed(caller), 1, [0xff; 16], [0; 16], 100u32.into(), 10)
+    verify {
+        assert!(CollectionBids::<T>::contains_key(0));
+    }
+
+    cancel_collection_bid {
+        let caller: T::AccountId = whitelisted_caller();
+        fund::<T>(&caller);
+        KnightModule::<T>::place_collection_bid(
+            RawOrigin::Signed(caller.clone()).into(),
+            1,
+            [0; 16],
+            [0; 16],
+            100u32.into(),
+            10,
+        )?;
+    }: _(RawOrigin::Signed(caller), 0)
+    verify {
+        assert!(!CollectionBids::<T>::contains_key(0));
+    }
+
+    fill_collection_bid {
+        let bidder: T::AccountId = account("bidder", 0, SEED);
+        let caller: T::AccountId = whitelisted_caller();
+        fund::<T>(&bidder);
+        let knight_id = mint_knights::<T>(&caller, 1);
+        let dna = Knights::<T>::get(knight_id).unwrap().dna;
+        KnightModule::<T>::place_collection_bid(
+            RawOrigin::Signed(bidder.clone()).into(),
+            1,
+            [0xff; 16],
+            dna,
+            100u32.into(),
+            2,
+        )?;
+    }: _(RawOrigin::Signed(caller), 0, knight_id)
+    verify {
+        assert_eq!(KnightToOwner::<T>::get(knight_id), Some(bidder));
+    }
+
+    approve {
+        let caller: T::AccountId = whitelisted_caller();
+        let spender: T::AccountId = account("spender", 0, SEED);
+        let knight_id = mint_knights::<T>(&caller, 1);
+    }: _(RawOrigin::Signed(caller), knight_id, spender.clone())
+    verify {
+        assert_eq!(TransferApprovals::<T>::get(knight_id), Some(spender));
+    }
+
+    cancel_approval {
+        let caller: T::AccountId = whitelisted_caller();
+        let spender: T::AccountId = account("spender", 0, SEED);
+        let knight_id = mint_knights::<T>(&caller, 1);
+        KnightModule::<T>::approve(RawOrigin::Signed(caller.clone()).into(), knight_id, spender)?;
+    }: _(RawOrigin::Signed(caller), knight_id)
+    verify {
+        assert_eq!(TransferApprovals::<T>::get(knight_id), None);
+    }
+
+    set_approval_for_all {
+        let caller: T::AccountId = whitelisted_caller();
+        let operator: T::AccountId = account("operator", 0, SEED);
+    }: _(RawOrigin::Signed(caller.clone()), operator.clone(), true)
+    verify {
+        assert!(OperatorApprovals::<T>::get(&caller, &operator));
+    }
+
+    transfer_from {
+        // the approval check reaches the operator lookup last
+        let owner: T::AccountId = account("owner", 0, SEED);
+        let to: T::AccountId = account("to", 0, SEED);
+        let caller: T::AccountId = whitelisted_caller();
+        let knight_id = mint_knights::<T>(&owner, 1);
+        KnightModule::<T>::set_approval_for_all(
+            RawOrigin::Signed(owner.clone()).into(),
+            caller.clone(),
+            true,
+        )?;
+    }: _(RawOrigin::Signed(caller), knight_id, owner, to.clone())
+    verify {
+        assert_eq!(KnightToOwner::<T>::get(knight_id), Some(to));
+    }
+
+    deposit_wealth {
+        let caller: T::AccountId = whitelisted_caller();
+        fund::<T>(&caller);
+        let knight_id = mint_knights::<T>(&caller, 1);
+        let amount: T::Balance = 1_000_000u32.into();
+    }: _(RawOrigin::Signed(caller.clone()), knight_id, amount)
+    verify {
+        assert_eq!(Knights::<T>::get(knight_id).unwrap().wealth, amount);
+    }
+
+    withdraw_wealth {
+        let caller: T::AccountId = whitelisted_caller();
+        fund::<T>(&caller);
+        let knight_id = mint_knights::<T>(&caller, 1);
+        KnightModule::<T>::deposit_wealth(
+            RawOrigin::Signed(caller.clone()).into(),
+            knight_id,
+            1_000_000u32.into(),
+        )
+        .expect("Deposits wealth");
+    }: _(RawOrigin::Signed(caller.clone()), knight_id, 400_000u32.into())
+    verify {
+        assert_eq!(Knights::<T>::get(knight_id).unwrap().wealth, 600_000u32.into());
+    }
+
+    challenge {
+        let caller: T::AccountId = whitelisted_caller();
+        fund::<T>(&caller);
+        let knight_id = mint_knights::<T>(&caller, 1);
+        let opponent: T::AccountId = account("opponent", 0, SEED);
+        let opponent_id = mint_knights::<T>(&opponent, 1);
+    }: _(RawOrigin::Signed(caller.clone()), knight_id, opponent_id, 100u32.into())
+    verify {
+        assert!(DuelChallenges::<T>::contains_key(knight_id));
+        assert_eq!(KnightLocks::<T>::get(knight_id), Some(KnightLock::Duel));
+    }
+
+    cancel_challenge {
+        let caller: T::AccountId = whitelisted_caller();
+        fund::<T>(&caller);
+        let knight_id = mint_knights::<T>(&caller, 1);
+        let opponent: T::AccountId = account("opponent", 0, SEED);
+        let opponent_id = mint_knights::<T>(&opponent, 1);
+        KnightModule::<T>::challenge(
+            RawOrigin::Signed(caller.clone()).into(),
+            knight_id,
+            opponent_id,
+            100u32.into(),
+        )
+        .expect("Challenges knight");
+    }: _(RawOrigin::Signed(caller.clone()), knight_id)
+    verify {
+        assert!(!DuelChallenges::<T>::contains_key(knight_id));
+        assert_eq!(KnightLocks::<T>::get(knight_id), None);
+    }
+
+    accept_challenge {
+        let challenger: T::AccountId = account("challenger", 0, SEED);
+        fund::<T>(&challenger);
+        let challenger_id = mint_knights::<T>(&challenger, 1);
+        let caller: T::AccountId = whitelisted_caller();
+        fund::<T>(&caller);
+        let knight_id = mint_knights::<T>(&caller, 1);
+        KnightModule::<T>::challenge(
+            RawOrigin::Signed(challenger.clone()).into(),
+            challenger_id,
+            knight_id,
+            100u32.into(),
+        )
+        .expect("Challenges knight");
+    }: _(RawOrigin::Signed(caller.clone()), challenger_id, 100u32.into())
+    verify {
+        assert_eq!(AcceptedDuels::<T>::get(challenger_id), Some(caller));
+        assert_eq!(KnightLocks::<T>::get(knight_id), Some(KnightLock::Duel));
+    }
+
+    open_tournament {
+        let caller: T::AccountId = whitelisted_caller();
+    }: _(
+        RawOrigin::Signed(caller.clone()),
+        100u32.into(),
+        T::MaxTournamentSize::get(),
+        registration_end::<T>()
+    )
+    verify {
+        assert!(Tournaments::<T>::contains_key(0));
+    }
+
+    enter_tournament {
+        // filling the bracket does the most work
+        let organiser: T::AccountId = account("organiser", 0, SEED);
+        tournament_with_entrants::<T>(&organiser, 2, 1);
+        let caller: T::AccountId = whitelisted_caller();
+        fund::<T>(&caller);
+        let knight_id = mint_knights::<T>(&caller, 1);
+    }: _(RawOrigin::Signed(caller.clone()), 0, knight_id)
+    verify {
+        assert_eq!(KnightLocks::<T>::get(knight_id), Some(KnightLock::Tournament));
+        assert_eq!(ActiveTournaments::<T>::get(), vec![0]);
+    }
+
+    cancel_tournament {
+        let n in 1 .. T::MaxTournamentSize::get() - 1;
+
+        let caller: T::AccountId = whitelisted_caller();
+        tournament_with_entrants::<T>(&caller, T::MaxTournamentSize::get(), n);
+    }: _(RawOrigin::Signed(caller.clone()), 0)
+    verify {
+        assert!(!Tournaments::<T>::contains_key(0));
+    }
+
+    fight_tournament_round {
+        let n in 2 .. T::MaxTournamentSize::get();
+
+        let organiser: T::AccountId = account("organiser", 0, SEED);
+        let mut contenders = Vec::new();
+        for i in 0 .. n {
+            let entrant: T::AccountId = account("entrant", i, SEED);
+            let knight_id = mint_knights::<T>(&entrant, 1);
+            KnightLocks::<T>::insert(knight_id, KnightLock::Tournament);
+            contenders.push(knight_id);
+        }
+        Tournaments::<T>::insert(0, Tournament {
+            organiser,
+            entry_fee: 100u32.into(),
+            size: n,
+            contenders,
+            round: 1,
+            registration_end: registration_end::<T>(),
+        });
+        ActiveTournaments::<T>::put(vec![0]);
+    }: {
+        KnightModule::<T>::_fight_tournament_round(0);
+    }
+    verify {
+        let remaining = Tournaments::<T>::get(0).map_or(1, |tournament| tournament.contenders.len() as u32);
+        assert_eq!(remaining, (n + 1) / 2);
+    }
+
+    found_order {
+        let n in 1 .. T::MaxNameLength::get();
+
+        let caller: T::AccountId = whitelisted_caller();
+    }: _(RawOrigin::Signed(caller.clone()), name(n), order_rules::<T>())
+    verify {
+        assert!(Orders::<T>::contains_key(0));
+    }
+
+    enroll_knight {
+        let founder: T::AccountId = account("founder", 0, SEED);
+        found_order::<T>(&founder);
+        let caller: T::AccountId = whitelisted_caller();
+        fund::<T>(&caller);
+        let knight_id = mint_knights::<T>(&caller, 1);
+    }: _(RawOrigin::Signed(caller.clone()), 0, knight_id)
+    verify {
+        assert_eq!(KnightOrders::<T>::get(knight_id), Some(0));
+    }
+
+    leave_order {
+        let n in 1 .. T::MaxOpenSpends::get();
+
+        // an officer that voted on every open spend does the most work
+        let caller: T::AccountId = whitelisted_caller();
+        found_order::<T>(&caller);
+        let knight_id = enroll::<T>(&caller);
+        KnightModule::<T>::appoint_officer(RawOrigin::Signed(caller.clone()).into(), 0, knight_id)?;
+        let beneficiary: T::AccountId = account("beneficiary", 0, SEED);
+        for proposal_id in 0 .. n as u64 {
+            KnightModule::<T>::propose_spend(
+                RawOrigin::Signed(caller.clone()).into(),
+                knight_id,
+                beneficiary.clone(),
+                100u32.into(),
+            )?;
+            KnightModule::<T>::vote_on_spend(
+                RawOrigin::Signed(caller.clone()).into(),
+                proposal_id,
+                knight_id,
+                true,
+            )?;
+        }
+    }: _(RawOrigin::Signed(caller.clone()), knight_id)
+    verify {
+        assert_eq!(KnightOrders::<T>::get(knight_id), None);
+        assert_eq!(SpendProposals::<T>::get(0).unwrap().ayes, 0);
+    }
+
+    appoint_officer {
+        let caller: T::AccountId = whitelisted_caller();
+        found_order::<T>(&caller);
+        let knight_id = enroll::<T>(&caller);
+    }: _(RawOrigin::Signed(caller.clone()), 0, knight_id)
+    verify {
+        assert_eq!(Orders::<T>::get(0).unwrap().officers, vec![knight_id]);
+    }
+
+    dismiss_officer {
+        let caller: T::AccountId = whitelisted_caller();
+        found_order::<T>(&caller);
+        let knight_id = enroll::<T>(&caller);
+        KnightModule::<T>::appoint_officer(RawOrigin::Signed(caller.clone()).into(), 0, knight_id)?;
+    }: _(RawOrigin::Signed(caller.clone()), 0, knight_id)
+    verify {
+        assert!(Orders::<T>::get(0).unwrap().officers.is_empty());
+    }
+
+    propose_spend {
+        let caller: T::AccountId = whitelisted_caller();
+        found_order::<T>(&caller);
+        let knight_id = enroll::<T>(&caller);
+        KnightModule::<T>::appoint_officer(RawOrigin::Signed(caller.clone()).into(), 0, knight_id)?;
+        let beneficiary: T::AccountId = account("beneficiary", 0, SEED);
+    }: _(RawOrigin::Signed(caller.clone()), knight_id, beneficiary, 100u32.into())
+    verify {
+        assert!(SpendProposals::<T>::contains_key(0));
+    }
+
+    vote_on_spend {
+        // changing a vote does the most work
+        let caller: T::AccountId = whitelisted_caller();
+        found_order::<T>(&caller);
+        let knight_id = enroll::<T>(&caller);
+        KnightModule::<T>::appoint_officer(RawOrigin::Signed(caller.clone()).into(), 0, knight_id)?;
+        let beneficiary: T::AccountId = account("beneficiary", 0, SEED);
+        KnightModule::<T>::propose_spend(
+            RawOrigin::Signed(caller.clone()).into(),
+            knight_id,
+            beneficiary,
+            100u32.into(),
+        )?;
+        KnightModule::<T>::vote_on_spend(RawOrigin::Signed(caller.clone()).into(), 0, knight_id, false)?;
+    }: _(RawOrigin::Signed(caller.clone()), 0, knight_id, true)
+    verify {
+        assert_eq!(SpendVotes::<T>::get(0, knight_id), Some(true));
+    }
+
+    close_spend {
+        let n in 1 .. T::MaxOrderMembers::get();
+
+        let founder: T::AccountId = account("founder", 0, SEED);
+        found_order::<T>(&founder);
+        let mut voters = Vec::new();
+        for i in 0 .. n {
+            let member: T::AccountId = account("member", i, SEED);
+            voters.push((member.clone(), enroll::<T>(&member)));
+        }
+        let (officer, officer_knight) = voters[0].clone();
+        KnightModule::<T>::appoint_officer(RawOrigin::Signed(founder).into(), 0, officer_knight)?;
+        let beneficiary: T::AccountId = account("beneficiary", 0, SEED);
+        KnightModule::<T>::propose_spend(
+            RawOrigin::Signed(officer).into(),
+            officer_knight,
+            beneficiary.clone(),
+            100u32.into(),
+        )?;
+        for (member, knight_id) in voters {
+            KnightModule::<T>::vote_on_spend(RawOrigin::Signed(member).into(), 0, knight_id, true)?;
+        }
+        let end = frame_system::Pallet::<T>::block_number() + T::OrderVotingPeriod::get() + One::one();
+        frame_system::Pallet::<T>::set_block_number(end);
+        let caller: T::AccountId = whitelisted_caller();
+    }: _(RawOrigin::Signed(caller.clone()), 0)
+    verify {
+        assert!(!SpendProposals::<T>::contains_key(0));
+        assert!(OpenSpends::<T>::get(0).is_empty());
+        assert_eq!(
+            <pallet_balances::Pallet<T> as Currency<_>>::free_balance(&beneficiary),
+            100u32.into()
+        );
+    }
+
+    fight_duel {
+        let challenger: T::AccountId = account("challenger", 0, SEED);
+        fund::<T>(&challenger);
+        let challenger_id = mint_knights::<T>(&challenger, 1);
+        let caller: T::AccountId = whitelisted_caller();
+        fund::<T>(&caller);
+        let knight_id = mint_knights::<T>(&caller, 1);
+        KnightModule::<T>::challenge(
+            RawOrigin::Signed(challenger).into(),
+            challenger_id,
+            knight_id,
+            100u32.into(),
+        )?;
+        KnightModule::<T>::accept_challenge(
+            RawOrigin::Signed(caller).into(),
+            challenger_id,
+            100u32.into(),
+        )?;
+    }: {
+        KnightModule::<T>::_fight_duel(challenger_id);
+    }
+    verify {
+        assert!(!DuelChallenges::<T>::contains_key(challenger_id));
+        let record = DuelRecords::<T>::get(knight_id);
+        assert_eq!(record.wins + record.losses, 1);
+    }
+
+    leave_tournament {
+        // the last entrant to arrive is the furthest to find
+        let n in 1 .. T::MaxTournamentSize::get() - 2;
+
+        let organiser: T::AccountId = account("organiser", 0, SEED);
+        tournament_with_entrants::<T>(&organiser, T::MaxTournamentSize::get(), n);
+        let caller: T::AccountId = whitelisted_caller();
+        fund::<T>(&caller);
+        let knight_id = mint_knights::<T>(&caller, 1);
+        KnightModule::<T>::enter_tournament(RawOrigin::Signed(caller.clone()).into(), 0, knight_id)?;
+    }: _(RawOrigin::Signed(caller.clone()), 0, knight_id)
+    verify {
+        assert_eq!(KnightLocks::<T>::get(knight_id), None);
+        assert_eq!(Tournaments::<T>::get(0).unwrap().contenders.len() as u32, n);
+    }
+
+    close_registration {
+        let n in 1 .. T::MaxTournamentSize::get() - 1;
+
+        let organiser: T::AccountId = account("organiser", 0, SEED);
+        tournament_with_entrants::<T>(&organiser, T::MaxTournamentSize::get(), n);
+    }: {
+        KnightModule::<T>::_close_registration(0);
+    }
+    verify {
+        assert!(!Tournaments::<T>::contains_key(0));
+    }
+}
+
+impl_benchmark_test_suite!(KnightModule, crate::mock::new_test_ext(), crate::mock::Test,);
@@ -0,0 +1,172 @@
+//! Decoding of knight DNA into named traits.
+//!
+//! Bytes 0-7 of a knight's DNA set its stats (see `Pallet::base_stats`). The
+//! next four set how it looks, one byte each:
+//!
+//! | byte | trait    |
+//! |------|----------|
+//! | 8    | `Helm`   |
+//! | 9    | `Crest`  |
+//! | 10   | `Steed`  |
+//! | 11   | `Colour` |
+//!
+//! Each trait splits the byte's 256 values into uneven ranges, so some variants
+//! are born rarer than others. Bytes 12-15 are unused.
+
+use codec::{Decode, Encode};
+
+/// The DNA byte each trait is read from.
+pub const HELM_GENE: usize = 8;
+pub const CREST_GENE: usize = 9;
+pub const STEED_GENE: usize = 10;
+pub const COLOUR_GENE: usize = 11;
+
+#[derive(Encode, Decode, Clone, Copy, PartialEq, Eq)]
+#[cfg_attr(feature = "std", derive(Debug))]
+pub enum Helm {
+    /// 0-99
+    Kettle,
+    /// 100-169
+    Barbute,
+    /// 170-219
+    Bascinet,
+    /// 220-249
+    GreatHelm,
+    /// 250-255
+    Crowned,
+}
+
+impl Helm {
+    pub fn from_gene(gene: u8) -> Self {
+        match gene {
+            0..=99 => Helm::Kettle,
+            100..=169 => Helm::Barbute,
+            170..=219 => Helm::Bascinet,
+            220..=249 => Helm::GreatHelm,
+            _ => Helm::Crowned,
+        }
+    }
+}
+
+#[derive(Encode, Decode, Clone, Copy, PartialEq, Eq)]
+#[cfg_attr(feature = "std", derive(Debug))]
+pub enum Crest {
+    /// 0-89
+    Plain,
+    /// 90-149
+    Lion,
+    /// 150-199
+    Eagle,
+    /// 200-239
+    Stag,
+    /// 240-255
+    Dragon,
+}
+
+impl Crest {
+    pub fn from_gene(gene: u8) -> Self {
+        match gene {
+            0..=89 => Crest::Plain,
+            90..=149 => Crest::Lion,
+            150..=199 => Crest::Eagle,
+            200..=239 => Crest::Stag,
+            _ => Crest::Dragon,
+        }
+    }
+}
+
+#[derive(Encode, Decode, Clone, Copy, PartialEq, Eq)]
+#[cfg_attr(feature = "std", derive(Debug))]
+pub enum Steed {
+    /// 0-109
+    Palfrey,
+    /// 110-179
+    Courser,
+    /// 180-244
+    Destrier,
+    /// 245-255
+    Unicorn,
+}
+
+impl Steed {
+    pub fn from_gene(gene: u8) -> Self {
+        match gene {
+            0..=109 => Steed::Palfrey,
+            110..=179 => Steed::Courser,
+            180..=244 => Steed::Destrier,
+            _ => Steed::Unicorn,
+        }
+    }
+}
+
+/// The heraldic tincture of a knight's surcoat.
+#[derive(Encode, Decode, Clone, Copy, PartialEq, Eq)]
+#[cfg_attr(feature = "std", derive(Debug))]
+pub enum Colour {
+    /// 0-63
+    Argent,
+    /// 64-127
+    Gules,
+    /// 128-175
+    Azure,
+    /// 176-215
+    Vert,
+    /// 216-243
+    Sable,
+    /// 244-255
+    Or,
+}
+
+impl Colour {
+    pub fn from_gene(gene: u8) -> Self {
+        match gene {
+            0..=63 => Colour::Argent,
+            64..=127 => Colour::Gules,
+            128..=175 => Colour::Azure,
+            176..=215 => Colour::Vert,
+            216..=243 => Colour::Sable,
+            _ => Colour::Or,
+        }
+    }
+}
+
+/// One trait a knight can have. Used to key trait frequencies.
+#[derive(Encode, Decode, Clone, Copy, PartialEq, Eq)]
+#[cfg_attr(feature = "std", derive(Debug))]
+pub enum KnightTrait {
+    Helm(Helm),
+    Crest(Crest),
+    Steed(Steed),
+    Colour(Colour),
+}
+
+/// Everything a knight's DNA says about how it looks.
+#[derive(Encode, Decode, Clone, Copy, PartialEq, Eq)]
+#[cfg_attr(feature = "std", derive(Debug))]
+pub struct Traits {
+    pub helm: Helm,
+    pub crest: Crest,
+    pub steed: Steed,
+    pub colour: Colour,
+}
+
+impl Traits {
+    pub fn from_dna(dna: &[u8; 16]) -> Self {
+        Traits {
+            helm: Helm::from_gene(dna[HELM_GENE]),
+            crest: Crest::from_gene(dna[CREST_GENE]),
+            steed: Steed::from_gene(dna[STEED_GENE]),
+            colour: Colour::from_gene(dna[COLOUR_GENE]),
+        }
+    }
+
+    /// Each of the traits on its own, for looking up frequencies.
+    pub fn all(&self) -> [KnightTrait; 4] {
+        [
+            KnightTrait::Helm(self.helm),
+            KnightTrait::Crest(self.crest),
+            KnightTrait::Steed(self.steed),
+            KnightTrait::Colour(self.colour),
+        ]
+    }
+}
@@ -17,6 +17,11 @@ mod tests;
 #[cfg(feature = "runtime-benchmarks")]
 mod benchmarking;
 
+pub mod dna;
+pub mod migrations;
+pub mod weights;
+pub use weights::WeightInfo;
+
 #[frame_support::pallet]
 pub mod pallet {
     use frame_support::traits::Vec;
@@ -25,9 +30,20 @@ pub mod pallet {
 
     use frame_support::dispatch::Parameter;
     use frame_support::traits::Currency;
+    use frame_support::traits::ExistenceRequirement;
     use frame_support::traits::Randomness;
+    use frame_support::traits::{BalanceStatus, OnUnbalanced, ReservableCurrency, WithdrawReasons};
+    use frame_support::transactional;
     use sp_core::H256;
-    use sp_runtime::traits::{AtLeast32BitUnsigned, Bounded, CheckedAdd, CheckedSub, One, Zero};
+    use sp_runtime::traits::AccountIdConversion;
+    use sp_runtime::traits::{
+        AtLeast32BitUnsigned, Bounded, CheckedAdd, CheckedSub, Hash as HashT, IntegerSquareRoot,
+        One, SaturatedConversion, Saturating, Zero,
+    };
+    use sp_runtime::{ModuleId, Perbill};
+
+    use crate::dna::{KnightTrait, Traits};
+    use crate::weights::WeightInfo;
 
     // thx to macro magic, we get to directly call this trait function
     use sp_io::hashing::blake2_128;
@@ -40,7 +56,7 @@ pub mod pallet {
 
         // type Currency: Currency<<Self as frame_system::Config>::AccountId>;
         // or...
-        type Currency: Currency<Self::AccountId>;
+        type Currency: ReservableCurrency<Self::AccountId, Balance = Self::Balance>;
         type RandomnessSource: Randomness<H256>;
         type KnightIndex: Parameter
             + AtLeast32BitUnsigned
@@ -51,6 +67,132 @@ pub mod pallet {
             + CheckedSub
             + Encode
             + Decode;
+
+        /// Blocks a generation 1 knight must rest after squiring before it can squire again.
+        #[pallet::constant]
+        type BaseSquireCooldown: Get<Self::BlockNumber>;
+
+        /// Extra blocks of rest added for every generation after the first.
+        #[pallet::constant]
+        type SquireCooldownPerGeneration: Get<Self::BlockNumber>;
+
+        /// The longest name, in bytes, a knight may be given.
+        #[pallet::constant]
+        type MaxNameLength: Get<u32>;
+
+        /// The shortest an auction may run, in blocks.
+        #[pallet::constant]
+        type MinAuctionDuration: Get<Self::BlockNumber>;
+
+        /// The longest an auction may run, in blocks.
+        #[pallet::constant]
+        type MaxAuctionDuration: Get<Self::BlockNumber>;
+
+        /// How many auctions may end in the same block.
+        /// Bounds the work done settling auctions in `on_initialize`.
+        #[pallet::constant]
+        type MaxAuctionsPerBlock: Get<u32>;
+
+        /// How many blocks sealed-bid auction bidders have to reveal their bids.
+        #[pallet::constant]
+        type RevealPeriod: Get<Self::BlockNumber>;
+
+        /// How many bids a sealed-bid auction takes.
+        /// Bounds the work done settling it in `on_initialize`.
+        #[pallet::constant]
+        type MaxSealedBids: Get<u32>;
+
+        /// How many blocks an offer on a knight stands before it expires.
+        #[pallet::constant]
+        type OfferDuration: Get<Self::BlockNumber>;
+
+        /// How many offers may expire in the same block.
+        /// Bounds the work done expiring offers in `on_initialize`.
+        #[pallet::constant]
+        type MaxOffersPerBlock: Get<u32>;
+
+        /// The smallest offer that may be made on a knight. Keeps free offers from
+        /// taking up a block's `MaxOffersPerBlock` slots.
+        #[pallet::constant]
+        type MinOfferAmount: Get<Self::Balance>;
+
+        /// The largest share of each sale a creator may take as royalty.
+        #[pallet::constant]
+        type MaxRoyalty: Get<Perbill>;
+
+        /// The share of each `buy_knight` price taken as a marketplace fee.
+        #[pallet::constant]
+        type MarketplaceFee: Get<Perbill>;
+
+        /// Where marketplace fees go, e.g. the treasury.
+        type OnMarketplaceFee: OnUnbalanced<NegativeImbalanceOf<Self>>;
+
+        /// Derives each knight's vault account, which holds the knight's wealth.
+        #[pallet::constant]
+        type ModuleId: Get<ModuleId>;
+
+        /// The largest bracket a tournament may have.
+        /// Bounds the work done fighting a round in `on_initialize`.
+        #[pallet::constant]
+        type MaxTournamentSize: Get<u32>;
+
+        /// Tournament rounds are fought at the start of every block that is a
+        /// multiple of this. Must not be zero.
+        #[pallet::constant]
+        type TournamentRoundInterval: Get<Self::BlockNumber>;
+
+        /// How many tournaments may be underway at once.
+        /// Bounds the work done fighting rounds in `on_initialize`.
+        #[pallet::constant]
+        type MaxActiveTournaments: Get<u32>;
+
+        /// The longest a tournament may take entrants for.
+        #[pallet::constant]
+        type MaxRegistrationPeriod: Get<Self::BlockNumber>;
+
+        /// How many tournaments' registration may close in the same block.
+        /// Bounds the work done cancelling tournaments in `on_initialize`.
+        #[pallet::constant]
+        type MaxRegistrationsPerBlock: Get<u32>;
+
+        /// The share of a tournament's prize pool paid to the runner-up.
+        /// The champion takes the rest.
+        #[pallet::constant]
+        type RunnerUpPrize: Get<Perbill>;
+
+        /// Experience the winner of a duel gains.
+        #[pallet::constant]
+        type DuelExperience: Get<u64>;
+
+        /// How many accepted duels may be fought in the same block.
+        /// Bounds the work done fighting duels in `on_initialize`.
+        #[pallet::constant]
+        type MaxDuelsPerBlock: Get<u32>;
+
+        /// The furthest back or forward, in generations, a lineage query will look.
+        #[pallet::constant]
+        type MaxLineageDepth: Get<u32>;
+
+        /// The most knights an order may enroll.
+        /// Bounds the votes cleared when a spend is closed.
+        #[pallet::constant]
+        type MaxOrderMembers: Get<u32>;
+
+        /// The most officers an order may have.
+        #[pallet::constant]
+        type MaxOrderOfficers: Get<u32>;
+
+        /// How long member knights have to vote on a spend from their order's treasury.
+        #[pallet::constant]
+        type OrderVotingPeriod: Get<Self::BlockNumber>;
+
+        /// How many spends an order may be voting on at once.
+        /// Bounds the votes withdrawn when a knight leaves its order.
+        #[pallet::constant]
+        type MaxOpenSpends: Get<u32>;
+
+        /// Weight information for extrinsics in this pallet.
+        type WeightInfo: WeightInfo;
     }
 
     #[pallet::pallet]
@@ -59,19 +201,359 @@ pub mod pallet {
 
     #[derive(Encode, Decode, Clone, PartialEq, Eq)]
     #[cfg_attr(feature = "std", derive(Debug))]
-    pub struct Knight<Balance, KnightIndex> {
+    pub struct Knight<Balance, KnightIndex, BlockNumber> {
         pub id: KnightIndex,
         pub dna: [u8; 16],
         pub name: Vec<u8>,
+        /// Mirrors the balance of the knight's vault account. See `vault_account`.
         pub wealth: Balance,
         pub price: Balance,
         pub gen: u64,
+        /// The block the knight was minted in.
+        /// Knights minted before `Releases::V2_0_0` report block zero.
+        pub birth_block: BlockNumber,
+        /// The knights this one was squired from, if any.
+        pub parents: Option<(KnightIndex, KnightIndex)>,
+    }
+
+    /// Why a knight is locked. A locked knight cannot change hands, be priced or be burned.
+    #[derive(Encode, Decode, Clone, Copy, PartialEq, Eq)]
+    #[cfg_attr(feature = "std", derive(Debug))]
+    pub enum KnightLock {
+        /// The knight is up for an English auction.
+        Auction,
+        /// The knight is up for a Dutch auction.
+        DutchAuction,
+        /// The knight is up for a sealed-bid auction.
+        SealedAuction,
+        /// The knight has challenged another to a duel.
+        Duel,
+        /// The knight is entered in a tournament.
+        Tournament,
+    }
+
+    /// An English auction: bids rise until the end block and the highest takes the knight.
+    #[derive(Encode, Decode, Clone, PartialEq, Eq)]
+    #[cfg_attr(feature = "std", derive(Debug))]
+    pub struct Auction<AccountId, Balance, BlockNumber> {
+        pub seller: AccountId,
+        /// No bid below this is accepted.
+        pub reserve_price: Balance,
+        /// The auction is settled at the start of this block.
+        pub end: BlockNumber,
+        /// The highest bidder and their bid, which is held in reserve.
+        pub highest_bid: Option<(AccountId, Balance)>,
+    }
+
+    pub type AuctionOf<T> = Auction<
+        <T as frame_system::Config>::AccountId,
+        <T as pallet_balances::Config>::Balance,
+        <T as frame_system::Config>::BlockNumber,
+    >;
+
+    /// A Dutch auction: the price falls from `start_price` to `floor_price` over
+    /// `duration` blocks and the first buyer to meet it takes the knight.
+    #[derive(Encode, Decode, Clone, PartialEq, Eq)]
+    #[cfg_attr(feature = "std", derive(Debug))]
+    pub struct DutchAuction<AccountId, Balance, BlockNumber> {
+        pub seller: AccountId,
+        pub start_price: Balance,
+        /// The price stays here once `duration` has passed.
+        pub floor_price: Balance,
+        pub start: BlockNumber,
+        pub duration: BlockNumber,
+    }
+
+    pub type DutchAuctionOf<T> = DutchAuction<
+        <T as frame_system::Config>::AccountId,
+        <T as pallet_balances::Config>::Balance,
+        <T as frame_system::Config>::BlockNumber,
+    >;
+
+    /// A sealed-bid, second-price auction. Bids are committed as hashes until
+    /// `commit_end`, revealed until `reveal_end`, and settled at the start of `reveal_end`.
+    #[derive(Encode, Decode, Clone, PartialEq, Eq)]
+    #[cfg_attr(feature = "std", derive(Debug))]
+    pub struct SealedAuction<AccountId, Balance, BlockNumber> {
+        pub seller: AccountId,
+        pub reserve_price: Balance,
+        pub commit_end: BlockNumber,
+        pub reveal_end: BlockNumber,
+        /// How many bids have been committed.
+        pub bid_count: u32,
+        /// The highest revealed bid so far. Ties go to whoever revealed first.
+        pub highest_bid: Option<(AccountId, Balance)>,
+        /// The runner-up's revealed bid, which sets the price.
+        pub second_bid: Option<Balance>,
+    }
+
+    pub type SealedAuctionOf<T> = SealedAuction<
+        <T as frame_system::Config>::AccountId,
+        <T as pallet_balances::Config>::Balance,
+        <T as frame_system::Config>::BlockNumber,
+    >;
+
+    /// A committed bid in a sealed-bid auction.
+    #[derive(Encode, Decode, Clone, PartialEq, Eq)]
+    #[cfg_attr(feature = "std", derive(Debug))]
+    pub struct SealedBid<Hash, Balance> {
+        /// The hash of `(bid, salt)`.
+        pub commitment: Hash,
+        /// Held in reserve until settlement. Caps the bid that may be revealed,
+        /// and is slashed if the bid is never revealed.
+        pub deposit: Balance,
+        pub revealed: bool,
+    }
+
+    pub type SealedBidOf<T> =
+        SealedBid<<T as frame_system::Config>::Hash, <T as pallet_balances::Config>::Balance>;
+
+    /// A standing offer to buy a knight, whether or not it is for sale.
+    #[derive(Encode, Decode, Clone, PartialEq, Eq)]
+    #[cfg_attr(feature = "std", derive(Debug))]
+    pub struct Offer<Balance, BlockNumber> {
+        /// Held in reserve until the offer is accepted, cancelled or expires.
+        pub amount: Balance,
+        /// The offer is withdrawn at the start of this block.
+        pub expires: BlockNumber,
+    }
+
+    pub type OfferOf<T> =
+        Offer<<T as pallet_balances::Config>::Balance, <T as frame_system::Config>::BlockNumber>;
+
+    /// Where a buyer's payment comes from.
+    #[derive(Clone, Copy)]
+    pub(crate) enum Payment {
+        /// The buyer's free balance.
+        Free,
+        /// Funds the buyer already has in reserve for this purchase.
+        Reserved,
+    }
+
+    /// A standing bid on any knight of a given generation, optionally narrowed to
+    /// knights whose DNA matches `dna_pattern` on the bits set in `dna_mask`.
+    #[derive(Encode, Decode, Clone, PartialEq, Eq)]
+    #[cfg_attr(feature = "std", derive(Debug))]
+    pub struct CollectionBid<AccountId, Balance> {
+        pub bidder: AccountId,
+        pub gen: u64,
+        /// An all-zero mask matches any DNA.
+        pub dna_mask: [u8; 16],
+        pub dna_pattern: [u8; 16],
+        /// Paid for each knight. `price * remaining` is held in reserve.
+        pub price: Balance,
+        /// How many more knights the bidder will take.
+        pub remaining: u32,
+    }
+
+    pub type CollectionBidOf<T> = CollectionBid<
+        <T as frame_system::Config>::AccountId,
+        <T as pallet_balances::Config>::Balance,
+    >;
+
+    /// Who minted a knight, and the share of every later sale they are owed.
+    #[derive(Encode, Decode, Clone, PartialEq, Eq)]
+    #[cfg_attr(feature = "std", derive(Debug))]
+    pub struct Creator<AccountId> {
+        pub account: AccountId,
+        pub royalty: Perbill,
+    }
+
+    pub type NegativeImbalanceOf<T> = <<T as Config>::Currency as Currency<
+        <T as frame_system::Config>::AccountId,
+    >>::NegativeImbalance;
+
+    /// A standing challenge from one knight to another.
+    #[derive(Encode, Decode, Clone, PartialEq, Eq)]
+    #[cfg_attr(feature = "std", derive(Debug))]
+    pub struct DuelChallenge<AccountId, KnightIndex, Balance> {
+        /// The challenging knight's owner, whose wager is held in reserve.
+        pub challenger: AccountId,
+        /// The knight challenged. Whoever owns it may accept.
+        pub opponent: KnightIndex,
+        /// Staked by each side. The winner takes both.
+        pub wager: Balance,
+    }
+
+    pub type DuelChallengeOf<T> = DuelChallenge<
+        <T as frame_system::Config>::AccountId,
+        <T as Config>::KnightIndex,
+        <T as pallet_balances::Config>::Balance,
+    >;
+
+    /// How a knight has fared in duels.
+    #[derive(Encode, Decode, Clone, Copy, Default, PartialEq, Eq)]
+    #[cfg_attr(feature = "std", derive(Debug))]
+    pub struct DuelRecord {
+        pub wins: u32,
+        pub losses: u32,
+    }
+
+    /// A single-elimination tournament.
+    #[derive(Encode, Decode, Clone, PartialEq, Eq)]
+    #[cfg_attr(feature = "std", derive(Debug))]
+    pub struct Tournament<AccountId, KnightIndex, Balance, BlockNumber> {
+        pub organiser: AccountId,
+        /// Paid into the prize pool by each entrant.
+        pub entry_fee: Balance,
+        /// How many knights the bracket takes. Always a power of two.
+        pub size: u32,
+        /// The knights still in: entrants in order of entry while registration is
+        /// open, then the survivors in bracket order, where neighbours fight each other.
+        pub contenders: Vec<KnightIndex>,
+        /// The round fought next. Zero while registration is open.
+        pub round: u32,
+        /// The tournament is cancelled if its bracket is not full by this block.
+        pub registration_end: BlockNumber,
+    }
+
+    pub type TournamentOf<T> = Tournament<
+        <T as frame_system::Config>::AccountId,
+        <T as Config>::KnightIndex,
+        <T as pallet_balances::Config>::Balance,
+        <T as frame_system::Config>::BlockNumber,
+    >;
+
+    /// How many points a class adds to the stat it favours.
+    pub const CLASS_BONUS: u16 = 20;
+
+    /// How many points every stat gains for each level above the first.
+    pub const LEVEL_BONUS: u16 = 5;
+
+    /// Reaching level `n + 1` takes `EXPERIENCE_PER_LEVEL * n * n` experience.
+    pub const EXPERIENCE_PER_LEVEL: u64 = 100;
+
+    /// A knight's fighting attributes. See `Pallet::base_stats` for how they are
+    /// read from DNA.
+    #[derive(Encode, Decode, Clone, Copy, Default, PartialEq, Eq)]
+    #[cfg_attr(feature = "std", derive(Debug))]
+    pub struct Stats {
+        pub strength: u16,
+        pub agility: u16,
+        pub valor: u16,
+        pub endurance: u16,
+    }
+
+    /// A calling a knight may be given when minted. Each favours one stat.
+    #[derive(Encode, Decode, Clone, Copy, PartialEq, Eq)]
+    #[cfg_attr(feature = "std", derive(Debug))]
+    pub enum KnightClass {
+        /// Favours strength.
+        Warrior,
+        /// Favours agility.
+        Ranger,
+        /// Favours valor.
+        Paladin,
+        /// Favours endurance.
+        Guardian,
+    }
+
+    /// What a trait shared by every knight adds to a rarity score.
+    pub const RARITY_SCALE: u64 = 100;
+
+    /// What a knight needs to join an order.
+    #[derive(Encode, Decode, Clone, PartialEq, Eq)]
+    #[cfg_attr(feature = "std", derive(Debug))]
+    pub struct OrderRules<Balance> {
+        /// Paid into the order's treasury by a knight's owner when it is enrolled.
+        pub dues: Balance,
+        /// The lowest level a knight may join at.
+        pub min_level: u32,
+        /// If set, only knights of this class may join.
+        pub class: Option<KnightClass>,
+    }
+
+    /// A knightly order. Its members are knights, whoever happens to own them.
+    #[derive(Encode, Decode, Clone, PartialEq, Eq)]
+    #[cfg_attr(feature = "std", derive(Debug))]
+    pub struct Order<AccountId, KnightIndex, Balance> {
+        /// Appoints and dismisses officers.
+        pub founder: AccountId,
+        pub name: Vec<u8>,
+        pub rules: OrderRules<Balance>,
+        /// Member knights whose owners may propose spends from the treasury.
+        pub officers: Vec<KnightIndex>,
+        /// How many knights are enrolled.
+        pub members: u32,
+    }
+
+    pub type OrderOf<T> = Order<
+        <T as frame_system::Config>::AccountId,
+        <T as Config>::KnightIndex,
+        <T as pallet_balances::Config>::Balance,
+    >;
+
+    /// A proposal to pay out of an order's treasury, put to a vote of its member knights.
+    #[derive(Encode, Decode, Clone, PartialEq, Eq)]
+    #[cfg_attr(feature = "std", derive(Debug))]
+    pub struct SpendProposal<AccountId, KnightIndex, Balance, BlockNumber> {
+        pub order_id: u64,
+        /// The officer knight that proposed the spend.
+        pub proposer: KnightIndex,
+        pub beneficiary: AccountId,
+        pub amount: Balance,
+        /// Knights enrolled after this block may not vote.
+        pub proposed_at: BlockNumber,
+        pub ayes: u32,
+        pub nays: u32,
+    }
+
+    pub type SpendProposalOf<T> = SpendProposal<
+        <T as frame_system::Config>::AccountId,
+        <T as Config>::KnightIndex,
+        <T as pallet_balances::Config>::Balance,
+        <T as frame_system::Config>::BlockNumber,
+    >;
+
+    /// Convenience alias for a `Knight` using the types of a given runtime.
+    pub type KnightOf<T> = Knight<
+        <T as pallet_balances::Config>::Balance,
+        <T as Config>::KnightIndex,
+        <T as frame_system::Config>::BlockNumber,
+    >;
+
+    /// The storage layouts this pallet has gone through.
+    /// Checked in `on_runtime_upgrade` to decide which migrations to run.
+    #[derive(Encode, Decode, Clone, Copy, PartialEq, Eq, PartialOrd, Ord)]
+    #[cfg_attr(feature = "std", derive(Debug))]
+    pub enum Releases {
+        /// The original layout. `Knight` has no birth block or parents.
+        V1_0_0,
+        /// `Knight` records its birth block and parents.
+        V2_0_0,
+        /// Ownership is indexed by the `OwnedKnights` double map instead of
+        /// a `Vec` per owner.
+        V3_0_0,
+        /// Trait frequencies are tracked in `TraitCounts`.
+        V4_0_0,
+        /// Squires are indexed under their parents in `Children`.
+        V5_0_0,
     }
 
+    impl Default for Releases {
+        fn default() -> Self {
+            Releases::V1_0_0
+        }
+    }
+
+    impl Releases {
+        /// The layout the current code expects.
+        pub const LATEST: Releases = Releases::V5_0_0;
+    }
+
+    /// The storage layout currently on chain.
+    ///
+    /// Chains launched before this was introduced have nothing stored here
+    /// and so read back as `V1_0_0`.
+    #[pallet::storage]
+    #[pallet::getter(fn storage_version)]
+    pub(super) type StorageVersion<T: Config> = StorageValue<_, Releases, ValueQuery>;
+
     #[pallet::storage]
     #[pallet::getter(fn thing)]
     pub type Thing<T: Config> = StorageValue<_, u64, ValueQuery>;
 
+    /// The number of knights in existence. Burned knights are not counted.
     #[pallet::storage]
     #[pallet::getter(fn knight_count)]
     pub type KnightCount<T: Config> = StorageValue<_, u64, ValueQuery>;
@@ -82,31 +564,309 @@ pub mod pallet {
 
     #[pallet::storage]
     #[pallet::getter(fn knights)]
-    pub type Knights<T: Config> = StorageMap<
-        _,
-        Blake2_128Concat,
-        T::KnightIndex,
-        Knight<T::Balance, T::KnightIndex>,
-        OptionQuery,
-    >;
+    pub type Knights<T: Config> =
+        StorageMap<_, Blake2_128Concat, T::KnightIndex, KnightOf<T>, OptionQuery>;
 
     #[pallet::storage]
     #[pallet::getter(fn knight_to_owner)]
     pub type KnightToOwner<T: Config> =
         StorageMap<_, Blake2_128Concat, T::KnightIndex, T::AccountId, OptionQuery>;
 
+    /// The knights each account owns, keyed by `(owner, knight_id)`.
+    ///
+    /// Use `Pallet::knight_ids_of_owner` or `iter_prefix` to list an owner's knights.
     #[pallet::storage]
-    #[pallet::getter(fn owner_to_knights)]
-    pub type OwnerToKnights<T: Config> =
-        StorageMap<_, Blake2_128Concat, T::AccountId, Vec<T::KnightIndex>, ValueQuery>;
+    pub type OwnedKnights<T: Config> = StorageDoubleMap<
+        _,
+        Blake2_128Concat,
+        T::AccountId,
+        Blake2_128Concat,
+        T::KnightIndex,
+        (),
+        OptionQuery,
+    >;
 
     #[pallet::storage]
     #[pallet::getter(fn owner_to_knight_count)]
     pub type OwnerToKnightCount<T: Config> =
         StorageMap<_, Blake2_128Concat, T::AccountId, u64, ValueQuery>;
 
+    /// The block at which a knight may squire again.
+    /// Knights that have never squired have no entry.
+    #[pallet::storage]
+    #[pallet::getter(fn squire_ready_at)]
+    pub type SquireReadyAt<T: Config> =
+        StorageMap<_, Blake2_128Concat, T::KnightIndex, T::BlockNumber, OptionQuery>;
+
+    /// Accounts the owner of a knight has allowed to squire with it.
+    /// The value is `true` when the approval is used up by a single squiring.
+    #[pallet::storage]
+    #[pallet::getter(fn squire_approval)]
+    pub type SquireApprovals<T: Config> = StorageDoubleMap<
+        _,
+        Blake2_128Concat,
+        T::KnightIndex,
+        Blake2_128Concat,
+        T::AccountId,
+        bool,
+        OptionQuery,
+    >;
+
+    /// Knights their owners have put up as sires, and the fee for hiring one.
+    #[pallet::storage]
+    #[pallet::getter(fn sire_fee)]
+    pub type SireListings<T: Config> =
+        StorageMap<_, Blake2_128Concat, T::KnightIndex, T::Balance, OptionQuery>;
+
+    /// Knights that are tied up and may not change hands.
+    #[pallet::storage]
+    #[pallet::getter(fn knight_lock)]
+    pub type KnightLocks<T: Config> =
+        StorageMap<_, Blake2_128Concat, T::KnightIndex, KnightLock, OptionQuery>;
+
+    /// Running English auctions.
+    #[pallet::storage]
+    #[pallet::getter(fn auction)]
+    pub type Auctions<T: Config> =
+        StorageMap<_, Blake2_128Concat, T::KnightIndex, AuctionOf<T>, OptionQuery>;
+
+    /// The auctions to settle at the start of each block.
+    #[pallet::storage]
+    #[pallet::getter(fn auctions_ending_at)]
+    pub type AuctionsEndingAt<T: Config> =
+        StorageMap<_, Twox64Concat, T::BlockNumber, Vec<T::KnightIndex>, ValueQuery>;
+
+    /// Running Dutch auctions.
+    #[pallet::storage]
+    #[pallet::getter(fn dutch_auction)]
+    pub type DutchAuctions<T: Config> =
+        StorageMap<_, Blake2_128Concat, T::KnightIndex, DutchAuctionOf<T>, OptionQuery>;
+
+    /// Running sealed-bid auctions.
+    #[pallet::storage]
+    #[pallet::getter(fn sealed_auction)]
+    pub type SealedAuctions<T: Config> =
+        StorageMap<_, Blake2_128Concat, T::KnightIndex, SealedAuctionOf<T>, OptionQuery>;
+
+    /// The bids committed to each sealed-bid auction.
+    #[pallet::storage]
+    #[pallet::getter(fn sealed_bid)]
+    pub type SealedBids<T: Config> = StorageDoubleMap<
+        _,
+        Blake2_128Concat,
+        T::KnightIndex,
+        Blake2_128Concat,
+        T::AccountId,
+        SealedBidOf<T>,
+        OptionQuery,
+    >;
+
+    /// Standing offers on knights, by knight and buyer.
+    #[pallet::storage]
+    #[pallet::getter(fn offer)]
+    pub type Offers<T: Config> = StorageDoubleMap<
+        _,
+        Blake2_128Concat,
+        T::KnightIndex,
+        Blake2_128Concat,
+        T::AccountId,
+        OfferOf<T>,
+        OptionQuery,
+    >;
+
+    /// The offers to withdraw at the start of each block.
+    #[pallet::storage]
+    #[pallet::getter(fn offers_expiring_at)]
+    pub type OffersExpiringAt<T: Config> = StorageMap<
+        _,
+        Twox64Concat,
+        T::BlockNumber,
+        Vec<(T::KnightIndex, T::AccountId)>,
+        ValueQuery,
+    >;
+
+    /// Standing bids on any knight matching a generation and DNA mask.
+    #[pallet::storage]
+    #[pallet::getter(fn collection_bid)]
+    pub type CollectionBids<T: Config> =
+        StorageMap<_, Blake2_128Concat, u64, CollectionBidOf<T>, OptionQuery>;
+
+    /// The id given to the next collection bid.
+    #[pallet::storage]
+    #[pallet::getter(fn next_collection_bid_id)]
+    pub type NextCollectionBidId<T> = StorageValue<_, u64, ValueQuery>;
+
+    /// Who minted each knight and the royalty they take on sales.
+    /// Knights minted before royalties existed have no entry.
+    #[pallet::storage]
+    #[pallet::getter(fn creator)]
+    pub type KnightCreators<T: Config> =
+        StorageMap<_, Blake2_128Concat, T::KnightIndex, Creator<T::AccountId>, OptionQuery>;
+
+    /// The account each knight's owner has approved to transfer it.
+    /// Cleared whenever the knight changes hands.
+    #[pallet::storage]
+    #[pallet::getter(fn approved)]
+    pub type TransferApprovals<T: Config> =
+        StorageMap<_, Blake2_128Concat, T::KnightIndex, T::AccountId, OptionQuery>;
+
+    /// Operators that may transfer every knight of an owner, by owner and operator.
+    #[pallet::storage]
+    #[pallet::getter(fn is_approved_for_all)]
+    pub type OperatorApprovals<T: Config> = StorageDoubleMap<
+        _,
+        Blake2_128Concat,
+        T::AccountId,
+        Blake2_128Concat,
+        T::AccountId,
+        bool,
+        ValueQuery,
+    >;
+
+    /// Standing duel challenges, by challenging knight.
+    #[pallet::storage]
+    #[pallet::getter(fn duel_challenge)]
+    pub type DuelChallenges<T: Config> =
+        StorageMap<_, Blake2_128Concat, T::KnightIndex, DuelChallengeOf<T>, OptionQuery>;
+
+    /// Every knight's duel wins and losses. Knights that have never duelled have no entry.
+    #[pallet::storage]
+    #[pallet::getter(fn duel_record)]
+    pub type DuelRecords<T: Config> =
+        StorageMap<_, Blake2_128Concat, T::KnightIndex, DuelRecord, ValueQuery>;
+
+    /// Tournaments that are taking entrants or underway.
+    #[pallet::storage]
+    #[pallet::getter(fn tournament)]
+    pub type Tournaments<T: Config> =
+        StorageMap<_, Blake2_128Concat, u64, TournamentOf<T>, OptionQuery>;
+
+    /// The id given to the next tournament.
+    #[pallet::storage]
+    #[pallet::getter(fn next_tournament_id)]
+    pub type NextTournamentId<T> = StorageValue<_, u64, ValueQuery>;
+
+    /// Tournaments whose brackets are full, which fight a round every
+    /// `TournamentRoundInterval` blocks until they have a champion.
+    #[pallet::storage]
+    #[pallet::getter(fn active_tournaments)]
+    pub type ActiveTournaments<T> = StorageValue<_, Vec<u64>, ValueQuery>;
+
+    /// The class each knight was minted with. Knights without a class have no entry.
+    #[pallet::storage]
+    #[pallet::getter(fn class)]
+    pub type KnightClasses<T: Config> =
+        StorageMap<_, Blake2_128Concat, T::KnightIndex, KnightClass, OptionQuery>;
+
+    /// The experience each knight has gained. See `Pallet::level`.
+    #[pallet::storage]
+    #[pallet::getter(fn experience)]
+    pub type Experience<T: Config> =
+        StorageMap<_, Blake2_128Concat, T::KnightIndex, u64, ValueQuery>;
+
+    /// How many living knights have each trait. See `dna` for how traits are decoded.
+    #[pallet::storage]
+    #[pallet::getter(fn trait_count)]
+    pub type TraitCounts<T: Config> = StorageMap<_, Blake2_128Concat, KnightTrait, u64, ValueQuery>;
+
+    /// The knights squired from each knight, keyed by `(parent, child)`.
+    /// Burned knights keep their place in the family tree.
+    #[pallet::storage]
+    pub type Children<T: Config> = StorageDoubleMap<
+        _,
+        Blake2_128Concat,
+        T::KnightIndex,
+        Blake2_128Concat,
+        T::KnightIndex,
+        (),
+        OptionQuery,
+    >;
+
+    /// Every knightly order.
+    #[pallet::storage]
+    #[pallet::getter(fn order)]
+    pub type Orders<T: Config> = StorageMap<_, Blake2_128Concat, u64, OrderOf<T>, OptionQuery>;
+
+    /// The id given to the next order.
+    #[pallet::storage]
+    #[pallet::getter(fn next_order_id)]
+    pub type NextOrderId<T> = StorageValue<_, u64, ValueQuery>;
+
+    /// The knights enrolled in each order and the block each joined, keyed by
+    /// `(order_id, knight_id)`.
+    #[pallet::storage]
+    #[pallet::getter(fn order_member)]
+    pub type OrderMembers<T: Config> = StorageDoubleMap<
+        _,
+        Blake2_128Concat,
+        u64,
+        Blake2_128Concat,
+        T::KnightIndex,
+        T::BlockNumber,
+        OptionQuery,
+    >;
+
+    /// The order each knight belongs to. A knight belongs to at most one.
+    #[pallet::storage]
+    #[pallet::getter(fn knight_order)]
+    pub type KnightOrders<T: Config> =
+        StorageMap<_, Blake2_128Concat, T::KnightIndex, u64, OptionQuery>;
+
+    /// Spends from order treasuries that are being voted on.
+    #[pallet::storage]
+    #[pallet::getter(fn spend_proposal)]
+    pub type SpendProposals<T: Config> =
+        StorageMap<_, Blake2_128Concat, u64, SpendProposalOf<T>, OptionQuery>;
+
+    /// The id given to the next spend proposal.
+    #[pallet::storage]
+    #[pallet::getter(fn next_spend_proposal_id)]
+    pub type NextSpendProposalId<T> = StorageValue<_, u64, ValueQuery>;
+
+    /// The spends each order is voting on.
+    #[pallet::storage]
+    #[pallet::getter(fn open_spends)]
+    pub type OpenSpends<T> = StorageMap<_, Blake2_128Concat, u64, Vec<u64>, ValueQuery>;
+
+    /// How each knight voted on each spend, keyed by `(proposal_id, knight_id)`.
+    /// `true` is an aye.
+    #[pallet::storage]
+    #[pallet::getter(fn spend_vote)]
+    pub type SpendVotes<T: Config> = StorageDoubleMap<
+        _,
+        Blake2_128Concat,
+        u64,
+        Blake2_128Concat,
+        T::KnightIndex,
+        bool,
+        OptionQuery,
+    >;
+
+    /// Accepted duels waiting to be fought, by challenging knight, with the opponent's owner.
+    #[pallet::storage]
+    #[pallet::getter(fn accepted_duel)]
+    pub type AcceptedDuels<T: Config> =
+        StorageMap<_, Blake2_128Concat, T::KnightIndex, T::AccountId, OptionQuery>;
+
+    /// The accepted duels to fight at the start of each block.
+    #[pallet::storage]
+    #[pallet::getter(fn duels_fought_at)]
+    pub type DuelsFoughtAt<T: Config> =
+        StorageMap<_, Twox64Concat, T::BlockNumber, Vec<T::KnightIndex>, ValueQuery>;
+
+    /// The tournaments whose registration closes at the start of each block.
+    #[pallet::storage]
+    #[pallet::getter(fn registrations_closing_at)]
+    pub type RegistrationsClosingAt<T: Config> =
+        StorageMap<_, Twox64Concat, T::BlockNumber, Vec<u64>, ValueQuery>;
+
     #[pallet::event]
-    #[pallet::metadata(T::AccountId = "AccountId", T::KnightIndex = "KnightIndex")]
+    #[pallet::metadata(
+        T::AccountId = "AccountId",
+        T::KnightIndex = "KnightIndex",
+        T::BlockNumber = "BlockNumber",
+        T::Balance = "Balance"
+    )]
     #[pallet::generate_deposit(pub(super) fn deposit_event)]
     pub enum Event<T: Config> {
         KnightCreated(T::KnightIndex, T::AccountId),
@@ -116,6 +876,143 @@ pub mod pallet {
         KnightPriceSet(T::KnightIndex, T::Balance),
         /// [new_knight_id, knight_1_id, knight_2_id, account_id]
         SquireKnighted(T::KnightIndex, T::KnightIndex, T::KnightIndex, T::AccountId),
+        /// A parent is resting after squiring. [knight_id, ready_at_block]
+        KnightResting(T::KnightIndex, T::BlockNumber),
+        /// [knight_id, approved_account_id, one_shot]
+        SquireApproved(T::KnightIndex, T::AccountId, bool),
+        /// [knight_id, approved_account_id]
+        SquireApprovalRevoked(T::KnightIndex, T::AccountId),
+        /// [knight_id, fee]
+        SireListed(T::KnightIndex, T::Balance),
+        /// [knight_id]
+        SireDelisted(T::KnightIndex),
+        /// [sire_id, knight_id, new_knight_id, sire_owner_account_id, account_id, fee]
+        SireHired(
+            T::KnightIndex,
+            T::KnightIndex,
+            T::KnightIndex,
+            T::AccountId,
+            T::AccountId,
+            T::Balance,
+        ),
+        /// [knight_id, seller_account_id, reserve_price, end_block]
+        AuctionStarted(T::KnightIndex, T::AccountId, T::Balance, T::BlockNumber),
+        /// [knight_id, seller_account_id, start_price, floor_price, duration]
+        DutchAuctionStarted(
+            T::KnightIndex,
+            T::AccountId,
+            T::Balance,
+            T::Balance,
+            T::BlockNumber,
+        ),
+        /// [knight_id, bidder_account_id, bid]
+        BidPlaced(T::KnightIndex, T::AccountId, T::Balance),
+        /// [knight_id, seller_account_id, winner_account_id, winning_bid]
+        AuctionWon(T::KnightIndex, T::AccountId, T::AccountId, T::Balance),
+        /// The auction ended or was cancelled without a sale. [knight_id, seller_account_id]
+        AuctionClosed(T::KnightIndex, T::AccountId),
+        /// [knight_id, seller_account_id, reserve_price, commit_end_block, reveal_end_block]
+        SealedAuctionStarted(
+            T::KnightIndex,
+            T::AccountId,
+            T::Balance,
+            T::BlockNumber,
+            T::BlockNumber,
+        ),
+        /// [knight_id, bidder_account_id, deposit]
+        BidCommitted(T::KnightIndex, T::AccountId, T::Balance),
+        /// [knight_id, bidder_account_id, bid]
+        BidRevealed(T::KnightIndex, T::AccountId, T::Balance),
+        /// A bid was never revealed and its deposit was slashed.
+        /// [knight_id, bidder_account_id, deposit]
+        SealedBidSlashed(T::KnightIndex, T::AccountId, T::Balance),
+        /// [knight_id, buyer_account_id, amount, expiry_block]
+        OfferMade(T::KnightIndex, T::AccountId, T::Balance, T::BlockNumber),
+        /// [knight_id, buyer_account_id]
+        OfferCancelled(T::KnightIndex, T::AccountId),
+        /// [knight_id, buyer_account_id]
+        OfferExpired(T::KnightIndex, T::AccountId),
+        /// [knight_id, seller_account_id, buyer_account_id, amount]
+        OfferAccepted(T::KnightIndex, T::AccountId, T::AccountId, T::Balance),
+        /// [collection_bid_id, bidder_account_id, gen, price, quantity]
+        CollectionBidPlaced(u64, T::AccountId, u64, T::Balance, u32),
+        /// [collection_bid_id, knight_id, seller_account_id, bidder_account_id, price]
+        CollectionBidFilled(u64, T::KnightIndex, T::AccountId, T::AccountId, T::Balance),
+        /// [collection_bid_id, bidder_account_id]
+        CollectionBidCancelled(u64, T::AccountId),
+        /// [knight_id, owner_account_id, spender_account_id]
+        Approval(T::KnightIndex, T::AccountId, T::AccountId),
+        /// [knight_id, owner_account_id]
+        ApprovalCancelled(T::KnightIndex, T::AccountId),
+        /// [owner_account_id, operator_account_id, approved]
+        ApprovalForAll(T::AccountId, T::AccountId, bool),
+        /// [knight_id, depositor_account_id, amount]
+        WealthDeposited(T::KnightIndex, T::AccountId, T::Balance),
+        /// [knight_id, owner_account_id, amount]
+        WealthWithdrawn(T::KnightIndex, T::AccountId, T::Balance),
+        /// [knight_id, opponent_knight_id, challenger_account_id, wager]
+        DuelChallenged(T::KnightIndex, T::KnightIndex, T::AccountId, T::Balance),
+        /// [knight_id, challenger_account_id]
+        DuelChallengeCancelled(T::KnightIndex, T::AccountId),
+        /// [winner_knight_id, loser_knight_id, winner_account_id, pot]
+        DuelWon(T::KnightIndex, T::KnightIndex, T::AccountId, T::Balance),
+        /// [tournament_id, organiser_account_id, entry_fee, size]
+        TournamentOpened(u64, T::AccountId, T::Balance, u32),
+        /// [tournament_id, knight_id, owner_account_id]
+        TournamentEntered(u64, T::KnightIndex, T::AccountId),
+        /// Registration was cancelled and the entry fees refunded. [tournament_id]
+        TournamentCancelled(u64),
+        /// The bracket was drawn, neighbours fighting each other. [tournament_id, bracket]
+        TournamentSeeded(u64, Vec<T::KnightIndex>),
+        /// [tournament_id, round, winner_knight_ids]
+        TournamentRoundFought(u64, u32, Vec<T::KnightIndex>),
+        /// [tournament_id, runner_up_knight_id, owner_account_id, prize]
+        TournamentPrizePaid(u64, T::KnightIndex, T::AccountId, T::Balance),
+        /// [tournament_id, champion_knight_id, owner_account_id, prize]
+        TournamentWon(u64, T::KnightIndex, T::AccountId, T::Balance),
+        /// [knight_id, experience]
+        ExperienceGained(T::KnightIndex, u64),
+        /// [knight_id, level]
+        LevelledUp(T::KnightIndex, u32),
+        /// [order_id, founder_account_id]
+        OrderFounded(u64, T::AccountId),
+        /// [order_id, knight_id, owner_account_id, dues]
+        KnightEnrolled(u64, T::KnightIndex, T::AccountId, T::Balance),
+        /// [order_id, knight_id]
+        KnightLeftOrder(u64, T::KnightIndex),
+        /// [order_id, knight_id]
+        OfficerAppointed(u64, T::KnightIndex),
+        /// [order_id, knight_id]
+        OfficerDismissed(u64, T::KnightIndex),
+        /// [proposal_id, order_id, officer_knight_id, beneficiary_account_id, amount]
+        SpendProposed(u64, u64, T::KnightIndex, T::AccountId, T::Balance),
+        /// [proposal_id, knight_id, aye]
+        SpendVoted(u64, T::KnightIndex, bool),
+        /// The vote passed and the treasury paid out. [proposal_id, beneficiary_account_id, amount]
+        SpendApproved(u64, T::AccountId, T::Balance),
+        /// The vote failed, or the proposer is no longer an officer. [proposal_id]
+        SpendRejected(u64),
+        /// [knight_id, account_id]
+        KnightBurned(T::KnightIndex, T::AccountId),
+        /// [knight_id, seller_account_id, buyer_account_id, price]
+        KnightSold(T::KnightIndex, T::AccountId, T::AccountId, T::Balance),
+        /// A sale's price was split between the seller and the knight's creator.
+        /// [knight_id, seller_account_id, seller_amount, creator_account_id, royalty]
+        SaleSplit(
+            T::KnightIndex,
+            T::AccountId,
+            T::Balance,
+            T::AccountId,
+            T::Balance,
+        ),
+        /// [knight_id, fee]
+        MarketplaceFeePaid(T::KnightIndex, T::Balance),
+        /// The challenge was taken up and the duel is fought at the start of `fight_at`.
+        /// [knight_id, opponent_knight_id, opponent_account_id, fight_at_block]
+        DuelAccepted(T::KnightIndex, T::KnightIndex, T::AccountId, T::BlockNumber),
+        /// An entrant withdrew before the bracket filled and was refunded.
+        /// [tournament_id, knight_id, owner_account_id]
+        TournamentLeft(u64, T::KnightIndex, T::AccountId),
     }
 
     // Errors inform users that something went wrong.
@@ -131,17 +1028,202 @@ pub mod pallet {
         NotRightfulOwner,
         KnightTransferFailed,
         KnightGenOverflow,
+        /// A knight's name cannot be empty.
+        NameEmpty,
+        /// A knight's name is longer than `MaxNameLength`.
+        NameTooLong,
+        /// A knight's name must be valid UTF-8.
+        NameNotUtf8,
+        /// A parent is still resting from squiring and cannot squire yet.
+        KnightStillResting,
+        /// The caller neither owns a parent nor has the owner's approval to squire with it.
+        NotApprovedToSquire,
+        /// There is no squire approval to revoke.
+        SquireApprovalNotFound,
+        /// The knight is not listed as a sire.
+        SireNotListed,
+        /// The sire's fee is higher than the caller is willing to pay.
+        SireFeeTooHigh,
+        /// Owners squire with their own knights through `knight_squire`.
+        CannotHireOwnSire,
+        KnightCountUnderflow,
+        /// The knight has a price set and must be taken off sale first.
+        KnightForSale,
+        /// The knight is listed as a sire and must be delisted first.
+        KnightListedAsSire,
+        /// The knight has no price set.
+        KnightNotForSale,
+        /// The buyer already owns the knight.
+        AlreadyOwnKnight,
+        /// The knight's price has been raised above what the buyer agreed to pay.
+        PriceChanged,
+        /// The royalty is above `MaxRoyalty`.
+        RoyaltyTooHigh,
+        /// The knight is locked by an auction or similar and cannot change hands.
+        KnightLocked,
+        /// The auction must end between `MinAuctionDuration` and `MaxAuctionDuration` from now.
+        InvalidAuctionEnd,
+        /// `MaxAuctionsPerBlock` auctions already end in that block.
+        TooManyAuctionsEnding,
+        AuctionNotFound,
+        /// Sellers cannot bid on their own auctions.
+        CannotBidOnOwnAuction,
+        /// The bid is below the reserve price or does not beat the highest bid.
+        BidTooLow,
+        /// Auctions with bids on them run until they end.
+        AuctionHasBids,
+        /// A Dutch auction's floor price cannot be above its start price.
+        FloorAboveStartPrice,
+        /// The sealed-bid auction is no longer taking bids.
+        CommitPhaseOver,
+        /// Bids can only be revealed after bidding closes and before the auction ends.
+        NotRevealPhase,
+        /// The caller has already committed a bid to this auction.
+        AlreadyCommitted,
+        /// `MaxSealedBids` bids have already been committed to this auction.
+        TooManySealedBids,
+        SealedBidNotFound,
+        AlreadyRevealed,
+        /// The bid and salt do not hash to the committed value.
+        CommitmentMismatch,
+        /// The revealed bid is larger than the deposit held for it.
+        BidExceedsDeposit,
+        /// The caller already has an offer standing on this knight.
+        AlreadyOffered,
+        /// Owners cannot make or accept offers on their own knights.
+        CannotOfferOnOwnKnight,
+        /// `MaxOffersPerBlock` offers already expire in that block.
+        TooManyOffersExpiring,
+        OfferNotFound,
+        /// The buyer's reserved funds no longer cover the price.
+        ReserveTooLow,
+        CollectionBidNotFound,
+        CollectionBidIdOverflow,
+        /// A collection bid must ask for at least one knight.
+        ZeroQuantity,
+        /// The knight's generation or DNA does not match the collection bid.
+        KnightDoesNotMatchBid,
+        /// Bidders cannot fill their own collection bids.
+        CannotFillOwnBid,
+        /// The caller is neither the owner nor approved to transfer the knight.
+        NotApprovedToTransfer,
+        /// Owners do not need to approve themselves.
+        CannotApproveSelf,
+        ChallengeNotFound,
+        /// Owners cannot duel their own knights.
+        CannotDuelOwnKnight,
+        /// The challenge's wager is more than the caller agreed to stake.
+        WagerTooHigh,
+        TournamentNotFound,
+        TournamentIdOverflow,
+        /// A bracket must be a power of two between 2 and `MaxTournamentSize`.
+        InvalidBracketSize,
+        /// The tournament's bracket is full and registration has closed.
+        TournamentUnderway,
+        /// `MaxActiveTournaments` tournaments are already underway.
+        TooManyActiveTournaments,
+        /// Knights cannot squire with themselves, their parents, their children
+        /// or their siblings.
+        CloseRelatives,
+        OrderNotFound,
+        OrderIdOverflow,
+        /// A knight may belong to only one order at a time.
+        AlreadyInOrder,
+        /// The knight is not enrolled in the order.
+        NotInOrder,
+        /// The order already has `MaxOrderMembers` knights.
+        OrderFull,
+        /// The knight's level or class does not meet the order's rules.
+        KnightDoesNotMeetOrderRules,
+        /// The order already has `MaxOrderOfficers` officers.
+        TooManyOfficers,
+        AlreadyOfficer,
+        NotOfficer,
+        SpendProposalNotFound,
+        SpendProposalIdOverflow,
+        /// The spend's voting period is over.
+        VotingClosed,
+        /// The spend can be closed once its voting period is over.
+        VotingNotOver,
+        /// Only knights enrolled by the time a spend was proposed may vote on it.
+        EnrolledAfterProposal,
+        /// The offer is below `MinOfferAmount`.
+        OfferTooLow,
+        /// The order is already voting on `MaxOpenSpends` spends.
+        TooManyOpenSpends,
+        /// The challenge has been accepted and the duel is waiting to be fought.
+        ChallengeAccepted,
+        /// `MaxDuelsPerBlock` duels are already fought in the next block.
+        TooManyDuels,
+        /// Registration must close after this block and within `MaxRegistrationPeriod`.
+        InvalidRegistrationEnd,
+        /// `MaxRegistrationsPerBlock` tournaments already close registration in that block.
+        TooManyRegistrationsClosing,
+        /// The knight is not entered in the tournament.
+        NotEntered,
     }
 
     #[pallet::hooks]
-    impl<T: Config> Hooks<BlockNumberFor<T>> for Pallet<T> {}
+    impl<T: Config> Hooks<BlockNumberFor<T>> for Pallet<T> {
+        fn on_initialize(now: T::BlockNumber) -> Weight {
+            // Taking `OffersExpiringAt`, `AuctionsEndingAt`, `DuelsFoughtAt` and
+            // `RegistrationsClosingAt` for this block.
+            let db = T::DbWeight::get();
+            let mut weight = db.reads_writes(4, 4);
+
+            for (knight_id, buyer) in OffersExpiringAt::<T>::take(now) {
+                Self::_expire_offer(knight_id, buyer);
+                weight = weight.saturating_add(T::WeightInfo::expire_offer());
+            }
+
+            for knight_id in AuctionsEndingAt::<T>::take(now) {
+                let settled = match KnightLocks::<T>::get(knight_id) {
+                    Some(KnightLock::SealedAuction) => {
+                        let bids = Self::_settle_sealed_auction(knight_id);
+                        T::WeightInfo::settle_sealed_auction(bids)
+                    }
+                    _ => {
+                        Self::_settle_auction(knight_id);
+                        T::WeightInfo::settle_auction()
+                    }
+                };
+                // The `KnightLocks` read that picks the kind of auction.
+                weight = weight.saturating_add(db.reads(1)).saturating_add(settled);
+            }
+
+            for challenger_id in DuelsFoughtAt::<T>::take(now) {
+                Self::_fight_duel(challenger_id);
+                weight = weight.saturating_add(T::WeightInfo::fight_duel());
+            }
+
+            for tournament_id in RegistrationsClosingAt::<T>::take(now) {
+                let refunded = Self::_close_registration(tournament_id);
+                weight = weight.saturating_add(T::WeightInfo::close_registration(refunded));
+            }
+
+            let interval = T::TournamentRoundInterval::get();
+            if !interval.is_zero() && (now % interval).is_zero() {
+                weight = weight.saturating_add(db.reads(1));
+                for tournament_id in ActiveTournaments::<T>::get() {
+                    let fought = Self::_fight_tournament_round(tournament_id);
+                    weight = weight.saturating_add(T::WeightInfo::fight_tournament_round(fought));
+                }
+            }
+
+            weight
+        }
+
+        fn on_runtime_upgrade() -> Weight {
+            crate::migrations::migrate::<T>()
+        }
+    }
 
     // Dispatchable functions allows users to interact with the pallet and invoke state changes.
     // These functions materialize as "extrinsics", which are often compared to transactions.
     // Dispatchable functions must be annotated with a weight and must return a DispatchResult.
     #[pallet::call]
     impl<T: Config> Pallet<T> {
-        #[pallet::weight(10_000 + T::DbWeight::get().reads_writes(4, 6))]
+        #[pallet::weight(T::WeightInfo::transfer_knight())]
         pub fn transfer_knight(
             origin: OriginFor<T>,
             id: T::KnightIndex,
@@ -149,15 +1231,14 @@ pub mod pallet {
         ) -> DispatchResultWithPostInfo {
             let from = ensure_signed(origin)?;
 
-            let owner = KnightToOwner::<T>::get(&id).ok_or(Error::<T>::KnightNotFound)?;
-            ensure!(owner == from, Error::<T>::NotRightfulOwner);
+            Self::ensure_may_transfer(id, &from)?;
 
-            Self::_transfer_knight(id, from, to).expect("Transfers Knight");
+            Self::_transfer_knight(id, from, to)?;
 
             Ok(().into())
         }
 
-        #[pallet::weight(10_000 + T::DbWeight::get().reads_writes(2,1))]
+        #[pallet::weight(T::WeightInfo::set_price())]
         pub fn set_price(
             origin: OriginFor<T>,
             knight_id: T::KnightIndex,
@@ -169,6 +1250,7 @@ pub mod pallet {
             let owner = KnightToOwner::<T>::get(&knight_id).ok_or(Error::<T>::KnightNotFound)?;
 
             ensure!(owner == who, Error::<T>::NotRightfulOwner);
+            Self::ensure_unlocked(knight_id)?;
 
             knight.price = price;
 
@@ -179,7 +1261,7 @@ pub mod pallet {
             Ok(().into())
         }
 
-        #[pallet::weight(10_000 + T::DbWeight::get().reads_writes(1,1))]
+        #[pallet::weight(T::WeightInfo::knight_squire(squire_name.len() as u32))]
         pub fn knight_squire(
             origin: OriginFor<T>,
             squire_name: Vec<u8>,
@@ -188,66 +1270,157 @@ pub mod pallet {
         ) -> DispatchResultWithPostInfo {
             let who = ensure_signed(origin)?;
 
-            let latest_knight_id = LatestKnightId::<T>::get();
-            let next_knight_id = latest_knight_id
-                .checked_add(&One::one())
-                .ok_or(Error::<T>::KnightIdOverflow)?;
+            Self::ensure_valid_name(&squire_name)?;
 
-            ensure!(
-                !Knights::<T>::contains_key(next_knight_id),
-                Error::<T>::KnightAlreadyExists
-            );
+            let approval_1 = Self::ensure_may_squire_with(&who, knight_id_1)?;
+            let approval_2 = Self::ensure_may_squire_with(&who, knight_id_2)?;
 
-            let mut knight_1 = Knights::<T>::get(knight_id_1).ok_or(Error::<T>::KnightNotFound)?;
-            let mut knight_2 = Knights::<T>::get(knight_id_2).ok_or(Error::<T>::KnightNotFound)?;
+            Self::_squire(&who, squire_name, knight_id_1, knight_id_2)?;
 
-            &knight_1.name.append(&mut knight_2.name);
+            if approval_1 {
+                Self::_use_squire_approval(knight_id_1, &who);
+            }
+            if approval_2 {
+                Self::_use_squire_approval(knight_id_2, &who);
+            }
 
-            let random_hash = T::RandomnessSource::random(&knight_1.name);
+            Ok(().into())
+        }
 
-            let mut final_dna = knight_1.dna;
-            for (i, (dna_2_element, r)) in knight_2
-                .dna
-                .as_ref()
-                .iter()
-                .zip(random_hash.as_ref().iter())
-                .enumerate()
-            {
-                if r % 2 == 0 {
-                    final_dna.as_mut()[i] = *dna_2_element;
-                }
-            }
+        /// Allow `approved` to squire with a knight the caller owns.
+        ///
+        /// A `one_shot` approval is removed after it is used once; otherwise it
+        /// stands until revoked or the knight changes hands.
+        #[pallet::weight(T::WeightInfo::approve_squire())]
+        pub fn approve_squire(
+            origin: OriginFor<T>,
+            knight_id: T::KnightIndex,
+            approved: T::AccountId,
+            one_shot: bool,
+        ) -> DispatchResultWithPostInfo {
+            let who = ensure_signed(origin)?;
 
-            let new_gen = knight_1
-                .gen
-                .checked_add(1)
-                .ok_or(Error::<T>::KnightGenOverflow)?;
+            let owner = KnightToOwner::<T>::get(knight_id).ok_or(Error::<T>::KnightNotFound)?;
+            ensure!(owner == who, Error::<T>::NotRightfulOwner);
 
-            let knight = Knight {
-                id: next_knight_id,
-                dna: final_dna,
-                name: squire_name,
-                wealth: T::Balance::zero(),
-                price: T::Balance::zero(),
-                gen: new_gen,
-            };
+            SquireApprovals::<T>::insert(knight_id, &approved, one_shot);
 
-            Self::_mint(&who, knight)?;
+            Self::deposit_event(Event::SquireApproved(knight_id, approved, one_shot));
 
-            Self::deposit_event(Event::SquireKnighted(
-                next_knight_id,
-                knight_1.id,
-                knight_2.id,
+            Ok(().into())
+        }
+
+        #[pallet::weight(T::WeightInfo::revoke_squire_approval())]
+        pub fn revoke_squire_approval(
+            origin: OriginFor<T>,
+            knight_id: T::KnightIndex,
+            approved: T::AccountId,
+        ) -> DispatchResultWithPostInfo {
+            let who = ensure_signed(origin)?;
+
+            let owner = KnightToOwner::<T>::get(knight_id).ok_or(Error::<T>::KnightNotFound)?;
+            ensure!(owner == who, Error::<T>::NotRightfulOwner);
+            ensure!(
+                SquireApprovals::<T>::contains_key(knight_id, &approved),
+                Error::<T>::SquireApprovalNotFound
+            );
+
+            SquireApprovals::<T>::remove(knight_id, &approved);
+
+            Self::deposit_event(Event::SquireApprovalRevoked(knight_id, approved));
+
+            Ok(().into())
+        }
+
+        /// Offer a knight the caller owns as a sire for others to squire with.
+        #[pallet::weight(T::WeightInfo::list_sire())]
+        pub fn list_sire(
+            origin: OriginFor<T>,
+            knight_id: T::KnightIndex,
+            fee: T::Balance,
+        ) -> DispatchResultWithPostInfo {
+            let who = ensure_signed(origin)?;
+
+            let owner = KnightToOwner::<T>::get(knight_id).ok_or(Error::<T>::KnightNotFound)?;
+            ensure!(owner == who, Error::<T>::NotRightfulOwner);
+
+            SireListings::<T>::insert(knight_id, fee);
+
+            Self::deposit_event(Event::SireListed(knight_id, fee));
+
+            Ok(().into())
+        }
+
+        #[pallet::weight(T::WeightInfo::delist_sire())]
+        pub fn delist_sire(
+            origin: OriginFor<T>,
+            knight_id: T::KnightIndex,
+        ) -> DispatchResultWithPostInfo {
+            let who = ensure_signed(origin)?;
+
+            let owner = KnightToOwner::<T>::get(knight_id).ok_or(Error::<T>::KnightNotFound)?;
+            ensure!(owner == who, Error::<T>::NotRightfulOwner);
+            ensure!(
+                SireListings::<T>::contains_key(knight_id),
+                Error::<T>::SireNotListed
+            );
+
+            SireListings::<T>::remove(knight_id);
+
+            Self::deposit_event(Event::SireDelisted(knight_id));
+
+            Ok(().into())
+        }
+
+        /// Squire one of the caller's knights with a listed sire.
+        ///
+        /// The sire's fee is paid to its owner and the squire belongs to the caller.
+        /// `max_fee` guards against the owner raising the fee in the meantime.
+        #[pallet::weight(T::WeightInfo::squire_with_sire(squire_name.len() as u32))]
+        pub fn squire_with_sire(
+            origin: OriginFor<T>,
+            squire_name: Vec<u8>,
+            knight_id: T::KnightIndex,
+            sire_id: T::KnightIndex,
+            max_fee: T::Balance,
+        ) -> DispatchResultWithPostInfo {
+            let who = ensure_signed(origin)?;
+
+            Self::ensure_valid_name(&squire_name)?;
+
+            let owner = KnightToOwner::<T>::get(knight_id).ok_or(Error::<T>::KnightNotFound)?;
+            ensure!(owner == who, Error::<T>::NotRightfulOwner);
+
+            let fee = SireListings::<T>::get(sire_id).ok_or(Error::<T>::SireNotListed)?;
+            ensure!(fee <= max_fee, Error::<T>::SireFeeTooHigh);
+
+            let sire_owner = KnightToOwner::<T>::get(sire_id).ok_or(Error::<T>::KnightNotFound)?;
+            ensure!(sire_owner != who, Error::<T>::CannotHireOwnSire);
+
+            let new_knight_id =
+                Self::_hire_sire(&who, &sire_owner, fee, squire_name, knight_id, sire_id)?;
+
+            Self::deposit_event(Event::SireHired(
+                sire_id,
+                knight_id,
+                new_knight_id,
+                sire_owner,
                 who,
+                fee,
             ));
 
             Ok(().into())
         }
 
-        #[pallet::weight(10_000 + T::DbWeight::get().reads_writes(6,8))]
+        /// Buy a knight at its set price.
+        ///
+        /// Fails if the price has been raised above `max_price`, so an owner cannot
+        /// raise the price on a buyer between submission and execution.
+        #[pallet::weight(T::WeightInfo::buy_knight())]
         pub fn buy_knight(
             origin: OriginFor<T>,
             knight_id: T::KnightIndex,
+            max_price: T::Balance,
         ) -> DispatchResultWithPostInfo {
             let buyer = ensure_signed(origin)?;
 
@@ -256,53 +1429,52 @@ pub mod pallet {
             // the owner / seller of the knight would still have the tokens.
 
             // the knight exists
-            let mut knight = Knights::<T>::get(knight_id).ok_or(Error::<T>::KnightNotFound)?;
+            let knight = Knights::<T>::get(knight_id).ok_or(Error::<T>::KnightNotFound)?;
 
-            ensure!(
-                !knight.price.is_zero(),
-                "The knight you want to buy isn't for sale."
-            );
+            ensure!(!knight.price.is_zero(), Error::<T>::KnightNotForSale);
+            ensure!(knight.price <= max_price, Error::<T>::PriceChanged);
 
             // we'll send funds to the owner of the knight
             let owner = KnightToOwner::<T>::get(knight_id).ok_or(Error::<T>::KnightNotFound)?;
 
-            ensure!(owner != buyer, "You already own this Knight");
+            ensure!(owner != buyer, Error::<T>::AlreadyOwnKnight);
 
-            <pallet_balances::Pallet<T> as Currency<_>>::transfer(
-                &buyer,
-                &owner,
+            let fee = T::MarketplaceFee::get() * knight.price;
+            Self::_sell(
+                knight_id,
+                owner.clone(),
+                buyer.clone(),
                 knight.price,
-                frame_support::traits::ExistenceRequirement::KeepAlive,
+                fee,
+                Payment::Free,
             )?;
 
-            // NOTE on underflow and overflow::
-            // Since these counts are set when a Knight is
-            // minted if a user owns a Knight, then his count
-            // will always be >= 1.
-            // as for the buyer, his knight count will never exceed
-            // the total number of knights minted. Since they are
-            // both u64, we can be certain that an overflow will never occur.
-            // All that said, it's typical to see .expect() statements
-            // in Substrate code to document why something will never fail.
-            Self::_transfer_knight(knight_id, owner, buyer).expect("Transfers Knight");
-
-            // update price to zero so this Knight cannot be purchased again
-            // until the new owner decides.
-            knight.price = T::Balance::zero();
-            Knights::<T>::insert(knight_id, &knight);
+            Self::deposit_event(Event::KnightSold(knight_id, owner, buyer, knight.price));
 
             Ok(().into())
         }
 
         /// An example dispatchable that takes a singles value as a parameter, writes the value to
         /// storage and emits an event. This function must be dispatched by a signed extrinsic.
-        #[pallet::weight(10_000 + T::DbWeight::get().reads_writes(2,5))]
-        pub fn create_knight(origin: OriginFor<T>, name: Vec<u8>) -> DispatchResultWithPostInfo {
+        ///
+        /// The caller is recorded as the knight's creator and is paid `royalty`
+        /// of the price whenever someone else sells it. A `class`, if given,
+        /// favours one of the knight's stats for good.
+        #[pallet::weight(T::WeightInfo::create_knight(name.len() as u32))]
+        pub fn create_knight(
+            origin: OriginFor<T>,
+            name: Vec<u8>,
+            royalty: Perbill,
+            class: Option<KnightClass>,
+        ) -> DispatchResultWithPostInfo {
             // Check that the extrinsic was signed and get the signer.
             // This function will return an error if the extrinsic is not signed.
             // https://substrate.dev/docs/en/knowledgebase/runtime/origin
             let who = ensure_signed(origin)?;
 
+            Self::ensure_valid_name(&name)?;
+            ensure!(royalty <= T::MaxRoyalty::get(), Error::<T>::RoyaltyTooHigh);
+
             let current_count = KnightCount::<T>::get();
 
             let new_count = current_count
@@ -323,137 +1495,2542 @@ pub mod pallet {
             let knight = Knight {
                 id: next_knight_id,
                 name,
-                dna: (new_count, &who).using_encoded(blake2_128),
+                // ids are never reused, unlike the count, which drops when
+                // a knight is burned
+                dna: (next_knight_id, &who).using_encoded(blake2_128),
                 wealth: 0u8.into(),
                 price: 0u8.into(),
                 gen: 1,
+                birth_block: frame_system::Pallet::<T>::block_number(),
+                parents: None,
             };
 
-            Self::_mint(&who, knight)?;
+            Self::_mint(&who, knight, royalty)?;
+
+            if let Some(class) = class {
+                KnightClasses::<T>::insert(next_knight_id, class);
+            }
 
             return Ok(().into());
         }
-    }
-    // end pallet::call
 
-    // The genesis config type.
-    #[pallet::genesis_config]
-    pub struct GenesisConfig<T: Config> {
-        pub thing: u64,
-        pub alice: T::AccountId,
-        // pub dummy: T::Balance,
-        // pub bar: Vec<(T::AccountId, T::Balance)>,
-        // pub foo: T::Balance,
-    }
+        /// Permanently destroy a knight the caller owns.
+        ///
+        /// Knights that are for sale or listed as sires must be delisted first.
+        #[pallet::weight(
+            T::WeightInfo::burn_knight()
+                .saturating_add(T::WeightInfo::leave_order(T::MaxOpenSpends::get()))
+        )]
+        pub fn burn_knight(
+            origin: OriginFor<T>,
+            knight_id: T::KnightIndex,
+        ) -> DispatchResultWithPostInfo {
+            let who = ensure_signed(origin)?;
 
-    // The default value for the genesis config type.
-    #[cfg(feature = "std")]
-    impl<T: Config> Default for GenesisConfig<T> {
-        fn default() -> Self {
-            Self {
-                thing: Default::default(),
-                alice: Default::default(),
-                // dummy: Default::default(),
-                // bar: Default::default(),
-                // foo: Default::default(),
-            }
-        }
-    }
+            let knight = Knights::<T>::get(knight_id).ok_or(Error::<T>::KnightNotFound)?;
+            let owner = KnightToOwner::<T>::get(knight_id).ok_or(Error::<T>::KnightNotFound)?;
+            ensure!(owner == who, Error::<T>::NotRightfulOwner);
 
-    // The build of genesis for the pallet.
-    #[pallet::genesis_build]
-    impl<T: Config> GenesisBuild<T> for GenesisConfig<T> {
-        fn build(&self) {
-            Thing::<T>::put(100);
+            Self::ensure_not_listed(&knight)?;
+            Self::ensure_unlocked(knight_id)?;
 
-            let latest_knight_id = LatestKnightId::<T>::get();
-            let next_knight_id = latest_knight_id.checked_add(&One::one()).unwrap();
+            // whatever the knight held goes back to its owner
+            let vault = Self::vault_account(knight_id);
+            T::Currency::transfer(
+                &vault,
+                &owner,
+                T::Currency::free_balance(&vault),
+                ExistenceRequirement::AllowDeath,
+            )?;
 
-            let knight = Knight {
-                id: next_knight_id,
-                name: "Danny the Daring".as_bytes().to_vec(),
-                dna: (1).using_encoded(blake2_128),
-                wealth: 0u8.into(),
-                price: 0u8.into(),
-                gen: 1,
-            };
+            Self::_burn(knight_id, &owner)?;
 
-            Pallet::<T>::_mint(&self.alice, knight).unwrap();
+            Self::deposit_event(Event::KnightBurned(knight_id, owner));
 
-            // <Dummy<T>>::put(&self.dummy);
-            // for (a, b) in &self.bar {
-            // <Bar<T>>::insert(a, b);
-            // }
-            // <Foo<T>>::put(&self.foo);
+            Ok(().into())
         }
-    }
 
-    impl<T: Config> Pallet<T> {
-        fn _mint(
-            owner: &T::AccountId,
-            knight: Knight<T::Balance, T::KnightIndex>,
-        ) -> Result<(), &'static str> {
-            let knight_id: T::KnightIndex = knight.id;
+        /// Put a knight the caller owns up for auction until block `end`.
+        ///
+        /// The knight is locked until the auction is settled at the start of `end`.
+        #[pallet::weight(T::WeightInfo::start_auction())]
+        pub fn start_auction(
+            origin: OriginFor<T>,
+            knight_id: T::KnightIndex,
+            reserve_price: T::Balance,
+            end: T::BlockNumber,
+        ) -> DispatchResultWithPostInfo {
+            let who = ensure_signed(origin)?;
 
-            let current_count = KnightCount::<T>::get();
-            let new_count = current_count
-                .checked_add(One::one())
-                .ok_or(Error::<T>::KnightCountOverflow)?;
-            KnightCount::<T>::put(new_count);
+            let knight = Knights::<T>::get(knight_id).ok_or(Error::<T>::KnightNotFound)?;
+            let owner = KnightToOwner::<T>::get(knight_id).ok_or(Error::<T>::KnightNotFound)?;
+            ensure!(owner == who, Error::<T>::NotRightfulOwner);
+            ensure!(knight.price.is_zero(), Error::<T>::KnightForSale);
+            Self::ensure_unlocked(knight_id)?;
 
-            Knights::<T>::insert(knight.id, knight);
-            KnightToOwner::<T>::insert(knight_id, owner);
-            OwnerToKnights::<T>::append(owner, knight_id);
-            LatestKnightId::<T>::put(knight_id);
+            let now = frame_system::Pallet::<T>::block_number();
+            ensure!(
+                end >= now.saturating_add(T::MinAuctionDuration::get())
+                    && end <= now.saturating_add(T::MaxAuctionDuration::get()),
+                Error::<T>::InvalidAuctionEnd
+            );
 
-            let current_owner_to_knight_count = OwnerToKnightCount::<T>::get(owner);
-            let new_count = current_owner_to_knight_count
-                .checked_add(1)
-                .ok_or(Error::<T>::OwnerToKnightCountOverflow)?;
+            Self::_schedule_settlement(knight_id, end)?;
 
-            OwnerToKnightCount::<T>::insert(owner, new_count);
+            Auctions::<T>::insert(
+                knight_id,
+                Auction {
+                    seller: who.clone(),
+                    reserve_price,
+                    end,
+                    highest_bid: None,
+                },
+            );
+            KnightLocks::<T>::insert(knight_id, KnightLock::Auction);
 
-            Self::deposit_event(Event::KnightCreated(knight_id, owner.clone()));
+            Self::deposit_event(Event::AuctionStarted(knight_id, who, reserve_price, end));
 
-            Ok(())
+            Ok(().into())
         }
 
-        fn _transfer_knight(
+        /// Bid on an auction. The bid is reserved until the caller is outbid
+        /// or the auction is settled.
+        #[pallet::weight(T::WeightInfo::bid())]
+        pub fn bid(
+            origin: OriginFor<T>,
             knight_id: T::KnightIndex,
-            from: T::AccountId,
-            to: T::AccountId,
-        ) -> Result<(), DispatchError> {
-            // you could argue this check really isn't needed;
-            // nevertheless, if we did want to check, we'd do it
-            // before writing to storage below.
-            match OwnerToKnights::<T>::get(&to)
-                .iter()
-                .position(|&k_id| k_id == knight_id)
-            {
-                Some(_pos) => {
-                    return Err(Error::<T>::KnightAlreadyExists)?;
+            amount: T::Balance,
+        ) -> DispatchResultWithPostInfo {
+            let who = ensure_signed(origin)?;
+
+            let mut auction = Auctions::<T>::get(knight_id).ok_or(Error::<T>::AuctionNotFound)?;
+            ensure!(auction.seller != who, Error::<T>::CannotBidOnOwnAuction);
+            ensure!(amount >= auction.reserve_price, Error::<T>::BidTooLow);
+
+            match auction.highest_bid {
+                Some((ref bidder, highest)) => {
+                    ensure!(amount > highest, Error::<T>::BidTooLow);
+
+                    if *bidder == who {
+                        // raising your own bid only reserves the difference
+                        T::Currency::reserve(&who, amount - highest)?;
+                    } else {
+                        T::Currency::reserve(&who, amount)?;
+                        T::Currency::unreserve(bidder, highest);
+                    }
+                }
+                None => {
+                    T::Currency::reserve(&who, amount)?;
                 }
-                _ => {}
             }
 
-            KnightToOwner::<T>::remove(knight_id);
-            KnightToOwner::<T>::insert(knight_id, &to);
+            auction.highest_bid = Some((who.clone(), amount));
+            Auctions::<T>::insert(knight_id, auction);
+
+            Self::deposit_event(Event::BidPlaced(knight_id, who, amount));
+
+            Ok(().into())
+        }
+
+        /// Withdraw a knight from auction. Only possible before anyone has bid.
+        #[pallet::weight(T::WeightInfo::cancel_auction())]
+        pub fn cancel_auction(
+            origin: OriginFor<T>,
+            knight_id: T::KnightIndex,
+        ) -> DispatchResultWithPostInfo {
+            let who = ensure_signed(origin)?;
+
+            let auction = Auctions::<T>::get(knight_id).ok_or(Error::<T>::AuctionNotFound)?;
+            ensure!(auction.seller == who, Error::<T>::NotRightfulOwner);
+            ensure!(auction.highest_bid.is_none(), Error::<T>::AuctionHasBids);
+
+            AuctionsEndingAt::<T>::mutate(auction.end, |ending| {
+                ending.retain(|id| *id != knight_id)
+            });
+            Auctions::<T>::remove(knight_id);
+            KnightLocks::<T>::remove(knight_id);
+
+            Self::deposit_event(Event::AuctionClosed(knight_id, who));
+
+            Ok(().into())
+        }
+
+        /// Put a knight the caller owns up for a Dutch auction.
+        ///
+        /// The price falls linearly from `start_price` to `floor_price` over `duration`
+        /// blocks and then stays at the floor until the knight is bought or withdrawn.
+        #[pallet::weight(T::WeightInfo::start_dutch_auction())]
+        pub fn start_dutch_auction(
+            origin: OriginFor<T>,
+            knight_id: T::KnightIndex,
+            start_price: T::Balance,
+            floor_price: T::Balance,
+            duration: T::BlockNumber,
+        ) -> DispatchResultWithPostInfo {
+            let who = ensure_signed(origin)?;
+
+            let knight = Knights::<T>::get(knight_id).ok_or(Error::<T>::KnightNotFound)?;
+            let owner = KnightToOwner::<T>::get(knight_id).ok_or(Error::<T>::KnightNotFound)?;
+            ensure!(owner == who, Error::<T>::NotRightfulOwner);
+            ensure!(knight.price.is_zero(), Error::<T>::KnightForSale);
+            Self::ensure_unlocked(knight_id)?;
+
+            ensure!(floor_price <= start_price, Error::<T>::FloorAboveStartPrice);
+            ensure!(
+                duration >= T::MinAuctionDuration::get()
+                    && duration <= T::MaxAuctionDuration::get(),
+                Error::<T>::InvalidAuctionEnd
+            );
+
+            DutchAuctions::<T>::insert(
+                knight_id,
+                DutchAuction {
+                    seller: who.clone(),
+                    start_price,
+                    floor_price,
+                    start: frame_system::Pallet::<T>::block_number(),
+                    duration,
+                },
+            );
+            KnightLocks::<T>::insert(knight_id, KnightLock::DutchAuction);
+
+            Self::deposit_event(Event::DutchAuctionStarted(
+                knight_id,
+                who,
+                start_price,
+                floor_price,
+                duration,
+            ));
+
+            Ok(().into())
+        }
+
+        /// Buy a knight from a Dutch auction at its current price.
+        ///
+        /// `max_price` guards against paying more than expected; the buyer
+        /// is only charged the current price.
+        #[pallet::weight(T::WeightInfo::buy_from_dutch_auction())]
+        pub fn buy_from_dutch_auction(
+            origin: OriginFor<T>,
+            knight_id: T::KnightIndex,
+            max_price: T::Balance,
+        ) -> DispatchResultWithPostInfo {
+            let buyer = ensure_signed(origin)?;
+
+            let auction = DutchAuctions::<T>::get(knight_id).ok_or(Error::<T>::AuctionNotFound)?;
+            ensure!(auction.seller != buyer, Error::<T>::CannotBidOnOwnAuction);
+
+            let price = Self::dutch_price(&auction, frame_system::Pallet::<T>::block_number());
+            ensure!(max_price >= price, Error::<T>::BidTooLow);
+
+            Self::_sell(
+                knight_id,
+                auction.seller.clone(),
+                buyer.clone(),
+                price,
+                Zero::zero(),
+                Payment::Free,
+            )?;
+            DutchAuctions::<T>::remove(knight_id);
+            KnightLocks::<T>::remove(knight_id);
+
+            Self::deposit_event(Event::AuctionWon(knight_id, auction.seller, buyer, price));
 
-            // remove the knight_id from owner's list of knight ids
-            OwnerToKnights::<T>::mutate(&from, |ids| {
-                // mutable reference
-                let pos = ids
-                    .binary_search_by(|probe| probe.cmp(&knight_id))
-                    .expect("Knight not found. Perhaps it was already transferred.");
+            Ok(().into())
+        }
 
-                ids.remove(pos);
+        /// Withdraw a knight from a Dutch auction.
+        #[pallet::weight(T::WeightInfo::cancel_dutch_auction())]
+        pub fn cancel_dutch_auction(
+            origin: OriginFor<T>,
+            knight_id: T::KnightIndex,
+        ) -> DispatchResultWithPostInfo {
+            let who = ensure_signed(origin)?;
+
+            let auction = DutchAuctions::<T>::get(knight_id).ok_or(Error::<T>::AuctionNotFound)?;
+            ensure!(auction.seller == who, Error::<T>::NotRightfulOwner);
+
+            DutchAuctions::<T>::remove(knight_id);
+            KnightLocks::<T>::remove(knight_id);
+
+            Self::deposit_event(Event::AuctionClosed(knight_id, who));
+
+            Ok(().into())
+        }
+
+        /// Put a knight the caller owns up for a sealed-bid auction.
+        ///
+        /// Bids are committed until `commit_end` and revealed for `RevealPeriod` blocks
+        /// after that. The highest revealed bid wins and pays the second-highest,
+        /// or the reserve price if nobody else revealed.
+        #[pallet::weight(T::WeightInfo::start_sealed_auction())]
+        pub fn start_sealed_auction(
+            origin: OriginFor<T>,
+            knight_id: T::KnightIndex,
+            reserve_price: T::Balance,
+            commit_end: T::BlockNumber,
+        ) -> DispatchResultWithPostInfo {
+            let who = ensure_signed(origin)?;
+
+            let knight = Knights::<T>::get(knight_id).ok_or(Error::<T>::KnightNotFound)?;
+            let owner = KnightToOwner::<T>::get(knight_id).ok_or(Error::<T>::KnightNotFound)?;
+            ensure!(owner == who, Error::<T>::NotRightfulOwner);
+            ensure!(knight.price.is_zero(), Error::<T>::KnightForSale);
+            Self::ensure_unlocked(knight_id)?;
+
+            let now = frame_system::Pallet::<T>::block_number();
+            ensure!(
+                commit_end >= now.saturating_add(T::MinAuctionDuration::get())
+                    && commit_end <= now.saturating_add(T::MaxAuctionDuration::get()),
+                Error::<T>::InvalidAuctionEnd
+            );
+            let reveal_end = commit_end.saturating_add(T::RevealPeriod::get());
+
+            Self::_schedule_settlement(knight_id, reveal_end)?;
+
+            SealedAuctions::<T>::insert(
+                knight_id,
+                SealedAuction {
+                    seller: who.clone(),
+                    reserve_price,
+                    commit_end,
+                    reveal_end,
+                    bid_count: 0,
+                    highest_bid: None,
+                    second_bid: None,
+                },
+            );
+            KnightLocks::<T>::insert(knight_id, KnightLock::SealedAuction);
+
+            Self::deposit_event(Event::SealedAuctionStarted(
+                knight_id,
+                who,
+                reserve_price,
+                commit_end,
+                reveal_end,
+            ));
+
+            Ok(().into())
+        }
+
+        /// Commit to a bid on a sealed-bid auction.
+        ///
+        /// `commitment` is the hash of `(bid, salt)`. The deposit is held in reserve
+        /// and must cover the bid; it is slashed if the bid is never revealed.
+        #[pallet::weight(T::WeightInfo::commit_bid())]
+        pub fn commit_bid(
+            origin: OriginFor<T>,
+            knight_id: T::KnightIndex,
+            commitment: T::Hash,
+            deposit: T::Balance,
+        ) -> DispatchResultWithPostInfo {
+            let who = ensure_signed(origin)?;
+
+            let mut auction =
+                SealedAuctions::<T>::get(knight_id).ok_or(Error::<T>::AuctionNotFound)?;
+            ensure!(auction.seller != who, Error::<T>::CannotBidOnOwnAuction);
+            ensure!(
+                frame_system::Pallet::<T>::block_number() < auction.commit_end,
+                Error::<T>::CommitPhaseOver
+            );
+            ensure!(
+                !SealedBids::<T>::contains_key(knight_id, &who),
+                Error::<T>::AlreadyCommitted
+            );
+            ensure!(
+                auction.bid_count < T::MaxSealedBids::get(),
+                Error::<T>::TooManySealedBids
+            );
+
+            T::Currency::reserve(&who, deposit)?;
+
+            auction.bid_count += 1;
+            SealedAuctions::<T>::insert(knight_id, auction);
+            SealedBids::<T>::insert(
+                knight_id,
+                &who,
+                SealedBid {
+                    commitment,
+                    deposit,
+                    revealed: false,
+                },
+            );
+
+            Self::deposit_event(Event::BidCommitted(knight_id, who, deposit));
+
+            Ok(().into())
+        }
+
+        /// Reveal a committed bid.
+        ///
+        /// A bid below the reserve price cannot win, so its deposit is returned at
+        /// once. Bids never revealed have their deposits slashed when the auction ends.
+        #[pallet::weight(T::WeightInfo::reveal_bid())]
+        pub fn reveal_bid(
+            origin: OriginFor<T>,
+            knight_id: T::KnightIndex,
+            bid: T::Balance,
+            salt: H256,
+        ) -> DispatchResultWithPostInfo {
+            let who = ensure_signed(origin)?;
+
+            let mut auction =
+                SealedAuctions::<T>::get(knight_id).ok_or(Error::<T>::AuctionNotFound)?;
+            let now = frame_system::Pallet::<T>::block_number();
+            ensure!(
+                now >= auction.commit_end && now < auction.reveal_end,
+                Error::<T>::NotRevealPhase
+            );
+
+            let mut sealed_bid =
+                SealedBids::<T>::get(knight_id, &who).ok_or(Error::<T>::SealedBidNotFound)?;
+            ensure!(!sealed_bid.revealed, Error::<T>::AlreadyRevealed);
+            ensure!(
+                <T::Hashing as HashT>::hash_of(&(bid, salt)) == sealed_bid.commitment,
+                Error::<T>::CommitmentMismatch
+            );
+            ensure!(bid <= sealed_bid.deposit, Error::<T>::BidExceedsDeposit);
+
+            if bid < auction.reserve_price {
+                // an honest bid below the reserve cannot win, but revealing it earns
+                // the deposit back rather than seeing it slashed at settlement
+                T::Currency::unreserve(&who, sealed_bid.deposit);
+                sealed_bid.deposit = Zero::zero();
+            } else {
+                match auction.highest_bid {
+                    Some((_, highest)) if bid <= highest => {
+                        if auction.second_bid.map_or(true, |second| bid > second) {
+                            auction.second_bid = Some(bid);
+                        }
+                    }
+                    Some((_, highest)) => {
+                        auction.second_bid = Some(highest);
+                        auction.highest_bid = Some((who.clone(), bid));
+                    }
+                    None => {
+                        auction.highest_bid = Some((who.clone(), bid));
+                    }
+                }
+            }
+
+            sealed_bid.revealed = true;
+            SealedBids::<T>::insert(knight_id, &who, sealed_bid);
+            SealedAuctions::<T>::insert(knight_id, auction);
+
+            Self::deposit_event(Event::BidRevealed(knight_id, who, bid));
+
+            Ok(().into())
+        }
+
+        /// Withdraw a knight from a sealed-bid auction. Only possible before anyone has bid.
+        #[pallet::weight(T::WeightInfo::cancel_sealed_auction())]
+        pub fn cancel_sealed_auction(
+            origin: OriginFor<T>,
+            knight_id: T::KnightIndex,
+        ) -> DispatchResultWithPostInfo {
+            let who = ensure_signed(origin)?;
+
+            let auction = SealedAuctions::<T>::get(knight_id).ok_or(Error::<T>::AuctionNotFound)?;
+            ensure!(auction.seller == who, Error::<T>::NotRightfulOwner);
+            ensure!(auction.bid_count == 0, Error::<T>::AuctionHasBids);
+
+            AuctionsEndingAt::<T>::mutate(auction.reveal_end, |ending| {
+                ending.retain(|id| *id != knight_id)
+            });
+            SealedAuctions::<T>::remove(knight_id);
+            KnightLocks::<T>::remove(knight_id);
+
+            Self::deposit_event(Event::AuctionClosed(knight_id, who));
+
+            Ok(().into())
+        }
+
+        /// Offer to buy any knight, whether or not its owner has set a price.
+        ///
+        /// The amount is held in reserve until the owner accepts, the caller cancels,
+        /// or the offer expires after `OfferDuration` blocks.
+        ///
+        /// The offer is for the knight only: its owner may withdraw the knight's wealth
+        /// before accepting, so offer on what the vault holds at your own risk.
+        #[pallet::weight(T::WeightInfo::make_offer())]
+        pub fn make_offer(
+            origin: OriginFor<T>,
+            knight_id: T::KnightIndex,
+            amount: T::Balance,
+        ) -> DispatchResultWithPostInfo {
+            let who = ensure_signed(origin)?;
+
+            let owner = KnightToOwner::<T>::get(knight_id).ok_or(Error::<T>::KnightNotFound)?;
+            ensure!(owner != who, Error::<T>::CannotOfferOnOwnKnight);
+            ensure!(amount >= T::MinOfferAmount::get(), Error::<T>::OfferTooLow);
+            ensure!(
+                !Offers::<T>::contains_key(knight_id, &who),
+                Error::<T>::AlreadyOffered
+            );
+
+            let expires =
+                frame_system::Pallet::<T>::block_number().saturating_add(T::OfferDuration::get());
+            let mut expiring = OffersExpiringAt::<T>::get(expires);
+            ensure!(
+                (expiring.len() as u32) < T::MaxOffersPerBlock::get(),
+                Error::<T>::TooManyOffersExpiring
+            );
+
+            T::Currency::reserve(&who, amount)?;
+
+            expiring.push((knight_id, who.clone()));
+            OffersExpiringAt::<T>::insert(expires, expiring);
+            Offers::<T>::insert(knight_id, &who, Offer { amount, expires });
+
+            Self::deposit_event(Event::OfferMade(knight_id, who, amount, expires));
+
+            Ok(().into())
+        }
+
+        /// Withdraw an offer and release the funds held for it.
+        #[pallet::weight(T::WeightInfo::cancel_offer())]
+        pub fn cancel_offer(
+            origin: OriginFor<T>,
+            knight_id: T::KnightIndex,
+        ) -> DispatchResultWithPostInfo {
+            let who = ensure_signed(origin)?;
+
+            let offer = Self::_take_offer(knight_id, &who)?;
+            T::Currency::unreserve(&who, offer.amount);
+
+            Self::deposit_event(Event::OfferCancelled(knight_id, who));
+
+            Ok(().into())
+        }
+
+        /// Bid `price` each for up to `quantity` knights of generation `gen` whose DNA
+        /// matches `dna_pattern` on the bits set in `dna_mask`.
+        ///
+        /// `price * quantity` is held in reserve until the bid is filled or cancelled.
+        /// Like an offer, the bid does not cover what the knights' vaults hold.
+        #[pallet::weight(T::WeightInfo::place_collection_bid())]
+        pub fn place_collection_bid(
+            origin: OriginFor<T>,
+            gen: u64,
+            dna_mask: [u8; 16],
+            dna_pattern: [u8; 16],
+            price: T::Balance,
+            quantity: u32,
+        ) -> DispatchResultWithPostInfo {
+            let who = ensure_signed(origin)?;
+
+            ensure!(quantity > 0, Error::<T>::ZeroQuantity);
+
+            let bid_id = NextCollectionBidId::<T>::get();
+            let next_bid_id = bid_id
+                .checked_add(1)
+                .ok_or(Error::<T>::CollectionBidIdOverflow)?;
+
+            T::Currency::reserve(&who, price.saturating_mul(quantity.into()))?;
+
+            NextCollectionBidId::<T>::put(next_bid_id);
+            CollectionBids::<T>::insert(
+                bid_id,
+                CollectionBid {
+                    bidder: who.clone(),
+                    gen,
+                    dna_mask,
+                    dna_pattern,
+                    price,
+                    remaining: quantity,
+                },
+            );
+
+            Self::deposit_event(Event::CollectionBidPlaced(
+                bid_id, who, gen, price, quantity,
+            ));
+
+            Ok(().into())
+        }
+
+        /// Withdraw a collection bid and release the funds still held for it.
+        #[pallet::weight(T::WeightInfo::cancel_collection_bid())]
+        pub fn cancel_collection_bid(
+            origin: OriginFor<T>,
+            bid_id: u64,
+        ) -> DispatchResultWithPostInfo {
+            let who = ensure_signed(origin)?;
+
+            let bid = CollectionBids::<T>::get(bid_id).ok_or(Error::<T>::CollectionBidNotFound)?;
+            ensure!(bid.bidder == who, Error::<T>::NotRightfulOwner);
+
+            CollectionBids::<T>::remove(bid_id);
+            T::Currency::unreserve(&who, bid.price.saturating_mul(bid.remaining.into()));
+
+            Self::deposit_event(Event::CollectionBidCancelled(bid_id, who));
+
+            Ok(().into())
+        }
+
+        /// Sell a knight the caller owns into a matching collection bid.
+        #[pallet::weight(T::WeightInfo::fill_collection_bid())]
+        pub fn fill_collection_bid(
+            origin: OriginFor<T>,
+            bid_id: u64,
+            knight_id: T::KnightIndex,
+        ) -> DispatchResultWithPostInfo {
+            let who = ensure_signed(origin)?;
+
+            let mut bid =
+                CollectionBids::<T>::get(bid_id).ok_or(Error::<T>::CollectionBidNotFound)?;
+            ensure!(bid.bidder != who, Error::<T>::CannotFillOwnBid);
+
+            Self::ensure_may_transfer(knight_id, &who)?;
+            let knight = Knights::<T>::get(knight_id).ok_or(Error::<T>::KnightNotFound)?;
+            ensure!(
+                Self::matches_collection_bid(&knight, &bid),
+                Error::<T>::KnightDoesNotMatchBid
+            );
+
+            Self::_sell(
+                knight_id,
+                who.clone(),
+                bid.bidder.clone(),
+                bid.price,
+                Zero::zero(),
+                Payment::Reserved,
+            )?;
+
+            bid.remaining -= 1;
+            if bid.remaining == 0 {
+                CollectionBids::<T>::remove(bid_id);
+            } else {
+                CollectionBids::<T>::insert(bid_id, &bid);
+            }
+
+            Self::deposit_event(Event::CollectionBidFilled(
+                bid_id, knight_id, who, bid.bidder, bid.price,
+            ));
+
+            Ok(().into())
+        }
+
+        /// Sell a knight the caller owns to `buyer` for the amount they offered.
+        #[pallet::weight(T::WeightInfo::accept_offer())]
+        pub fn accept_offer(
+            origin: OriginFor<T>,
+            knight_id: T::KnightIndex,
+            buyer: T::AccountId,
+        ) -> DispatchResultWithPostInfo {
+            let who = ensure_signed(origin)?;
+
+            Self::ensure_may_transfer(knight_id, &who)?;
+            // the buyer may have got hold of the knight since making the offer
+            ensure!(buyer != who, Error::<T>::CannotOfferOnOwnKnight);
+
+            let offer = Offers::<T>::get(knight_id, &buyer).ok_or(Error::<T>::OfferNotFound)?;
+            Self::_sell(
+                knight_id,
+                who.clone(),
+                buyer.clone(),
+                offer.amount,
+                Zero::zero(),
+                Payment::Reserved,
+            )?;
+            Self::_take_offer(knight_id, &buyer)?;
+
+            Self::deposit_event(Event::OfferAccepted(knight_id, who, buyer, offer.amount));
+
+            Ok(().into())
+        }
+
+        /// Let `spender` transfer one knight with `transfer_from`.
+        ///
+        /// Callable by the owner or one of their operators. Replaces any earlier
+        /// approval and lasts until the knight changes hands.
+        #[pallet::weight(T::WeightInfo::approve())]
+        pub fn approve(
+            origin: OriginFor<T>,
+            knight_id: T::KnightIndex,
+            spender: T::AccountId,
+        ) -> DispatchResultWithPostInfo {
+            let who = ensure_signed(origin)?;
+
+            let owner = KnightToOwner::<T>::get(knight_id).ok_or(Error::<T>::KnightNotFound)?;
+            ensure!(
+                owner == who || OperatorApprovals::<T>::get(&owner, &who),
+                Error::<T>::NotRightfulOwner
+            );
+            ensure!(spender != owner, Error::<T>::CannotApproveSelf);
+
+            TransferApprovals::<T>::insert(knight_id, &spender);
+
+            Self::deposit_event(Event::Approval(knight_id, owner, spender));
+
+            Ok(().into())
+        }
+
+        /// Withdraw the approval on a knight. Callable by the owner or one of their operators.
+        #[pallet::weight(T::WeightInfo::cancel_approval())]
+        pub fn cancel_approval(
+            origin: OriginFor<T>,
+            knight_id: T::KnightIndex,
+        ) -> DispatchResultWithPostInfo {
+            let who = ensure_signed(origin)?;
+
+            let owner = KnightToOwner::<T>::get(knight_id).ok_or(Error::<T>::KnightNotFound)?;
+            ensure!(
+                owner == who || OperatorApprovals::<T>::get(&owner, &who),
+                Error::<T>::NotRightfulOwner
+            );
+
+            TransferApprovals::<T>::remove(knight_id);
+
+            Self::deposit_event(Event::ApprovalCancelled(knight_id, owner));
+
+            Ok(().into())
+        }
+
+        /// Let `operator` transfer, and approve others to transfer, every knight the caller owns.
+        #[pallet::weight(T::WeightInfo::set_approval_for_all())]
+        pub fn set_approval_for_all(
+            origin: OriginFor<T>,
+            operator: T::AccountId,
+            approved: bool,
+        ) -> DispatchResultWithPostInfo {
+            let who = ensure_signed(origin)?;
+
+            ensure!(operator != who, Error::<T>::CannotApproveSelf);
+
+            if approved {
+                OperatorApprovals::<T>::insert(&who, &operator, true);
+            } else {
+                OperatorApprovals::<T>::remove(&who, &operator);
+            }
+
+            Self::deposit_event(Event::ApprovalForAll(who, operator, approved));
+
+            Ok(().into())
+        }
+
+        /// Transfer a knight on behalf of its owner `from`.
+        ///
+        /// The caller must be the owner, the knight's approved spender, or an operator of the owner.
+        #[pallet::weight(T::WeightInfo::transfer_from())]
+        pub fn transfer_from(
+            origin: OriginFor<T>,
+            knight_id: T::KnightIndex,
+            from: T::AccountId,
+            to: T::AccountId,
+        ) -> DispatchResultWithPostInfo {
+            let who = ensure_signed(origin)?;
+
+            Self::ensure_may_transfer(knight_id, &from)?;
+            ensure!(
+                Self::is_approved_or_owner(knight_id, &from, &who),
+                Error::<T>::NotApprovedToTransfer
+            );
+
+            Self::_transfer_knight(knight_id, from, to)?;
+
+            Ok(().into())
+        }
+
+        /// Give funds to a knight. Anyone may deposit into any knight.
+        ///
+        /// The funds are held in the knight's vault account and go with the knight
+        /// whenever it changes hands.
+        #[pallet::weight(T::WeightInfo::deposit_wealth())]
+        pub fn deposit_wealth(
+            origin: OriginFor<T>,
+            knight_id: T::KnightIndex,
+            amount: T::Balance,
+        ) -> DispatchResultWithPostInfo {
+            let who = ensure_signed(origin)?;
+
+            ensure!(
+                Knights::<T>::contains_key(knight_id),
+                Error::<T>::KnightNotFound
+            );
+
+            T::Currency::transfer(
+                &who,
+                &Self::vault_account(knight_id),
+                amount,
+                ExistenceRequirement::KeepAlive,
+            )?;
+            Self::_sync_wealth(knight_id);
+
+            Self::deposit_event(Event::WealthDeposited(knight_id, who, amount));
+
+            Ok(().into())
+        }
+
+        /// Take funds out of a knight the caller owns.
+        ///
+        /// Not allowed while the knight is locked or has a price set.
+        #[pallet::weight(T::WeightInfo::withdraw_wealth())]
+        pub fn withdraw_wealth(
+            origin: OriginFor<T>,
+            knight_id: T::KnightIndex,
+            amount: T::Balance,
+        ) -> DispatchResultWithPostInfo {
+            let who = ensure_signed(origin)?;
+
+            // a knight's wealth is part of what a buyer bids on, so it stays put while
+            // the knight is locked or listed for sale
+            Self::ensure_may_transfer(knight_id, &who)?;
+            let knight = Knights::<T>::get(knight_id).ok_or(Error::<T>::KnightNotFound)?;
+            ensure!(knight.price.is_zero(), Error::<T>::KnightForSale);
+
+            T::Currency::transfer(
+                &Self::vault_account(knight_id),
+                &who,
+                amount,
+                ExistenceRequirement::AllowDeath,
+            )?;
+            Self::_sync_wealth(knight_id);
+
+            Self::deposit_event(Event::WealthWithdrawn(knight_id, who, amount));
+
+            Ok(().into())
+        }
+
+        /// Challenge another knight to a duel, staking `wager`.
+        ///
+        /// The wager is held in reserve and the challenging knight is locked until
+        /// the opponent's owner accepts or the challenge is cancelled.
+        #[pallet::weight(T::WeightInfo::challenge())]
+        pub fn challenge(
+            origin: OriginFor<T>,
+            knight_id: T::KnightIndex,
+            opponent_id: T::KnightIndex,
+            wager: T::Balance,
+        ) -> DispatchResultWithPostInfo {
+            let who = ensure_signed(origin)?;
+
+            let owner = KnightToOwner::<T>::get(knight_id).ok_or(Error::<T>::KnightNotFound)?;
+            ensure!(owner == who, Error::<T>::NotRightfulOwner);
+            Self::ensure_unlocked(knight_id)?;
+
+            let opponent_owner =
+                KnightToOwner::<T>::get(opponent_id).ok_or(Error::<T>::KnightNotFound)?;
+            ensure!(opponent_owner != who, Error::<T>::CannotDuelOwnKnight);
+
+            T::Currency::reserve(&who, wager)?;
+
+            KnightLocks::<T>::insert(knight_id, KnightLock::Duel);
+            DuelChallenges::<T>::insert(
+                knight_id,
+                DuelChallenge {
+                    challenger: who.clone(),
+                    opponent: opponent_id,
+                    wager,
+                },
+            );
+
+            Self::deposit_event(Event::DuelChallenged(knight_id, opponent_id, who, wager));
+
+            Ok(().into())
+        }
+
+        /// Withdraw a challenge that has not been accepted, returning the wager.
+        #[pallet::weight(T::WeightInfo::cancel_challenge())]
+        pub fn cancel_challenge(
+            origin: OriginFor<T>,
+            knight_id: T::KnightIndex,
+        ) -> DispatchResultWithPostInfo {
+            let who = ensure_signed(origin)?;
+
+            let challenge =
+                DuelChallenges::<T>::get(knight_id).ok_or(Error::<T>::ChallengeNotFound)?;
+            ensure!(challenge.challenger == who, Error::<T>::NotRightfulOwner);
+            ensure!(
+                !AcceptedDuels::<T>::contains_key(knight_id),
+                Error::<T>::ChallengeAccepted
+            );
+
+            DuelChallenges::<T>::remove(knight_id);
+            KnightLocks::<T>::remove(knight_id);
+            T::Currency::unreserve(&who, challenge.wager);
+
+            Self::deposit_event(Event::DuelChallengeCancelled(knight_id, who));
+
+            Ok(().into())
+        }
+
+        /// Take up a challenge made to a knight the caller owns.
+        ///
+        /// The caller stakes the same wager and both knights are locked until the duel
+        /// is fought at the start of the next block, with randomness not yet known when
+        /// the challenge is accepted. The winner's owner takes both wagers. `max_wager`
+        /// guards against the challenge being replaced with a larger one in the meantime.
+        #[pallet::weight(T::WeightInfo::accept_challenge())]
+        pub fn accept_challenge(
+            origin: OriginFor<T>,
+            challenger_id: T::KnightIndex,
+            max_wager: T::Balance,
+        ) -> DispatchResultWithPostInfo {
+            let who = ensure_signed(origin)?;
+
+            let challenge =
+                DuelChallenges::<T>::get(challenger_id).ok_or(Error::<T>::ChallengeNotFound)?;
+            ensure!(challenge.wager <= max_wager, Error::<T>::WagerTooHigh);
+
+            let owner =
+                KnightToOwner::<T>::get(challenge.opponent).ok_or(Error::<T>::KnightNotFound)?;
+            ensure!(owner == who, Error::<T>::NotRightfulOwner);
+            ensure!(who != challenge.challenger, Error::<T>::CannotDuelOwnKnight);
+            ensure!(
+                !AcceptedDuels::<T>::contains_key(challenger_id),
+                Error::<T>::ChallengeAccepted
+            );
+            Self::ensure_unlocked(challenge.opponent)?;
+
+            let fight_at = frame_system::Pallet::<T>::block_number() + One::one();
+            let mut fought = DuelsFoughtAt::<T>::get(fight_at);
+            ensure!(
+                (fought.len() as u32) < T::MaxDuelsPerBlock::get(),
+                Error::<T>::TooManyDuels
+            );
+
+            T::Currency::reserve(&who, challenge.wager)?;
+
+            KnightLocks::<T>::insert(challenge.opponent, KnightLock::Duel);
+            AcceptedDuels::<T>::insert(challenger_id, &who);
+            fought.push(challenger_id);
+            DuelsFoughtAt::<T>::insert(fight_at, fought);
+
+            Self::deposit_event(Event::DuelAccepted(
+                challenger_id,
+                challenge.opponent,
+                who,
+                fight_at,
+            ));
+
+            Ok(().into())
+        }
+
+        /// Open registration for a tournament of `size` knights until `registration_end`.
+        ///
+        /// The tournament begins once the bracket is full, fighting a round every
+        /// `TournamentRoundInterval` blocks. If the bracket is not full by
+        /// `registration_end`, the tournament is cancelled and every entrant refunded.
+        #[pallet::weight(T::WeightInfo::open_tournament())]
+        pub fn open_tournament(
+            origin: OriginFor<T>,
+            entry_fee: T::Balance,
+            size: u32,
+            registration_end: T::BlockNumber,
+        ) -> DispatchResultWithPostInfo {
+            let who = ensure_signed(origin)?;
+
+            ensure!(
+                size >= 2 && size <= T::MaxTournamentSize::get() && size.is_power_of_two(),
+                Error::<T>::InvalidBracketSize
+            );
+
+            let now = frame_system::Pallet::<T>::block_number();
+            ensure!(
+                registration_end > now
+                    && registration_end <= now.saturating_add(T::MaxRegistrationPeriod::get()),
+                Error::<T>::InvalidRegistrationEnd
+            );
+            let mut closing = RegistrationsClosingAt::<T>::get(registration_end);
+            ensure!(
+                (closing.len() as u32) < T::MaxRegistrationsPerBlock::get(),
+                Error::<T>::TooManyRegistrationsClosing
+            );
+
+            let tournament_id = NextTournamentId::<T>::get();
+            let next_tournament_id = tournament_id
+                .checked_add(1)
+                .ok_or(Error::<T>::TournamentIdOverflow)?;
+
+            Tournaments::<T>::insert(
+                tournament_id,
+                Tournament {
+                    organiser: who.clone(),
+                    entry_fee,
+                    size,
+                    contenders: Vec::new(),
+                    round: 0,
+                    registration_end,
+                },
+            );
+            NextTournamentId::<T>::put(next_tournament_id);
+            closing.push(tournament_id);
+            RegistrationsClosingAt::<T>::insert(registration_end, closing);
+
+            Self::deposit_event(Event::TournamentOpened(tournament_id, who, entry_fee, size));
+
+            Ok(().into())
+        }
+
+        /// Enter a knight the caller owns, paying the entry fee into the prize pool.
+        ///
+        /// The knight is locked until it is knocked out, leaves, or the tournament is cancelled.
+        #[pallet::weight(T::WeightInfo::enter_tournament())]
+        pub fn enter_tournament(
+            origin: OriginFor<T>,
+            tournament_id: u64,
+            knight_id: T::KnightIndex,
+        ) -> DispatchResultWithPostInfo {
+            let who = ensure_signed(origin)?;
+
+            let mut tournament =
+                Tournaments::<T>::get(tournament_id).ok_or(Error::<T>::TournamentNotFound)?;
+            ensure!(tournament.round == 0, Error::<T>::TournamentUnderway);
+
+            let owner = KnightToOwner::<T>::get(knight_id).ok_or(Error::<T>::KnightNotFound)?;
+            ensure!(owner == who, Error::<T>::NotRightfulOwner);
+            Self::ensure_unlocked(knight_id)?;
+
+            tournament.contenders.push(knight_id);
+            let full = tournament.contenders.len() as u32 == tournament.size;
+
+            let mut active = ActiveTournaments::<T>::get();
+            if full {
+                ensure!(
+                    (active.len() as u32) < T::MaxActiveTournaments::get(),
+                    Error::<T>::TooManyActiveTournaments
+                );
+            }
+
+            T::Currency::transfer(
+                &who,
+                &Self::tournament_account(tournament_id),
+                tournament.entry_fee,
+                ExistenceRequirement::KeepAlive,
+            )?;
+
+            if full {
+                tournament.round = 1;
+                active.push(tournament_id);
+                ActiveTournaments::<T>::put(active);
+                RegistrationsClosingAt::<T>::mutate(tournament.registration_end, |closing| {
+                    closing.retain(|id| *id != tournament_id)
+                });
+            }
+            KnightLocks::<T>::insert(knight_id, KnightLock::Tournament);
+            Tournaments::<T>::insert(tournament_id, tournament);
+
+            Self::deposit_event(Event::TournamentEntered(tournament_id, knight_id, who));
+
+            Ok(().into())
+        }
+
+        /// Withdraw a knight the caller owns from a tournament that has not begun,
+        /// taking back its entry fee.
+        #[pallet::weight(T::WeightInfo::leave_tournament(T::MaxTournamentSize::get()))]
+        pub fn leave_tournament(
+            origin: OriginFor<T>,
+            tournament_id: u64,
+            knight_id: T::KnightIndex,
+        ) -> DispatchResultWithPostInfo {
+            let who = ensure_signed(origin)?;
+
+            let mut tournament =
+                Tournaments::<T>::get(tournament_id).ok_or(Error::<T>::TournamentNotFound)?;
+            ensure!(tournament.round == 0, Error::<T>::TournamentUnderway);
+
+            let owner = KnightToOwner::<T>::get(knight_id).ok_or(Error::<T>::KnightNotFound)?;
+            ensure!(owner == who, Error::<T>::NotRightfulOwner);
+            let position = tournament
+                .contenders
+                .iter()
+                .position(|id| *id == knight_id)
+                .ok_or(Error::<T>::NotEntered)?;
+
+            T::Currency::transfer(
+                &Self::tournament_account(tournament_id),
+                &who,
+                tournament.entry_fee,
+                ExistenceRequirement::AllowDeath,
+            )?;
+
+            tournament.contenders.remove(position);
+            KnightLocks::<T>::remove(knight_id);
+            Tournaments::<T>::insert(tournament_id, tournament);
+
+            Self::deposit_event(Event::TournamentLeft(tournament_id, knight_id, who));
+
+            Ok(().into())
+        }
+
+        /// Call off a tournament that has not begun, refunding every entrant.
+        #[pallet::weight(T::WeightInfo::cancel_tournament(T::MaxTournamentSize::get()))]
+        pub fn cancel_tournament(
+            origin: OriginFor<T>,
+            tournament_id: u64,
+        ) -> DispatchResultWithPostInfo {
+            let who = ensure_signed(origin)?;
+
+            let tournament =
+                Tournaments::<T>::get(tournament_id).ok_or(Error::<T>::TournamentNotFound)?;
+            ensure!(tournament.organiser == who, Error::<T>::NotRightfulOwner);
+            ensure!(tournament.round == 0, Error::<T>::TournamentUnderway);
+
+            Self::_refund_entrants(tournament_id, &tournament)?;
+            Tournaments::<T>::remove(tournament_id);
+            RegistrationsClosingAt::<T>::mutate(tournament.registration_end, |closing| {
+                closing.retain(|id| *id != tournament_id)
+            });
+
+            Self::deposit_event(Event::TournamentCancelled(tournament_id));
+
+            Ok(().into())
+        }
+
+        /// Found a knightly order. The caller becomes its founder, who appoints
+        /// and dismisses its officers.
+        ///
+        /// The order has its own account, see `order_account`, whose balance is
+        /// the order's treasury.
+        #[pallet::weight(T::WeightInfo::found_order(name.len() as u32))]
+        pub fn found_order(
+            origin: OriginFor<T>,
+            name: Vec<u8>,
+            rules: OrderRules<T::Balance>,
+        ) -> DispatchResultWithPostInfo {
+            let who = ensure_signed(origin)?;
+
+            Self::ensure_valid_name(&name)?;
+
+            let order_id = NextOrderId::<T>::get();
+            let next_order_id = order_id.checked_add(1).ok_or(Error::<T>::OrderIdOverflow)?;
+
+            Orders::<T>::insert(
+                order_id,
+                Order {
+                    founder: who.clone(),
+                    name,
+                    rules,
+                    officers: Vec::new(),
+                    members: 0,
+                },
+            );
+            NextOrderId::<T>::put(next_order_id);
+
+            Self::deposit_event(Event::OrderFounded(order_id, who));
+
+            Ok(().into())
+        }
+
+        /// Enroll a knight the caller owns in an order, paying the order's dues
+        /// into its treasury.
+        ///
+        /// Membership belongs to the knight and goes with it when it changes hands.
+        #[pallet::weight(T::WeightInfo::enroll_knight())]
+        pub fn enroll_knight(
+            origin: OriginFor<T>,
+            order_id: u64,
+            knight_id: T::KnightIndex,
+        ) -> DispatchResultWithPostInfo {
+            let who = ensure_signed(origin)?;
+
+            let mut order = Orders::<T>::get(order_id).ok_or(Error::<T>::OrderNotFound)?;
+
+            let owner = KnightToOwner::<T>::get(knight_id).ok_or(Error::<T>::KnightNotFound)?;
+            ensure!(owner == who, Error::<T>::NotRightfulOwner);
+            ensure!(
+                !KnightOrders::<T>::contains_key(knight_id),
+                Error::<T>::AlreadyInOrder
+            );
+            ensure!(
+                order.members < T::MaxOrderMembers::get(),
+                Error::<T>::OrderFull
+            );
+            ensure!(
+                Self::meets_order_rules(knight_id, &order.rules),
+                Error::<T>::KnightDoesNotMeetOrderRules
+            );
+
+            T::Currency::transfer(
+                &who,
+                &Self::order_account(order_id),
+                order.rules.dues,
+                ExistenceRequirement::KeepAlive,
+            )?;
+
+            order.members += 1;
+            let dues = order.rules.dues;
+            Orders::<T>::insert(order_id, order);
+            OrderMembers::<T>::insert(
+                order_id,
+                knight_id,
+                frame_system::Pallet::<T>::block_number(),
+            );
+            KnightOrders::<T>::insert(knight_id, order_id);
+
+            Self::deposit_event(Event::KnightEnrolled(order_id, knight_id, who, dues));
+
+            Ok(().into())
+        }
+
+        /// Take a knight the caller owns out of its order. Dues are not refunded,
+        /// and its votes on spends still open are withdrawn.
+        #[pallet::weight(T::WeightInfo::leave_order(T::MaxOpenSpends::get()))]
+        pub fn leave_order(
+            origin: OriginFor<T>,
+            knight_id: T::KnightIndex,
+        ) -> DispatchResultWithPostInfo {
+            let who = ensure_signed(origin)?;
+
+            let owner = KnightToOwner::<T>::get(knight_id).ok_or(Error::<T>::KnightNotFound)?;
+            ensure!(owner == who, Error::<T>::NotRightfulOwner);
+            let order_id = KnightOrders::<T>::get(knight_id).ok_or(Error::<T>::NotInOrder)?;
+
+            Self::_leave_order(order_id, knight_id);
+
+            Self::deposit_event(Event::KnightLeftOrder(order_id, knight_id));
+
+            Ok(().into())
+        }
+
+        /// Make a member knight an officer of an order the caller founded.
+        ///
+        /// Whoever owns an officer knight may propose spends from the treasury.
+        #[pallet::weight(T::WeightInfo::appoint_officer())]
+        pub fn appoint_officer(
+            origin: OriginFor<T>,
+            order_id: u64,
+            knight_id: T::KnightIndex,
+        ) -> DispatchResultWithPostInfo {
+            let who = ensure_signed(origin)?;
+
+            let mut order = Orders::<T>::get(order_id).ok_or(Error::<T>::OrderNotFound)?;
+            ensure!(order.founder == who, Error::<T>::NotRightfulOwner);
+            ensure!(
+                OrderMembers::<T>::contains_key(order_id, knight_id),
+                Error::<T>::NotInOrder
+            );
+            ensure!(
+                !order.officers.contains(&knight_id),
+                Error::<T>::AlreadyOfficer
+            );
+            ensure!(
+                (order.officers.len() as u32) < T::MaxOrderOfficers::get(),
+                Error::<T>::TooManyOfficers
+            );
+
+            order.officers.push(knight_id);
+            Orders::<T>::insert(order_id, order);
+
+            Self::deposit_event(Event::OfficerAppointed(order_id, knight_id));
+
+            Ok(().into())
+        }
+
+        /// Strip an officer of an order the caller founded of their office.
+        /// The knight stays a member, but spends it proposed can no longer pass.
+        #[pallet::weight(T::WeightInfo::dismiss_officer())]
+        pub fn dismiss_officer(
+            origin: OriginFor<T>,
+            order_id: u64,
+            knight_id: T::KnightIndex,
+        ) -> DispatchResultWithPostInfo {
+            let who = ensure_signed(origin)?;
+
+            let mut order = Orders::<T>::get(order_id).ok_or(Error::<T>::OrderNotFound)?;
+            ensure!(order.founder == who, Error::<T>::NotRightfulOwner);
+            let position = order
+                .officers
+                .iter()
+                .position(|officer| *officer == knight_id)
+                .ok_or(Error::<T>::NotOfficer)?;
+
+            order.officers.remove(position);
+            Orders::<T>::insert(order_id, order);
+
+            Self::deposit_event(Event::OfficerDismissed(order_id, knight_id));
+
+            Ok(().into())
+        }
+
+        /// Propose paying `amount` from the treasury of the order `knight_id` is an
+        /// officer of. The caller must own the officer knight.
+        ///
+        /// Member knights vote for `OrderVotingPeriod` blocks, after which anyone
+        /// may close the vote with `close_spend`.
+        #[pallet::weight(T::WeightInfo::propose_spend())]
+        pub fn propose_spend(
+            origin: OriginFor<T>,
+            knight_id: T::KnightIndex,
+            beneficiary: T::AccountId,
+            amount: T::Balance,
+        ) -> DispatchResultWithPostInfo {
+            let who = ensure_signed(origin)?;
+
+            let owner = KnightToOwner::<T>::get(knight_id).ok_or(Error::<T>::KnightNotFound)?;
+            ensure!(owner == who, Error::<T>::NotRightfulOwner);
+            let order_id = KnightOrders::<T>::get(knight_id).ok_or(Error::<T>::NotInOrder)?;
+            let order = Orders::<T>::get(order_id).ok_or(Error::<T>::OrderNotFound)?;
+            ensure!(order.officers.contains(&knight_id), Error::<T>::NotOfficer);
+
+            let mut open = OpenSpends::<T>::get(order_id);
+            ensure!(
+                (open.len() as u32) < T::MaxOpenSpends::get(),
+                Error::<T>::TooManyOpenSpends
+            );
+
+            let proposal_id = NextSpendProposalId::<T>::get();
+            let next_proposal_id = proposal_id
+                .checked_add(1)
+                .ok_or(Error::<T>::SpendProposalIdOverflow)?;
+
+            open.push(proposal_id);
+            OpenSpends::<T>::insert(order_id, open);
+
+            SpendProposals::<T>::insert(
+                proposal_id,
+                SpendProposal {
+                    order_id,
+                    proposer: knight_id,
+                    beneficiary: beneficiary.clone(),
+                    amount,
+                    proposed_at: frame_system::Pallet::<T>::block_number(),
+                    ayes: 0,
+                    nays: 0,
+                },
+            );
+            NextSpendProposalId::<T>::put(next_proposal_id);
+
+            Self::deposit_event(Event::SpendProposed(
+                proposal_id,
+                order_id,
+                knight_id,
+                beneficiary,
+                amount,
+            ));
+
+            Ok(().into())
+        }
+
+        /// Cast a member knight's vote on a spend from its order's treasury.
+        /// The caller must own the knight. Voting again changes the vote.
+        #[pallet::weight(T::WeightInfo::vote_on_spend())]
+        pub fn vote_on_spend(
+            origin: OriginFor<T>,
+            proposal_id: u64,
+            knight_id: T::KnightIndex,
+            aye: bool,
+        ) -> DispatchResultWithPostInfo {
+            let who = ensure_signed(origin)?;
+
+            let mut proposal =
+                SpendProposals::<T>::get(proposal_id).ok_or(Error::<T>::SpendProposalNotFound)?;
+            let now = frame_system::Pallet::<T>::block_number();
+            ensure!(
+                now <= proposal
+                    .proposed_at
+                    .saturating_add(T::OrderVotingPeriod::get()),
+                Error::<T>::VotingClosed
+            );
+
+            let owner = KnightToOwner::<T>::get(knight_id).ok_or(Error::<T>::KnightNotFound)?;
+            ensure!(owner == who, Error::<T>::NotRightfulOwner);
+            let joined = OrderMembers::<T>::get(proposal.order_id, knight_id)
+                .ok_or(Error::<T>::NotInOrder)?;
+            // otherwise a spend could be carried by enrolling knights to vote for it
+            ensure!(
+                joined <= proposal.proposed_at,
+                Error::<T>::EnrolledAfterProposal
+            );
+
+            match SpendVotes::<T>::get(proposal_id, knight_id) {
+                Some(true) => proposal.ayes -= 1,
+                Some(false) => proposal.nays -= 1,
+                None => {}
+            }
+            if aye {
+                proposal.ayes += 1;
+            } else {
+                proposal.nays += 1;
+            }
+
+            SpendProposals::<T>::insert(proposal_id, proposal);
+            SpendVotes::<T>::insert(proposal_id, knight_id, aye);
+
+            Self::deposit_event(Event::SpendVoted(proposal_id, knight_id, aye));
+
+            Ok(().into())
+        }
+
+        /// Close the vote on a spend once its voting period is over. Anyone may close it.
+        ///
+        /// The spend is paid if more knights voted aye than nay and its proposer is
+        /// still an officer. If the treasury cannot cover it the vote stays open to
+        /// be closed again once it can.
+        #[pallet::weight(T::WeightInfo::close_spend(T::MaxOrderMembers::get()))]
+        pub fn close_spend(origin: OriginFor<T>, proposal_id: u64) -> DispatchResultWithPostInfo {
+            ensure_signed(origin)?;
+
+            let proposal =
+                SpendProposals::<T>::get(proposal_id).ok_or(Error::<T>::SpendProposalNotFound)?;
+            let now = frame_system::Pallet::<T>::block_number();
+            ensure!(
+                now > proposal
+                    .proposed_at
+                    .saturating_add(T::OrderVotingPeriod::get()),
+                Error::<T>::VotingNotOver
+            );
+
+            let approved = proposal.ayes > proposal.nays
+                && Orders::<T>::get(proposal.order_id)
+                    .map_or(false, |order| order.officers.contains(&proposal.proposer));
+            if approved {
+                T::Currency::transfer(
+                    &Self::order_account(proposal.order_id),
+                    &proposal.beneficiary,
+                    proposal.amount,
+                    ExistenceRequirement::AllowDeath,
+                )?;
+            }
+
+            SpendProposals::<T>::remove(proposal_id);
+            SpendVotes::<T>::remove_prefix(proposal_id);
+            OpenSpends::<T>::mutate(proposal.order_id, |open| {
+                open.retain(|open_id| *open_id != proposal_id)
+            });
+
+            if approved {
+                Self::deposit_event(Event::SpendApproved(
+                    proposal_id,
+                    proposal.beneficiary,
+                    proposal.amount,
+                ));
+            } else {
+                Self::deposit_event(Event::SpendRejected(proposal_id));
+            }
+
+            Ok(().into())
+        }
+    }
+    // end pallet::call
+
+    // The genesis config type.
+    #[pallet::genesis_config]
+    pub struct GenesisConfig<T: Config> {
+        pub thing: u64,
+        pub alice: T::AccountId,
+        // pub dummy: T::Balance,
+        // pub bar: Vec<(T::AccountId, T::Balance)>,
+        // pub foo: T::Balance,
+    }
+
+    // The default value for the genesis config type.
+    #[cfg(feature = "std")]
+    impl<T: Config> Default for GenesisConfig<T> {
+        fn default() -> Self {
+            Self {
+                thing: Default::default(),
+                alice: Default::default(),
+                // dummy: Default::default(),
+                // bar: Default::default(),
+                // foo: Default::default(),
+            }
+        }
+    }
+
+    // The build of genesis for the pallet.
+    #[pallet::genesis_build]
+    impl<T: Config> GenesisBuild<T> for GenesisConfig<T> {
+        fn build(&self) {
+            Thing::<T>::put(100);
+            StorageVersion::<T>::put(Releases::LATEST);
+
+            let latest_knight_id = LatestKnightId::<T>::get();
+            let next_knight_id = latest_knight_id.checked_add(&One::one()).unwrap();
+
+            let knight = Knight {
+                id: next_knight_id,
+                name: "Danny the Daring".as_bytes().to_vec(),
+                dna: (1).using_encoded(blake2_128),
+                wealth: 0u8.into(),
+                price: 0u8.into(),
+                gen: 1,
+                birth_block: Zero::zero(),
+                parents: None,
+            };
+
+            Pallet::<T>::_mint(&self.alice, knight, Perbill::zero()).unwrap();
+
+            // <Dummy<T>>::put(&self.dummy);
+            // for (a, b) in &self.bar {
+            // <Bar<T>>::insert(a, b);
+            // }
+            // <Foo<T>>::put(&self.foo);
+        }
+    }
+
+    impl<T: Config> Pallet<T> {
+        /// The ids of every knight owned by `owner`, lowest first.
+        pub fn knight_ids_of_owner(owner: &T::AccountId) -> Vec<T::KnightIndex> {
+            let mut knight_ids: Vec<T::KnightIndex> = OwnedKnights::<T>::iter_prefix(owner)
+                .map(|(knight_id, _)| knight_id)
+                .collect();
+            knight_ids.sort();
+
+            knight_ids
+        }
+
+        /// Every knight owned by `owner`, lowest id first.
+        ///
+        /// Used by the `KnightsApi` runtime api so clients don't have to
+        /// walk `OwnedKnights` and `Knights` themselves.
+        pub fn knights_of_owner(owner: &T::AccountId) -> Vec<KnightOf<T>> {
+            Self::knight_ids_of_owner(owner)
+                .into_iter()
+                .filter_map(|knight_id| Knights::<T>::get(knight_id))
+                .collect()
+        }
+
+        /// How many blocks a knight of generation `gen` rests after squiring.
+        ///
+        /// Later generations are more fragile and need longer to recover.
+        pub fn squire_cooldown(gen: u64) -> T::BlockNumber {
+            let extra_generations: T::BlockNumber = gen.saturating_sub(1).saturated_into();
+
+            T::BaseSquireCooldown::get().saturating_add(
+                T::SquireCooldownPerGeneration::get().saturating_mul(extra_generations),
+            )
+        }
+
+        /// Mixes the DNA of two parents into a new knight minted to `who`.
+        ///
+        /// Callers must already have checked `who` may squire with both parents.
+        fn _squire(
+            who: &T::AccountId,
+            squire_name: Vec<u8>,
+            knight_id_1: T::KnightIndex,
+            knight_id_2: T::KnightIndex,
+        ) -> Result<T::KnightIndex, DispatchError> {
+            let latest_knight_id = LatestKnightId::<T>::get();
+            let next_knight_id = latest_knight_id
+                .checked_add(&One::one())
+                .ok_or(Error::<T>::KnightIdOverflow)?;
+
+            ensure!(
+                !Knights::<T>::contains_key(next_knight_id),
+                Error::<T>::KnightAlreadyExists
+            );
+
+            let mut knight_1 = Knights::<T>::get(knight_id_1).ok_or(Error::<T>::KnightNotFound)?;
+            let mut knight_2 = Knights::<T>::get(knight_id_2).ok_or(Error::<T>::KnightNotFound)?;
+
+            ensure!(
+                !Self::are_close_relatives(&knight_1, &knight_2),
+                Error::<T>::CloseRelatives
+            );
+
+            let now = frame_system::Pallet::<T>::block_number();
+            Self::ensure_rested(knight_id_1, now)?;
+            Self::ensure_rested(knight_id_2, now)?;
+
+            &knight_1.name.append(&mut knight_2.name);
+
+            let random_hash = T::RandomnessSource::random(&knight_1.name);
+
+            let mut final_dna = knight_1.dna;
+            for (i, (dna_2_element, r)) in knight_2
+                .dna
+                .as_ref()
+                .iter()
+                .zip(random_hash.as_ref().iter())
+                .enumerate()
+            {
+                if r % 2 == 0 {
+                    final_dna.as_mut()[i] = *dna_2_element;
+                }
+            }
+
+            let new_gen = knight_1
+                .gen
+                .max(knight_2.gen)
+                .checked_add(1)
+                .ok_or(Error::<T>::KnightGenOverflow)?;
+
+            let knight = Knight {
+                id: next_knight_id,
+                dna: final_dna,
+                name: squire_name,
+                wealth: T::Balance::zero(),
+                price: T::Balance::zero(),
+                gen: new_gen,
+                birth_block: now,
+                parents: Some((knight_1.id, knight_2.id)),
+            };
+
+            Self::_mint(who, knight, Perbill::zero())?;
+            Children::<T>::insert(knight_1.id, next_knight_id, ());
+            Children::<T>::insert(knight_2.id, next_knight_id, ());
+
+            Self::deposit_event(Event::SquireKnighted(
+                next_knight_id,
+                knight_1.id,
+                knight_2.id,
+                who.clone(),
+            ));
+
+            Self::_rest(&knight_1, now);
+            Self::_rest(&knight_2, now);
+
+            Ok(next_knight_id)
+        }
+
+        /// Pays the sire's fee and squires, undoing the payment if squiring fails.
+        #[transactional]
+        fn _hire_sire(
+            who: &T::AccountId,
+            sire_owner: &T::AccountId,
+            fee: T::Balance,
+            squire_name: Vec<u8>,
+            knight_id: T::KnightIndex,
+            sire_id: T::KnightIndex,
+        ) -> Result<T::KnightIndex, DispatchError> {
+            T::Currency::transfer(who, sire_owner, fee, ExistenceRequirement::KeepAlive)?;
+
+            Self::_squire(who, squire_name, knight_id, sire_id)
+        }
+
+        /// `who` may squire with a knight they own, or one whose owner approved them.
+        /// Returns `true` when an approval was needed.
+        fn ensure_may_squire_with(
+            who: &T::AccountId,
+            knight_id: T::KnightIndex,
+        ) -> Result<bool, DispatchError> {
+            let owner = KnightToOwner::<T>::get(knight_id).ok_or(Error::<T>::KnightNotFound)?;
+            if owner == *who {
+                return Ok(false);
+            }
+
+            ensure!(
+                SquireApprovals::<T>::contains_key(knight_id, who),
+                Error::<T>::NotApprovedToSquire
+            );
+
+            Ok(true)
+        }
+
+        fn _use_squire_approval(knight_id: T::KnightIndex, who: &T::AccountId) {
+            if SquireApprovals::<T>::get(knight_id, who) == Some(true) {
+                SquireApprovals::<T>::remove(knight_id, who);
+            }
+        }
+
+        fn ensure_rested(
+            knight_id: T::KnightIndex,
+            now: T::BlockNumber,
+        ) -> Result<(), DispatchError> {
+            if let Some(ready_at) = SquireReadyAt::<T>::get(knight_id) {
+                ensure!(now >= ready_at, Error::<T>::KnightStillResting);
+            }
+
+            Ok(())
+        }
+
+        fn _rest(knight: &KnightOf<T>, now: T::BlockNumber) {
+            let ready_at = now.saturating_add(Self::squire_cooldown(knight.gen));
+            SquireReadyAt::<T>::insert(knight.id, ready_at);
+
+            Self::deposit_event(Event::KnightResting(knight.id, ready_at));
+        }
+
+        /// Names must be non-empty UTF-8 no longer than `MaxNameLength` bytes.
+        ///
+        /// Names are still stored as a `Vec<u8>`, so this must be checked
+        /// everywhere a name enters storage.
+        fn ensure_valid_name(name: &[u8]) -> Result<(), DispatchError> {
+            ensure!(!name.is_empty(), Error::<T>::NameEmpty);
+            ensure!(
+                name.len() <= T::MaxNameLength::get() as usize,
+                Error::<T>::NameTooLong
+            );
+            ensure!(
+                sp_std::str::from_utf8(name).is_ok(),
+                Error::<T>::NameNotUtf8
+            );
+
+            Ok(())
+        }
+
+        fn _mint(
+            owner: &T::AccountId,
+            knight: KnightOf<T>,
+            royalty: Perbill,
+        ) -> Result<(), &'static str> {
+            let knight_id: T::KnightIndex = knight.id;
+
+            let current_count = KnightCount::<T>::get();
+            let new_count = current_count
+                .checked_add(One::one())
+                .ok_or(Error::<T>::KnightCountOverflow)?;
+            KnightCount::<T>::put(new_count);
+
+            Self::_count_traits(&knight.dna, true);
+            Knights::<T>::insert(knight.id, knight);
+            KnightToOwner::<T>::insert(knight_id, owner);
+            OwnedKnights::<T>::insert(owner, knight_id, ());
+            LatestKnightId::<T>::put(knight_id);
+            KnightCreators::<T>::insert(
+                knight_id,
+                Creator {
+                    account: owner.clone(),
+                    royalty,
+                },
+            );
+
+            let current_owner_to_knight_count = OwnerToKnightCount::<T>::get(owner);
+            let new_count = current_owner_to_knight_count
+                .checked_add(1)
+                .ok_or(Error::<T>::OwnerToKnightCountOverflow)?;
+
+            OwnerToKnightCount::<T>::insert(owner, new_count);
+
+            Self::deposit_event(Event::KnightCreated(knight_id, owner.clone()));
+
+            Ok(())
+        }
+
+        /// The price of a knight on Dutch auction, or `None` if it isn't on one.
+        pub fn dutch_auction_price(knight_id: T::KnightIndex) -> Option<T::Balance> {
+            DutchAuctions::<T>::get(knight_id).map(|auction| {
+                Self::dutch_price(&auction, frame_system::Pallet::<T>::block_number())
+            })
+        }
+
+        fn dutch_price(auction: &DutchAuctionOf<T>, now: T::BlockNumber) -> T::Balance {
+            let elapsed = now.saturating_sub(auction.start).min(auction.duration);
+            let drop = Perbill::from_rational_approximation(elapsed, auction.duration)
+                * auction.start_price.saturating_sub(auction.floor_price);
+
+            auction.start_price.saturating_sub(drop)
+        }
+
+        /// Pays the seller and hands the knight to the buyer. The payment is made first,
+        /// so a buyer who can't pay leaves everything untouched.
+        ///
+        /// Every sale goes through here, so the creator's royalty is taken out of
+        /// the price whichever way the knight was sold. `fee` comes out of the price
+        /// too and goes to `T::OnMarketplaceFee`.
+        #[transactional]
+        pub(crate) fn _sell(
+            knight_id: T::KnightIndex,
+            seller: T::AccountId,
+            buyer: T::AccountId,
+            price: T::Balance,
+            fee: T::Balance,
+            payment: Payment,
+        ) -> Result<(), DispatchError> {
+            if !fee.is_zero() {
+                Self::_pay_fee(&buyer, fee, payment)?;
+                Self::deposit_event(Event::MarketplaceFeePaid(knight_id, fee));
+            }
+            let net_price = price.saturating_sub(fee);
+
+            let royalty = match KnightCreators::<T>::get(knight_id) {
+                Some(creator) if creator.account != seller && !creator.royalty.is_zero() => {
+                    Some((creator.account, (creator.royalty * price).min(net_price)))
+                }
+                _ => None,
+            };
+
+            match royalty {
+                Some((creator, royalty)) => {
+                    let proceeds = net_price.saturating_sub(royalty);
+                    Self::_pay(&buyer, &creator, royalty, payment)?;
+                    Self::_pay(&buyer, &seller, proceeds, payment)?;
+
+                    Self::deposit_event(Event::SaleSplit(
+                        knight_id,
+                        seller.clone(),
+                        proceeds,
+                        creator,
+                        royalty,
+                    ));
+                }
+                None => Self::_pay(&buyer, &seller, net_price, payment)?,
+            }
+
+            // fails if the buyer already owns the knight, e.g. through a stale offer
+            Self::_transfer_knight(knight_id, seller, buyer)?;
+
+            Ok(())
+        }
+
+        fn _pay_fee(
+            from: &T::AccountId,
+            fee: T::Balance,
+            payment: Payment,
+        ) -> Result<(), DispatchError> {
+            let imbalance = match payment {
+                Payment::Free => T::Currency::withdraw(
+                    from,
+                    fee,
+                    WithdrawReasons::TRANSFER,
+                    ExistenceRequirement::KeepAlive,
+                )?,
+                Payment::Reserved => {
+                    let (imbalance, unpaid) = T::Currency::slash_reserved(from, fee);
+                    ensure!(unpaid.is_zero(), Error::<T>::ReserveTooLow);
+                    imbalance
+                }
+            };
+            T::OnMarketplaceFee::on_unbalanced(imbalance);
+
+            Ok(())
+        }
+
+        fn _pay(
+            from: &T::AccountId,
+            to: &T::AccountId,
+            amount: T::Balance,
+            payment: Payment,
+        ) -> Result<(), DispatchError> {
+            match payment {
+                Payment::Free => {
+                    T::Currency::transfer(from, to, amount, ExistenceRequirement::KeepAlive)
+                }
+                Payment::Reserved => {
+                    let unpaid =
+                        T::Currency::repatriate_reserved(from, to, amount, BalanceStatus::Free)?;
+                    ensure!(unpaid.is_zero(), Error::<T>::ReserveTooLow);
+
+                    Ok(())
+                }
+            }
+        }
+
+        /// Removes an offer and its place in the expiry queue. The funds stay reserved.
+        fn _take_offer(
+            knight_id: T::KnightIndex,
+            buyer: &T::AccountId,
+        ) -> Result<OfferOf<T>, DispatchError> {
+            let offer = Offers::<T>::take(knight_id, buyer).ok_or(Error::<T>::OfferNotFound)?;
+            OffersExpiringAt::<T>::mutate(offer.expires, |expiring| {
+                expiring.retain(|(id, who)| !(*id == knight_id && who == buyer))
+            });
+
+            Ok(offer)
+        }
+
+        pub(crate) fn _expire_offer(knight_id: T::KnightIndex, buyer: T::AccountId) {
+            if let Some(offer) = Offers::<T>::take(knight_id, &buyer) {
+                T::Currency::unreserve(&buyer, offer.amount);
+                Self::deposit_event(Event::OfferExpired(knight_id, buyer));
+            }
+        }
+
+        pub fn matches_collection_bid(knight: &KnightOf<T>, bid: &CollectionBidOf<T>) -> bool {
+            knight.gen == bid.gen
+                && knight
+                    .dna
+                    .iter()
+                    .zip(bid.dna_mask.iter().zip(bid.dna_pattern.iter()))
+                    .all(|(dna, (mask, pattern))| dna & mask == pattern & mask)
+        }
+
+        /// `who` owns the knight and it is free to change hands.
+        fn ensure_may_transfer(
+            knight_id: T::KnightIndex,
+            who: &T::AccountId,
+        ) -> Result<(), DispatchError> {
+            let owner = KnightToOwner::<T>::get(knight_id).ok_or(Error::<T>::KnightNotFound)?;
+            ensure!(owner == *who, Error::<T>::NotRightfulOwner);
+            Self::ensure_unlocked(knight_id)
+        }
+
+        /// `who` is the knight's owner, its approved spender, or an operator of the owner.
+        pub fn is_approved_or_owner(
+            knight_id: T::KnightIndex,
+            owner: &T::AccountId,
+            who: &T::AccountId,
+        ) -> bool {
+            who == owner
+                || TransferApprovals::<T>::get(knight_id).as_ref() == Some(who)
+                || OperatorApprovals::<T>::get(owner, who)
+        }
+
+        /// The account holding a knight's wealth.
+        pub fn vault_account(knight_id: T::KnightIndex) -> T::AccountId {
+            T::ModuleId::get().into_sub_account(knight_id)
+        }
+
+        /// Copies the vault's balance into the knight's `wealth`, which also picks up
+        /// anything paid straight into the vault.
+        fn _sync_wealth(knight_id: T::KnightIndex) {
+            let wealth = T::Currency::free_balance(&Self::vault_account(knight_id));
+            Knights::<T>::mutate(knight_id, |knight| {
+                if let Some(knight) = knight {
+                    knight.wealth = wealth;
+                }
+            });
+        }
+
+        /// A knight's fighting strength: the sum of its stats.
+        pub fn combat_rating(stats: &Stats) -> u32 {
+            stats.strength as u32
+                + stats.agility as u32
+                + stats.valor as u32
+                + stats.endurance as u32
+        }
+
+        /// Decides a fight between two knights. Returns `true` if `knight_a` wins.
+        ///
+        /// Each knight's chance of winning is its share of the two combat ratings.
+        pub(crate) fn _fight(knight_a: &KnightOf<T>, knight_b: &KnightOf<T>) -> bool {
+            let rating_a = Self::combat_rating(&Self::stats_of(knight_a));
+            let total = rating_a.saturating_add(Self::combat_rating(&Self::stats_of(knight_b)));
+            if total == 0 {
+                return true;
+            }
+
+            let now = frame_system::Pallet::<T>::block_number();
+            let seed =
+                T::RandomnessSource::random(&(b"fight", knight_a.id, knight_b.id, now).encode());
+            let roll = u32::decode(&mut seed.as_ref()).unwrap_or_default() % total;
+
+            roll < rating_a
+        }
+
+        /// Fights the duel, paying both wagers, already held in reserve, to the
+        /// winner's owner. Returns the winning and losing knights and the winner's owner.
+        #[transactional]
+        fn _duel(
+            challenger_id: T::KnightIndex,
+            challenge: DuelChallengeOf<T>,
+            opponent_owner: T::AccountId,
+        ) -> Result<(T::KnightIndex, T::KnightIndex, T::AccountId), DispatchError> {
+            let challenger_knight =
+                Knights::<T>::get(challenger_id).ok_or(Error::<T>::KnightNotFound)?;
+            let opponent_knight =
+                Knights::<T>::get(challenge.opponent).ok_or(Error::<T>::KnightNotFound)?;
+
+            let ((winner_id, winner), (loser_id, loser)) =
+                if Self::_fight(&challenger_knight, &opponent_knight) {
+                    (
+                        (challenger_id, challenge.challenger),
+                        (challenge.opponent, opponent_owner),
+                    )
+                } else {
+                    (
+                        (challenge.opponent, opponent_owner),
+                        (challenger_id, challenge.challenger),
+                    )
+                };
+
+            T::Currency::unreserve(&winner, challenge.wager);
+            Self::_pay(&loser, &winner, challenge.wager, Payment::Reserved)?;
+
+            DuelRecords::<T>::mutate(winner_id, |record| {
+                record.wins = record.wins.saturating_add(1)
+            });
+            DuelRecords::<T>::mutate(loser_id, |record| {
+                record.losses = record.losses.saturating_add(1)
+            });
+            Self::_gain_experience(winner_id, T::DuelExperience::get());
+
+            Ok((winner_id, loser_id, winner))
+        }
+
+        /// The account holding a tournament's prize pool.
+        pub fn tournament_account(tournament_id: u64) -> T::AccountId {
+            T::ModuleId::get().into_sub_account((b"tournament", tournament_id))
+        }
+
+        /// Returns each entrant's fee to its knight's owner and unlocks the knight.
+        #[transactional]
+        fn _refund_entrants(
+            tournament_id: u64,
+            tournament: &TournamentOf<T>,
+        ) -> Result<(), DispatchError> {
+            let pool = Self::tournament_account(tournament_id);
+            for knight_id in &tournament.contenders {
+                let owner = KnightToOwner::<T>::get(knight_id).ok_or(Error::<T>::KnightNotFound)?;
+                T::Currency::transfer(
+                    &pool,
+                    &owner,
+                    tournament.entry_fee,
+                    ExistenceRequirement::AllowDeath,
+                )?;
+                KnightLocks::<T>::remove(knight_id);
+            }
+
+            Ok(())
+        }
+
+        /// Shuffles the entrants into bracket order.
+        fn _seed(tournament_id: u64, contenders: &mut [T::KnightIndex]) {
+            let seed = T::RandomnessSource::random(&(b"seed", tournament_id).encode());
+            for i in (1..contenders.len()).rev() {
+                let roll = u32::decode(&mut &(seed, i as u32).using_encoded(blake2_128)[..])
+                    .unwrap_or_default();
+                contenders.swap(i, roll as usize % (i + 1));
+            }
+        }
+
+        /// Fights the next round of a tournament, paying out the prize pool after the final.
+        /// Returns how many knights fought.
+        pub(crate) fn _fight_tournament_round(tournament_id: u64) -> u32 {
+            let mut tournament = match Tournaments::<T>::get(tournament_id) {
+                Some(tournament) => tournament,
+                None => return 0,
+            };
+            let fought = tournament.contenders.len() as u32;
+
+            if tournament.round == 1 {
+                Self::_seed(tournament_id, &mut tournament.contenders);
+                Self::deposit_event(Event::TournamentSeeded(
+                    tournament_id,
+                    tournament.contenders.clone(),
+                ));
+            }
+
+            let mut winners = Vec::new();
+            let mut runner_up = None;
+            for pair in tournament.contenders.chunks(2) {
+                // locked knights cannot be burned, so both are always found
+                let knights: Vec<KnightOf<T>> = pair.iter().filter_map(Knights::<T>::get).collect();
+                match knights.as_slice() {
+                    [knight_a, knight_b] => {
+                        let (winner, loser) = if Self::_fight(knight_a, knight_b) {
+                            (knight_a.id, knight_b.id)
+                        } else {
+                            (knight_b.id, knight_a.id)
+                        };
+                        KnightLocks::<T>::remove(loser);
+                        winners.push(winner);
+                        runner_up = Some(loser);
+                    }
+                    [knight] => winners.push(knight.id),
+                    _ => {}
+                }
+            }
+
+            Self::deposit_event(Event::TournamentRoundFought(
+                tournament_id,
+                tournament.round,
+                winners.clone(),
+            ));
+
+            if winners.len() > 1 {
+                tournament.contenders = winners;
+                tournament.round = tournament.round.saturating_add(1);
+                Tournaments::<T>::insert(tournament_id, tournament);
+                return fought;
+            }
+
+            Tournaments::<T>::remove(tournament_id);
+            ActiveTournaments::<T>::mutate(|active| active.retain(|id| *id != tournament_id));
+
+            let pool = Self::tournament_account(tournament_id);
+            if let Some(runner_up) = runner_up {
+                let prize = T::RunnerUpPrize::get() * T::Currency::free_balance(&pool);
+                if let Some(owner) = Self::_pay_prize(&pool, runner_up, prize) {
+                    Self::deposit_event(Event::TournamentPrizePaid(
+                        tournament_id,
+                        runner_up,
+                        owner,
+                        prize,
+                    ));
+                }
+            }
+            if let Some(champion) = winners.pop() {
+                KnightLocks::<T>::remove(champion);
+                let prize = T::Currency::free_balance(&pool);
+                if let Some(owner) = Self::_pay_prize(&pool, champion, prize) {
+                    Self::deposit_event(Event::TournamentWon(
+                        tournament_id,
+                        champion,
+                        owner,
+                        prize,
+                    ));
+                }
+            }
+
+            fought
+        }
+
+        /// Pays `prize` from a tournament's pool to a knight's owner, returning the owner.
+        fn _pay_prize(
+            pool: &T::AccountId,
+            knight_id: T::KnightIndex,
+            prize: T::Balance,
+        ) -> Option<T::AccountId> {
+            let owner = KnightToOwner::<T>::get(knight_id)?;
+            T::Currency::transfer(pool, &owner, prize, ExistenceRequirement::AllowDeath).ok()?;
+
+            Some(owner)
+        }
+
+        /// The stats a knight is born with. Each is the average of two DNA bytes:
+        /// strength bytes 0-1, agility 2-3, valor 4-5 and endurance 6-7.
+        pub fn base_stats(dna: &[u8; 16]) -> Stats {
+            let gene = |i: usize| (dna[i] as u16 + dna[i + 1] as u16) / 2;
+
+            Stats {
+                strength: gene(0),
+                agility: gene(2),
+                valor: gene(4),
+                endurance: gene(6),
+            }
+        }
+
+        /// A knight's stats with its class and level bonuses applied.
+        pub fn stats_of(knight: &KnightOf<T>) -> Stats {
+            let mut stats = Self::base_stats(&knight.dna);
+
+            let level_bonus = LEVEL_BONUS
+                .saturating_mul(Self::level(knight.id).saturating_sub(1).saturated_into());
+            stats.strength = stats.strength.saturating_add(level_bonus);
+            stats.agility = stats.agility.saturating_add(level_bonus);
+            stats.valor = stats.valor.saturating_add(level_bonus);
+            stats.endurance = stats.endurance.saturating_add(level_bonus);
+
+            match KnightClasses::<T>::get(knight.id) {
+                Some(KnightClass::Warrior) => {
+                    stats.strength = stats.strength.saturating_add(CLASS_BONUS)
+                }
+                Some(KnightClass::Ranger) => {
+                    stats.agility = stats.agility.saturating_add(CLASS_BONUS)
+                }
+                Some(KnightClass::Paladin) => stats.valor = stats.valor.saturating_add(CLASS_BONUS),
+                Some(KnightClass::Guardian) => {
+                    stats.endurance = stats.endurance.saturating_add(CLASS_BONUS)
+                }
+                None => {}
+            }
+
+            stats
+        }
+
+        /// A knight's stats, or `None` if it does not exist.
+        pub fn knight_stats(knight_id: T::KnightIndex) -> Option<Stats> {
+            Knights::<T>::get(knight_id).map(|knight| Self::stats_of(&knight))
+        }
+
+        /// The level reached with `experience`: 1 below 100, 2 from 100, 3 from 400, 4 from 900...
+        pub fn level_for(experience: u64) -> u32 {
+            (experience / EXPERIENCE_PER_LEVEL)
+                .integer_sqrt()
+                .saturating_add(1)
+                .saturated_into()
+        }
+
+        pub fn level(knight_id: T::KnightIndex) -> u32 {
+            Self::level_for(Experience::<T>::get(knight_id))
+        }
+
+        /// Awards experience to a knight. Duels award it now; quests and training
+        /// are meant to go through here too.
+        pub(crate) fn _gain_experience(knight_id: T::KnightIndex, experience: u64) {
+            let before = Experience::<T>::get(knight_id);
+            let after = before.saturating_add(experience);
+            Experience::<T>::insert(knight_id, after);
+
+            Self::deposit_event(Event::ExperienceGained(knight_id, experience));
+
+            let level = Self::level_for(after);
+            if level > Self::level_for(before) {
+                Self::deposit_event(Event::LevelledUp(knight_id, level));
+            }
+        }
+
+        /// A knight's traits, or `None` if it does not exist.
+        pub fn knight_traits(knight_id: T::KnightIndex) -> Option<Traits> {
+            Knights::<T>::get(knight_id).map(|knight| Traits::from_dna(&knight.dna))
+        }
+
+        /// How rare a knight's traits are, or `None` if it does not exist. Higher is rarer.
+        ///
+        /// Each trait scores the number of knights divided by how many have that trait,
+        /// times `RARITY_SCALE`, so a knight whose traits are all shared by every other
+        /// knight scores `4 * RARITY_SCALE`.
+        pub fn rarity(knight_id: T::KnightIndex) -> Option<u64> {
+            let knight = Knights::<T>::get(knight_id)?;
+            let total = KnightCount::<T>::get().saturating_mul(RARITY_SCALE);
+
+            Some(
+                Traits::from_dna(&knight.dna)
+                    .all()
+                    .iter()
+                    .map(|knight_trait| total / TraitCounts::<T>::get(knight_trait).max(1))
+                    .fold(0, |score: u64, trait_score| {
+                        score.saturating_add(trait_score)
+                    }),
+            )
+        }
+
+        /// Adds a knight's traits to `TraitCounts` when it is minted, or takes them
+        /// away when it is burned.
+        pub(crate) fn _count_traits(dna: &[u8; 16], minted: bool) {
+            for knight_trait in Traits::from_dna(dna).all().iter() {
+                TraitCounts::<T>::mutate(knight_trait, |count| {
+                    *count = if minted {
+                        count.saturating_add(1)
+                    } else {
+                        count.saturating_sub(1)
+                    }
+                });
+            }
+        }
+
+        /// Whether two knights are the same knight, parent and child, or share a parent.
+        pub fn are_close_relatives(knight_1: &KnightOf<T>, knight_2: &KnightOf<T>) -> bool {
+            let is_parent_of = |parent: &KnightOf<T>, child: &KnightOf<T>| {
+                child
+                    .parents
+                    .map_or(false, |(a, b)| a == parent.id || b == parent.id)
+            };
+            let share_a_parent = match (knight_1.parents, knight_2.parents) {
+                (Some((a_1, b_1)), Some((a_2, b_2))) => {
+                    a_1 == a_2 || a_1 == b_2 || b_1 == a_2 || b_1 == b_2
+                }
+                _ => false,
+            };
+
+            knight_1.id == knight_2.id
+                || is_parent_of(knight_1, knight_2)
+                || is_parent_of(knight_2, knight_1)
+                || share_a_parent
+        }
+
+        /// The ids of a knight's ancestors up to `depth` generations back, parents first.
+        /// `depth` is capped at `MaxLineageDepth`.
+        pub fn ancestors(knight_id: T::KnightIndex, depth: u32) -> Vec<T::KnightIndex> {
+            Self::lineage(knight_id, depth, |knight_id| {
+                Knights::<T>::get(knight_id)
+                    .and_then(|knight| knight.parents)
+                    .map_or(Vec::new(), |(a, b)| [a, b].to_vec())
+            })
+        }
+
+        /// The ids of a knight's descendants up to `depth` generations on, children first.
+        /// `depth` is capped at `MaxLineageDepth`.
+        pub fn descendants(knight_id: T::KnightIndex, depth: u32) -> Vec<T::KnightIndex> {
+            Self::lineage(knight_id, depth, |knight_id| {
+                Children::<T>::iter_prefix(knight_id)
+                    .map(|(child, _)| child)
+                    .collect()
+            })
+        }
+
+        /// Walks the family tree one generation at a time using `next`, listing
+        /// each knight found once.
+        fn lineage(
+            knight_id: T::KnightIndex,
+            depth: u32,
+            next: impl Fn(T::KnightIndex) -> Vec<T::KnightIndex>,
+        ) -> Vec<T::KnightIndex> {
+            let mut found = Vec::new();
+            let mut generation = Vec::new();
+            generation.push(knight_id);
+
+            for _ in 0..depth.min(T::MaxLineageDepth::get()) {
+                let mut next_generation = Vec::new();
+                for relative in generation.into_iter().flat_map(&next) {
+                    if relative != knight_id && !found.contains(&relative) {
+                        found.push(relative);
+                        next_generation.push(relative);
+                    }
+                }
+                if next_generation.is_empty() {
+                    break;
+                }
+                generation = next_generation;
+            }
+
+            found
+        }
+
+        /// The account holding an order's treasury.
+        pub fn order_account(order_id: u64) -> T::AccountId {
+            T::ModuleId::get().into_sub_account((b"order", order_id))
+        }
+
+        /// Whether a knight's level and class let it join an order with `rules`.
+        pub fn meets_order_rules(
+            knight_id: T::KnightIndex,
+            rules: &OrderRules<T::Balance>,
+        ) -> bool {
+            Self::level(knight_id) >= rules.min_level
+                && rules.class.map_or(true, |class| {
+                    KnightClasses::<T>::get(knight_id) == Some(class)
+                })
+        }
+
+        /// Removes a knight from an order, and from its officers if it was one.
+        /// Its votes on the order's open spends are taken back.
+        fn _leave_order(order_id: u64, knight_id: T::KnightIndex) {
+            for proposal_id in OpenSpends::<T>::get(order_id) {
+                if let Some(aye) = SpendVotes::<T>::take(proposal_id, knight_id) {
+                    SpendProposals::<T>::mutate(proposal_id, |proposal| {
+                        if let Some(proposal) = proposal {
+                            if aye {
+                                proposal.ayes = proposal.ayes.saturating_sub(1);
+                            } else {
+                                proposal.nays = proposal.nays.saturating_sub(1);
+                            }
+                        }
+                    });
+                }
+            }
+            OrderMembers::<T>::remove(order_id, knight_id);
+            KnightOrders::<T>::remove(knight_id);
+            Orders::<T>::mutate(order_id, |order| {
+                if let Some(order) = order {
+                    order.members = order.members.saturating_sub(1);
+                    order.officers.retain(|officer| *officer != knight_id);
+                }
+            });
+        }
+
+        /// Fights an accepted duel. If it cannot be fought, both wagers are returned.
+        pub(crate) fn _fight_duel(challenger_id: T::KnightIndex) {
+            let challenge = match DuelChallenges::<T>::take(challenger_id) {
+                Some(challenge) => challenge,
+                None => return,
+            };
+            let opponent_owner = match AcceptedDuels::<T>::take(challenger_id) {
+                Some(opponent_owner) => opponent_owner,
+                None => return,
+            };
+            KnightLocks::<T>::remove(challenger_id);
+            KnightLocks::<T>::remove(challenge.opponent);
+
+            let pot = challenge.wager.saturating_add(challenge.wager);
+            match Self::_duel(challenger_id, challenge.clone(), opponent_owner.clone()) {
+                Ok((winner_id, loser_id, winner)) => {
+                    Self::deposit_event(Event::DuelWon(winner_id, loser_id, winner, pot));
+                }
+                Err(_) => {
+                    T::Currency::unreserve(&challenge.challenger, challenge.wager);
+                    T::Currency::unreserve(&opponent_owner, challenge.wager);
+                    Self::deposit_event(Event::DuelChallengeCancelled(
+                        challenger_id,
+                        challenge.challenger,
+                    ));
+                }
+            }
+        }
+
+        /// Cancels a tournament whose registration closed before its bracket filled,
+        /// refunding every entrant. Returns how many entrants were refunded.
+        pub(crate) fn _close_registration(tournament_id: u64) -> u32 {
+            let tournament = match Tournaments::<T>::get(tournament_id) {
+                Some(tournament) if tournament.round == 0 => tournament,
+                _ => return 0,
+            };
+
+            if Self::_refund_entrants(tournament_id, &tournament).is_ok() {
+                Tournaments::<T>::remove(tournament_id);
+                Self::deposit_event(Event::TournamentCancelled(tournament_id));
+            }
+
+            tournament.contenders.len() as u32
+        }
+
+        fn ensure_unlocked(knight_id: T::KnightIndex) -> Result<(), DispatchError> {
+            ensure!(
+                !KnightLocks::<T>::contains_key(knight_id),
+                Error::<T>::KnightLocked
+            );
+
+            Ok(())
+        }
+
+        /// Queues an auction to be settled at the start of block `end`.
+        fn _schedule_settlement(
+            knight_id: T::KnightIndex,
+            end: T::BlockNumber,
+        ) -> Result<(), DispatchError> {
+            let mut ending = AuctionsEndingAt::<T>::get(end);
+            ensure!(
+                (ending.len() as u32) < T::MaxAuctionsPerBlock::get(),
+                Error::<T>::TooManyAuctionsEnding
+            );
+            ending.push(knight_id);
+            AuctionsEndingAt::<T>::insert(end, ending);
+
+            Ok(())
+        }
+
+        /// Hands the knight to the highest revealed bidder at the second price,
+        /// releases revealed deposits and slashes unrevealed ones.
+        /// Returns how many bids were settled.
+        pub(crate) fn _settle_sealed_auction(knight_id: T::KnightIndex) -> u32 {
+            let auction = match SealedAuctions::<T>::take(knight_id) {
+                Some(auction) => auction,
+                None => return 0,
+            };
+            KnightLocks::<T>::remove(knight_id);
+
+            let bids: Vec<_> = SealedBids::<T>::iter_prefix(knight_id).collect();
+            SealedBids::<T>::remove_prefix(knight_id);
+
+            let seller = auction.seller;
+            let mut sold = false;
+            if let Some((winner, _)) = &auction.highest_bid {
+                let price = auction.second_bid.unwrap_or(auction.reserve_price);
+
+                // the winning bid never exceeds its deposit, so the price is held in full
+                if Self::_sell(
+                    knight_id,
+                    seller.clone(),
+                    winner.clone(),
+                    price,
+                    Zero::zero(),
+                    Payment::Reserved,
+                )
+                .is_ok()
+                {
+                    T::Currency::unreserve(
+                        winner,
+                        bids.iter()
+                            .find(|(bidder, _)| bidder == winner)
+                            .map_or(Zero::zero(), |(_, bid)| bid.deposit.saturating_sub(price)),
+                    );
+
+                    Self::deposit_event(Event::AuctionWon(
+                        knight_id,
+                        seller.clone(),
+                        winner.clone(),
+                        price,
+                    ));
+                    sold = true;
+                }
+            }
+
+            for (bidder, bid) in bids.iter() {
+                if sold && auction.highest_bid.as_ref().map(|(winner, _)| winner) == Some(bidder) {
+                    continue;
+                }
+
+                if bid.revealed {
+                    T::Currency::unreserve(bidder, bid.deposit);
+                } else {
+                    let _ = T::Currency::slash_reserved(bidder, bid.deposit);
+                    Self::deposit_event(Event::SealedBidSlashed(
+                        knight_id,
+                        bidder.clone(),
+                        bid.deposit,
+                    ));
+                }
+            }
+
+            if !sold {
+                Self::deposit_event(Event::AuctionClosed(knight_id, seller));
+            }
+
+            bids.len() as u32
+        }
+
+        /// Hands the knight to the highest bidder and pays the seller,
+        /// or returns it to the seller if nobody bid.
+        pub(crate) fn _settle_auction(knight_id: T::KnightIndex) {
+            let auction = match Auctions::<T>::take(knight_id) {
+                Some(auction) => auction,
+                None => return,
+            };
+            KnightLocks::<T>::remove(knight_id);
+
+            let seller = auction.seller;
+            match auction.highest_bid {
+                Some((winner, bid)) => {
+                    // the bid has been held in reserve since it was placed,
+                    // so the full amount is always there to move
+                    let sold = Self::_sell(
+                        knight_id,
+                        seller.clone(),
+                        winner.clone(),
+                        bid,
+                        Zero::zero(),
+                        Payment::Reserved,
+                    );
+                    if sold.is_err() {
+                        T::Currency::unreserve(&winner, bid);
+                        Self::deposit_event(Event::AuctionClosed(knight_id, seller));
+                        return;
+                    }
+
+                    Self::deposit_event(Event::AuctionWon(knight_id, seller, winner, bid));
+                }
+                None => {
+                    Self::deposit_event(Event::AuctionClosed(knight_id, seller));
+                }
+            }
+        }
+
+        /// A knight that is for sale or hired out as a sire cannot be destroyed.
+        fn ensure_not_listed(knight: &KnightOf<T>) -> Result<(), DispatchError> {
+            ensure!(knight.price.is_zero(), Error::<T>::KnightForSale);
+            ensure!(
+                !SireListings::<T>::contains_key(knight.id),
+                Error::<T>::KnightListedAsSire
+            );
+
+            Ok(())
+        }
+
+        /// Removes every trace of a knight from storage.
+        fn _burn(knight_id: T::KnightIndex, owner: &T::AccountId) -> Result<(), DispatchError> {
+            let new_count = KnightCount::<T>::get()
+                .checked_sub(1)
+                .ok_or(Error::<T>::KnightCountUnderflow)?;
+            let new_owner_count = OwnerToKnightCount::<T>::get(owner)
+                .checked_sub(1)
+                .ok_or(Error::<T>::OwnerToKnightCountUnderflow)?;
+
+            KnightCount::<T>::put(new_count);
+            OwnerToKnightCount::<T>::insert(owner, new_owner_count);
+
+            if let Some(knight) = Knights::<T>::take(knight_id) {
+                Self::_count_traits(&knight.dna, false);
+            }
+            KnightToOwner::<T>::remove(knight_id);
+            OwnedKnights::<T>::remove(owner, knight_id);
+            SquireReadyAt::<T>::remove(knight_id);
+            SquireApprovals::<T>::remove_prefix(knight_id);
+            SireListings::<T>::remove(knight_id);
+            KnightCreators::<T>::remove(knight_id);
+            TransferApprovals::<T>::remove(knight_id);
+            DuelRecords::<T>::remove(knight_id);
+            KnightClasses::<T>::remove(knight_id);
+            Experience::<T>::remove(knight_id);
+            if let Some(order_id) = KnightOrders::<T>::get(knight_id) {
+                Self::_leave_order(order_id, knight_id);
+            }
+
+            Ok(())
+        }
+
+        fn _transfer_knight(
+            knight_id: T::KnightIndex,
+            from: T::AccountId,
+            to: T::AccountId,
+        ) -> Result<(), DispatchError> {
+            // a knight can't be sent to the account that already owns it;
+            // check before writing to storage below.
+            ensure!(
+                !OwnedKnights::<T>::contains_key(&to, knight_id),
+                Error::<T>::KnightAlreadyExists
+            );
+
+            KnightToOwner::<T>::insert(knight_id, &to);
+
+            OwnedKnights::<T>::remove(&from, knight_id);
+            OwnedKnights::<T>::insert(&to, knight_id, ());
+
+            // update price to zero so this Knight cannot be purchased again
+            // until the new owner decides.
+            Knights::<T>::mutate(knight_id, |knight| {
+                if let Some(knight) = knight {
+                    knight.price = Zero::zero();
+                }
             });
 
-            OwnerToKnights::<T>::append(&to, knight_id);
+            // approvals and sire listings were made by the previous owner
+            SquireApprovals::<T>::remove_prefix(knight_id);
+            SireListings::<T>::remove(knight_id);
+            TransferApprovals::<T>::remove(knight_id);
 
-            // these underflow / overflows aren't possible,
-            // so at the call site of this function, we use an .expect()
-            // to document why this function will never fail.
+            // these underflow / overflows aren't possible
             let from_count = OwnerToKnightCount::<T>::get(&from);
             let new_from_count = from_count
                 .checked_sub(1)
@@ -0,0 +1,172 @@
+//! Storage migrations for pallet-knights.
+//!
+//! `migrate` is called from `on_runtime_upgrade` and runs every step between
+//! the on-chain `StorageVersion` and `Releases::LATEST`, in order. Each step
+//! lives in its own module and bumps `StorageVersion` when it is done, so a
+//! chain several releases behind catches up in a single upgrade.
+
+use crate::pallet::{
+    Children, Config, Knight, KnightToOwner, Knights, OwnedKnights, Pallet, Releases,
+    StorageVersion,
+};
+use codec::{Decode, Encode};
+use frame_support::{
+    storage::migration::storage_key_iter,
+    traits::{Get, PalletInfo},
+    weights::Weight,
+    Blake2_128Concat,
+};
+use sp_runtime::traits::Zero;
+use sp_std::prelude::*;
+
+/// Bring storage up to `Releases::LATEST`.
+pub fn migrate<T: Config>() -> Weight {
+    let mut weight = T::DbWeight::get().reads(1);
+    let version = StorageVersion::<T>::get();
+
+    if version < Releases::V2_0_0 {
+        weight = weight.saturating_add(v2::migrate::<T>());
+    }
+
+    if version < Releases::V3_0_0 {
+        weight = weight.saturating_add(v3::migrate::<T>());
+    }
+
+    if version < Releases::V4_0_0 {
+        weight = weight.saturating_add(v4::migrate::<T>());
+    }
+
+    if version < Releases::V5_0_0 {
+        weight = weight.saturating_add(v5::migrate::<T>());
+    }
+
+    weight
+}
+
+/// `V1_0_0` -> `V2_0_0`: add `birth_block` and `parents` to `Knight`.
+pub mod v2 {
+    use super::*;
+
+    /// `Knight` as it was stored in `V1_0_0`.
+    #[derive(Encode, Decode, Clone, PartialEq, Eq)]
+    #[cfg_attr(feature = "std", derive(Debug))]
+    pub struct OldKnight<Balance, KnightIndex> {
+        pub id: KnightIndex,
+        pub dna: [u8; 16],
+        pub name: Vec<u8>,
+        pub wealth: Balance,
+        pub price: Balance,
+        pub gen: u64,
+    }
+
+    pub fn migrate<T: Config>() -> Weight {
+        let mut translated = 0u64;
+
+        // The original birth block and parents were never recorded, so the
+        // best we can do is the genesis block and no parents.
+        Knights::<T>::translate::<OldKnight<T::Balance, T::KnightIndex>, _>(|_, old| {
+            translated += 1;
+
+            Some(Knight {
+                id: old.id,
+                dna: old.dna,
+                name: old.name,
+                wealth: old.wealth,
+                price: old.price,
+                gen: old.gen,
+                birth_block: Zero::zero(),
+                parents: None,
+            })
+        });
+
+        StorageVersion::<T>::put(Releases::V2_0_0);
+
+        T::DbWeight::get().reads_writes(translated, translated + 1)
+    }
+}
+
+/// `V2_0_0` -> `V3_0_0`: move `OwnerToKnights` vectors into the `OwnedKnights`
+/// double map.
+pub mod v3 {
+    use super::*;
+
+    /// The storage item the vectors were kept under.
+    pub const OLD_ITEM: &[u8] = b"OwnerToKnights";
+
+    pub fn migrate<T: Config>() -> Weight {
+        let pallet_name = <T as frame_system::Config>::PalletInfo::name::<Pallet<T>>()
+            .expect("pallet-knights is part of the runtime");
+
+        let mut reads = 0u64;
+        let mut writes = 0u64;
+
+        let old = storage_key_iter::<T::AccountId, Vec<T::KnightIndex>, Blake2_128Concat>(
+            pallet_name.as_bytes(),
+            OLD_ITEM,
+        );
+
+        for (owner, knight_ids) in old.drain() {
+            reads += 1;
+            writes += 1;
+
+            // The old vectors were not kept sorted and a failed transfer
+            // could leave a stale id behind, so only carry over the ids
+            // that `KnightToOwner` agrees belong to this owner.
+            for knight_id in knight_ids {
+                reads += 1;
+
+                if KnightToOwner::<T>::get(knight_id).as_ref() == Some(&owner) {
+                    OwnedKnights::<T>::insert(&owner, knight_id, ());
+                    writes += 1;
+                }
+            }
+        }
+
+        StorageVersion::<T>::put(Releases::V3_0_0);
+
+        T::DbWeight::get().reads_writes(reads, writes + 1)
+    }
+}
+
+/// `V3_0_0` -> `V4_0_0`: count the traits of every existing knight into `TraitCounts`.
+pub mod v4 {
+    use super::*;
+
+    pub fn migrate<T: Config>() -> Weight {
+        let mut counted = 0u64;
+
+        for knight in Knights::<T>::iter_values() {
+            counted += 1;
+            Pallet::<T>::_count_traits(&knight.dna, true);
+        }
+
+        StorageVersion::<T>::put(Releases::V4_0_0);
+
+        // every knight reads and writes its four trait counts
+        T::DbWeight::get().reads_writes(counted * 5, counted * 4 + 1)
+    }
+}
+
+/// `V4_0_0` -> `V5_0_0`: index every squire under its parents in `Children`.
+pub mod v5 {
+    use super::*;
+
+    pub fn migrate<T: Config>() -> Weight {
+        let mut reads = 0u64;
+        let mut writes = 0u64;
+
+        for knight in Knights::<T>::iter_values() {
+            reads += 1;
+
+            if let Some((parent_1, parent_2)) = knight.parents {
+                Children::<T>::insert(parent_1, knight.id, ());
+                Children::<T>::insert(parent_2, knight.id, ());
+                writes += 2;
+            }
+        }
+
+        StorageVersion::<T>::put(Releases::V5_0_0);
+
+        T::DbWeight::get().reads_writes(reads, writes + 1)
+    }
+}
@@ -1,13 +1,14 @@
 use crate as pallet_knights;
 use frame_support::parameter_types;
-use frame_support::traits::TestRandomness;
+use frame_support::traits::{Currency, Get, OnUnbalanced, TestRandomness};
 use frame_system as system;
 use sp_core::H256;
 use sp_runtime::{
     testing::Header,
     traits::{BlakeTwo256, IdentityLookup},
-    BuildStorage,
+    BuildStorage, ModuleId, Perbill,
 };
+use std::cell::RefCell;
 
 type UncheckedExtrinsic = frame_system::mocking::MockUncheckedExtrinsic<Test>;
 type Block = frame_system::mocking::MockBlock<Test>;
@@ -75,11 +76,92 @@ impl pallet_balances::Config for Test {
     type WeightInfo = pallet_balances::weights::SubstrateWeight<Test>;
 }
 
+parameter_types! {
+    pub const BaseSquireCooldown: u64 = 10;
+    pub const SquireCooldownPerGeneration: u64 = 5;
+    pub const MaxNameLength: u32 = 64;
+    pub const MinAuctionDuration: u64 = 5;
+    pub const MaxAuctionDuration: u64 = 100;
+    pub const MaxAuctionsPerBlock: u32 = 2;
+    pub const RevealPeriod: u64 = 5;
+    pub const MaxSealedBids: u32 = 3;
+    pub const OfferDuration: u64 = 20;
+    pub const MaxOffersPerBlock: u32 = 2;
+    pub const MinOfferAmount: u128 = 5;
+    pub const MaxRoyalty: Perbill = Perbill::from_percent(20);
+    pub const KnightsModuleId: ModuleId = ModuleId(*b"py/knigt");
+    pub const MaxTournamentSize: u32 = 8;
+    pub const TournamentRoundInterval: u64 = 10;
+    pub const MaxActiveTournaments: u32 = 1;
+    pub const MaxRegistrationPeriod: u64 = 50;
+    pub const MaxRegistrationsPerBlock: u32 = 2;
+    pub const RunnerUpPrize: Perbill = Perbill::from_percent(25);
+    pub const DuelExperience: u64 = 100;
+    pub const MaxDuelsPerBlock: u32 = 2;
+    pub const MaxLineageDepth: u32 = 5;
+    pub const MaxOrderMembers: u32 = 4;
+    pub const MaxOrderOfficers: u32 = 2;
+    pub const OrderVotingPeriod: u64 = 10;
+    pub const MaxOpenSpends: u32 = 2;
+}
+
+/// The account marketplace fees are paid to in tests.
+pub const TREASURY: u64 = 99;
+
+thread_local! {
+    pub static MARKETPLACE_FEE: RefCell<Perbill> = RefCell::new(Perbill::zero());
+}
+
+/// Zero unless a test sets `MARKETPLACE_FEE`, so fee-free tests keep round numbers.
+pub struct MarketplaceFee;
+impl Get<Perbill> for MarketplaceFee {
+    fn get() -> Perbill {
+        MARKETPLACE_FEE.with(|fee| *fee.borrow())
+    }
+}
+
+pub struct FeeToTreasury;
+impl OnUnbalanced<pallet_knights::NegativeImbalanceOf<Test>> for FeeToTreasury {
+    fn on_nonzero_unbalanced(fee: pallet_knights::NegativeImbalanceOf<Test>) {
+        Balances::resolve_creating(&TREASURY, fee);
+    }
+}
+
 // pub trait Config: pallet_balances::Config + frame_system::Config {
 impl pallet_knights::Config for Test {
     type Event = Event;
     type Currency = Balances;
     type RandomnessSource = TestRandomness;
+    type KnightIndex = u64;
+    type BaseSquireCooldown = BaseSquireCooldown;
+    type SquireCooldownPerGeneration = SquireCooldownPerGeneration;
+    type MaxNameLength = MaxNameLength;
+    type MinAuctionDuration = MinAuctionDuration;
+    type MaxAuctionDuration = MaxAuctionDuration;
+    type MaxAuctionsPerBlock = MaxAuctionsPerBlock;
+    type RevealPeriod = RevealPeriod;
+    type MaxSealedBids = MaxSealedBids;
+    type OfferDuration = OfferDuration;
+    type MaxOffersPerBlock = MaxOffersPerBlock;
+    type MinOfferAmount = MinOfferAmount;
+    type MaxRoyalty = MaxRoyalty;
+    type MarketplaceFee = MarketplaceFee;
+    type OnMarketplaceFee = FeeToTreasury;
+    type ModuleId = KnightsModuleId;
+    type MaxTournamentSize = MaxTournamentSize;
+    type TournamentRoundInterval = TournamentRoundInterval;
+    type MaxActiveTournaments = MaxActiveTournaments;
+    type MaxRegistrationPeriod = MaxRegistrationPeriod;
+    type MaxRegistrationsPerBlock = MaxRegistrationsPerBlock;
+    type RunnerUpPrize = RunnerUpPrize;
+    type DuelExperience = DuelExperience;
+    type MaxDuelsPerBlock = MaxDuelsPerBlock;
+    type MaxLineageDepth = MaxLineageDepth;
+    type MaxOrderMembers = MaxOrderMembers;
+    type MaxOrderOfficers = MaxOrderOfficers;
+    type OrderVotingPeriod = OrderVotingPeriod;
+    type MaxOpenSpends = MaxOpenSpends;
+    type WeightInfo = ();
 }
 
 // Build genesis storage according to the mock runtime.
@@ -1,8 +1,14 @@
-use crate::{mock::*, Error};
-use frame_support::traits::Currency;
+use crate::{migrations, mock::*, Error, Releases};
+use codec::Encode;
+use frame_support::dispatch::DispatchResultWithPostInfo;
+use frame_support::storage::migration::{get_storage_value, put_storage_value};
 use frame_support::traits::RefCell;
+use frame_support::traits::{Currency, OnInitialize, ReservableCurrency};
+use frame_support::{Blake2_128Concat, StorageHasher};
 use pallet_balances::Error as BalancesError;
 use sp_core::H256;
+use sp_runtime::traits::{BlakeTwo256, Hash};
+use sp_runtime::Perbill;
 
 use frame_support::{assert_err, assert_noop, assert_ok};
 
@@ -14,13 +20,26 @@ fn last_event() -> Event {
     System::events().last().unwrap().event.clone()
 }
 
+fn commitment(bid: u128, salt: H256) -> H256 {
+    BlakeTwo256::hash_of(&(bid, salt))
+}
+
+fn run_to_block(n: u64) {
+    while System::block_number() < n {
+        System::set_block_number(System::block_number() + 1);
+        KnightModule::on_initialize(System::block_number());
+    }
+}
+
 #[test]
 fn can_create_knight() {
     new_test_ext().execute_with(|| {
         let name = "Danny the Brave";
         assert_ok!(KnightModule::create_knight(
             Origin::signed(1),
-            name.as_bytes().to_vec()
+            name.as_bytes().to_vec(),
+            Perbill::zero(),
+            None
         ));
 
         let k = KnightModule::knights(&1).unwrap();
@@ -28,7 +47,7 @@ fn can_create_knight() {
         assert_eq!(k.name, name.as_bytes().to_vec());
         assert_eq!(k.gen, 1);
 
-        let knight_ids = KnightModule::owner_to_knights(&1);
+        let knight_ids = KnightModule::knight_ids_of_owner(&1);
         assert_eq!(knight_ids.len(), 1);
 
         let owner_knight_count = KnightModule::owner_to_knight_count(&1);
@@ -41,7 +60,9 @@ fn can_buy_knight() {
     new_test_ext().execute_with(|| {
         assert_ok!(KnightModule::create_knight(
             Origin::signed(1),
-            "Sir Rowan".as_bytes().to_vec()
+            "Sir Rowan".as_bytes().to_vec(),
+            Perbill::zero(),
+            None
         ));
 
         Balances::make_free_balance_be(&2, 50);
@@ -50,7 +71,7 @@ fn can_buy_knight() {
         assert_eq!(Balances::free_balance(&2), 50);
 
         KnightModule::set_price(Origin::signed(1), 1, 20).unwrap();
-        KnightModule::buy_knight(Origin::signed(2), 1).unwrap();
+        KnightModule::buy_knight(Origin::signed(2), 1, 20).unwrap();
 
         assert_eq!(Balances::free_balance(&1), 20);
         assert_eq!(Balances::free_balance(&2), 30);
@@ -64,14 +85,16 @@ fn knight_price_returns_to_zero_after_purchase() {
     new_test_ext().execute_with(|| {
         assert_ok!(KnightModule::create_knight(
             Origin::signed(1),
-            "Sir Bentley".as_bytes().to_vec()
+            "Sir Bentley".as_bytes().to_vec(),
+            Perbill::zero(),
+            None
         ));
 
         assert_ok!(KnightModule::set_price(Origin::signed(1), 1, 10));
 
         Balances::make_free_balance_be(&2, 50);
 
-        assert_ok!(KnightModule::buy_knight(Origin::signed(2), 1));
+        assert_ok!(KnightModule::buy_knight(Origin::signed(2), 1, 10));
         assert_eq!(KnightModule::knights(1).unwrap().price, 0);
     });
 }
@@ -81,7 +104,9 @@ fn cannot_buy_knight_with_insufficient_funds() {
     new_test_ext().execute_with(|| {
         assert_ok!(KnightModule::create_knight(
             Origin::signed(1),
-            "Sir Rowan".as_bytes().to_vec()
+            "Sir Rowan".as_bytes().to_vec(),
+            Perbill::zero(),
+            None
         ));
 
         KnightModule::set_price(Origin::signed(1), 1, 10).unwrap();
@@ -89,18 +114,61 @@ fn cannot_buy_knight_with_insufficient_funds() {
         Balances::make_free_balance_be(&2, 5);
 
         assert_err!(
-            KnightModule::buy_knight(Origin::signed(2), 1),
+            KnightModule::buy_knight(Origin::signed(2), 1, 10),
             BalancesError::<Test>::InsufficientBalance,
         );
     });
 }
 
+#[test]
+fn cannot_buy_knight_above_max_price() {
+    new_test_ext().execute_with(|| {
+        System::set_block_number(1);
+        assert_ok!(KnightModule::create_knight(
+            Origin::signed(1),
+            "Sir Rowan".as_bytes().to_vec(),
+            Perbill::zero(),
+            None
+        ));
+        Balances::make_free_balance_be(&2, 50);
+
+        assert_noop!(
+            KnightModule::buy_knight(Origin::signed(2), 1, 10),
+            Error::<Test>::KnightNotForSale
+        );
+
+        assert_ok!(KnightModule::set_price(Origin::signed(1), 1, 10));
+        assert_noop!(
+            KnightModule::buy_knight(Origin::signed(1), 1, 10),
+            Error::<Test>::AlreadyOwnKnight
+        );
+
+        // the owner raises the price before the buyer's purchase executes
+        assert_ok!(KnightModule::set_price(Origin::signed(1), 1, 40));
+        assert_noop!(
+            KnightModule::buy_knight(Origin::signed(2), 1, 10),
+            Error::<Test>::PriceChanged
+        );
+
+        // a lowered price is fine, and the buyer pays only that
+        assert_ok!(KnightModule::set_price(Origin::signed(1), 1, 5));
+        assert_ok!(KnightModule::buy_knight(Origin::signed(2), 1, 10));
+        assert_eq!(Balances::free_balance(&2), 45);
+        assert_eq!(
+            last_event(),
+            Event::pallet_knights(crate::Event::KnightSold(1, 1, 2, 5))
+        );
+    });
+}
+
 #[test]
 fn can_set_price_for_knight() {
     new_test_ext().execute_with(|| {
         assert_ok!(KnightModule::create_knight(
             Origin::signed(1),
-            "Sir Rowan".as_bytes().to_vec()
+            "Sir Rowan".as_bytes().to_vec(),
+            Perbill::zero(),
+            None
         ));
 
         KnightModule::set_price(Origin::signed(1), 1, 100).expect("cannot set price");
@@ -119,6 +187,8 @@ fn non_owner_cannot_set_price_for_knight() {
         assert_ok!(KnightModule::create_knight(
             Origin::signed(1),
             "Sir Cedric".as_bytes().to_vec(),
+            Perbill::zero(),
+            None
         ));
 
         let check = Origin::signed(1000);
@@ -135,12 +205,16 @@ fn can_get_knight_count() {
     new_test_ext().execute_with(|| {
         assert_ok!(KnightModule::create_knight(
             Origin::signed(1),
-            "Evan the Great".as_bytes().to_vec()
+            "Evan the Great".as_bytes().to_vec(),
+            Perbill::zero(),
+            None
         ));
 
         assert_ok!(KnightModule::create_knight(
             Origin::signed(1),
-            "Christian the Fearless".as_bytes().to_vec()
+            "Christian the Fearless".as_bytes().to_vec(),
+            Perbill::zero(),
+            None
         ));
 
         assert_eq!(KnightModule::knight_count(), 2);
@@ -152,12 +226,16 @@ fn can_get_knights_owner() {
     new_test_ext().execute_with(|| {
         assert_ok!(KnightModule::create_knight(
             Origin::signed(1),
-            "Evan the Bold".as_bytes().to_vec()
+            "Evan the Bold".as_bytes().to_vec(),
+            Perbill::zero(),
+            None
         ));
 
         assert_ok!(KnightModule::create_knight(
             Origin::signed(1),
-            "Daniel the Courageous".as_bytes().to_vec()
+            "Daniel the Courageous".as_bytes().to_vec(),
+            Perbill::zero(),
+            None
         ));
 
         assert_eq!(KnightModule::knight_to_owner(&1).unwrap(), 1);
@@ -170,20 +248,26 @@ fn can_get_owners_knights() {
     new_test_ext().execute_with(|| {
         assert_ok!(KnightModule::create_knight(
             Origin::signed(1),
-            "Sir Christian".as_bytes().to_vec()
+            "Sir Christian".as_bytes().to_vec(),
+            Perbill::zero(),
+            None
         ));
 
         assert_ok!(KnightModule::create_knight(
             Origin::signed(1),
-            "Sir Daniel".as_bytes().to_vec()
+            "Sir Daniel".as_bytes().to_vec(),
+            Perbill::zero(),
+            None
         ));
 
         assert_ok!(KnightModule::create_knight(
             Origin::signed(1),
-            "Sir Evan".as_bytes().to_vec()
+            "Sir Evan".as_bytes().to_vec(),
+            Perbill::zero(),
+            None
         ));
 
-        let knights = KnightModule::owner_to_knights(&1);
+        let knights = KnightModule::knight_ids_of_owner(&1);
 
         assert_eq!(knights.len(), 3);
 
@@ -209,12 +293,16 @@ fn knight_has_unique_id_even_with_identical_names_and_owners() {
     new_test_ext().execute_with(|| {
         assert_ok!(KnightModule::create_knight(
             Origin::signed(1),
-            "Sir Evan".as_bytes().to_vec()
+            "Sir Evan".as_bytes().to_vec(),
+            Perbill::zero(),
+            None
         ));
 
         assert_ok!(KnightModule::create_knight(
             Origin::signed(1),
-            "Sir Evan".as_bytes().to_vec()
+            "Sir Evan".as_bytes().to_vec(),
+            Perbill::zero(),
+            None
         ));
 
         let sir_evan1 = KnightModule::knights(&1).unwrap();
@@ -230,28 +318,32 @@ fn can_transfer_knight() {
     new_test_ext().execute_with(|| {
         assert_ok!(KnightModule::create_knight(
             Origin::signed(1),
-            "Beric the Briton".as_bytes().to_vec()
+            "Beric the Briton".as_bytes().to_vec(),
+            Perbill::zero(),
+            None
         ));
 
         assert_ok!(KnightModule::create_knight(
             Origin::signed(1),
-            "Sir Rowan of Chessington".as_bytes().to_vec()
+            "Sir Rowan of Chessington".as_bytes().to_vec(),
+            Perbill::zero(),
+            None
         ));
 
         assert_eq!(KnightModule::knight_to_owner(&1).unwrap(), 1);
-        assert_eq!(KnightModule::owner_to_knights(&1).len(), 2);
-        assert_eq!(KnightModule::owner_to_knights(&2).len(), 0);
+        assert_eq!(KnightModule::knight_ids_of_owner(&1).len(), 2);
+        assert_eq!(KnightModule::knight_ids_of_owner(&2).len(), 0);
         assert_eq!(KnightModule::owner_to_knight_count(&2), 0);
 
         assert_eq!(KnightModule::knight_to_owner(&1).unwrap(), 1);
-        assert_eq!(KnightModule::owner_to_knights(&1).len(), 2);
-        assert_eq!(KnightModule::owner_to_knights(&2).len(), 0);
+        assert_eq!(KnightModule::knight_ids_of_owner(&1).len(), 2);
+        assert_eq!(KnightModule::knight_ids_of_owner(&2).len(), 0);
 
         KnightModule::transfer_knight(Origin::signed(1), 1, 2).unwrap();
 
         assert_eq!(KnightModule::knight_to_owner(&1).unwrap(), 2);
-        assert_eq!(KnightModule::owner_to_knights(&1).len(), 1);
-        assert_eq!(KnightModule::owner_to_knights(&2).len(), 1);
+        assert_eq!(KnightModule::knight_ids_of_owner(&1).len(), 1);
+        assert_eq!(KnightModule::knight_ids_of_owner(&2).len(), 1);
         assert_eq!(KnightModule::owner_to_knight_count(&2), 1);
     });
 }
@@ -261,7 +353,9 @@ fn non_owner_cannot_transfer_knight() {
     new_test_ext().execute_with(|| {
         assert_ok!(KnightModule::create_knight(
             Origin::signed(1),
-            "Alfred the Great".as_bytes().to_vec()
+            "Alfred the Great".as_bytes().to_vec(),
+            Perbill::zero(),
+            None
         ));
 
         assert_err!(
@@ -281,17 +375,34 @@ fn cannot_transfer_non_existant_knight() {
     });
 }
 
+#[test]
+fn cannot_transfer_knight_to_self() {
+    new_test_ext().execute_with(|| {
+        create_knights(1);
+
+        assert_noop!(
+            KnightModule::transfer_knight(Origin::signed(1), 1, 1),
+            Error::<Test>::KnightAlreadyExists
+        );
+        assert_eq!(KnightModule::owner_to_knight_count(&1), 1);
+    });
+}
+
 #[test]
 fn can_knight_a_squire() {
     new_test_ext().execute_with(|| {
         assert_ok!(KnightModule::create_knight(
             Origin::signed(2),
-            "Sir Evan".as_bytes().to_vec()
+            "Sir Evan".as_bytes().to_vec(),
+            Perbill::zero(),
+            None
         ));
 
         assert_ok!(KnightModule::create_knight(
             Origin::signed(2),
-            "Sir Christian".as_bytes().to_vec()
+            "Sir Christian".as_bytes().to_vec(),
+            Perbill::zero(),
+            None
         ));
 
         assert_eq!(KnightModule::knight_count(), 2);
@@ -299,6 +410,19 @@ fn can_knight_a_squire() {
         let knight_1_id = 1;
         let knight_2_id = 2;
 
+        assert_ok!(KnightModule::approve_squire(
+            Origin::signed(2),
+            knight_1_id,
+            1,
+            false
+        ));
+        assert_ok!(KnightModule::approve_squire(
+            Origin::signed(2),
+            knight_2_id,
+            1,
+            false
+        ));
+
         assert_ok!(KnightModule::knight_squire(
             Origin::signed(1),
             "Sir Daniel".as_bytes().to_vec(),
@@ -319,3 +443,3462 @@ fn can_knight_a_squire() {
         assert_ne!(sir_christian.dna, sir_daniel.dna);
     });
 }
+
+#[test]
+fn squire_records_parents_and_birth_block() {
+    new_test_ext().execute_with(|| {
+        assert_ok!(KnightModule::create_knight(
+            Origin::signed(1),
+            "Sir Evan".as_bytes().to_vec(),
+            Perbill::zero(),
+            None
+        ));
+
+        assert_ok!(KnightModule::create_knight(
+            Origin::signed(1),
+            "Sir Christian".as_bytes().to_vec(),
+            Perbill::zero(),
+            None
+        ));
+
+        System::set_block_number(5);
+
+        assert_ok!(KnightModule::knight_squire(
+            Origin::signed(1),
+            "Sir Daniel".as_bytes().to_vec(),
+            1,
+            2
+        ));
+
+        let sir_evan = KnightModule::knights(1).unwrap();
+        assert_eq!(sir_evan.parents, None);
+        assert_eq!(sir_evan.birth_block, 0);
+
+        let sir_daniel = KnightModule::knights(3).unwrap();
+        assert_eq!(sir_daniel.parents, Some((1, 2)));
+        assert_eq!(sir_daniel.birth_block, 5);
+    });
+}
+
+fn put_v1_knight(knight: migrations::v2::OldKnight<u128, u64>) {
+    put_storage_value(
+        b"KnightModule",
+        b"Knights",
+        &Blake2_128Concat::hash(&knight.id.encode()),
+        knight,
+    );
+}
+
+#[test]
+fn migration_to_v2_translates_old_knights() {
+    new_test_ext().execute_with(|| {
+        put_v1_knight(migrations::v2::OldKnight {
+            id: 1,
+            dna: [1; 16],
+            name: "Sir Old".as_bytes().to_vec(),
+            wealth: 0,
+            price: 25,
+            gen: 1,
+        });
+        put_v1_knight(migrations::v2::OldKnight {
+            id: 2,
+            dna: [2; 16],
+            name: "Sir Older".as_bytes().to_vec(),
+            wealth: 0,
+            price: 0,
+            gen: 3,
+        });
+
+        assert_eq!(KnightModule::storage_version(), Releases::V1_0_0);
+
+        migrations::migrate::<Test>();
+
+        assert_eq!(KnightModule::storage_version(), Releases::LATEST);
+
+        let sir_old = KnightModule::knights(1).unwrap();
+        assert_eq!(sir_old.name, "Sir Old".as_bytes().to_vec());
+        assert_eq!(sir_old.dna, [1; 16]);
+        assert_eq!(sir_old.price, 25);
+        assert_eq!(sir_old.gen, 1);
+        assert_eq!(sir_old.birth_block, 0);
+        assert_eq!(sir_old.parents, None);
+
+        let sir_older = KnightModule::knights(2).unwrap();
+        assert_eq!(sir_older.name, "Sir Older".as_bytes().to_vec());
+        assert_eq!(sir_older.gen, 3);
+    });
+}
+
+#[test]
+fn migration_is_skipped_when_storage_is_current() {
+    new_test_ext().execute_with(|| {
+        assert_ok!(KnightModule::create_knight(
+            Origin::signed(1),
+            "Sir Evan".as_bytes().to_vec(),
+            Perbill::zero(),
+            None
+        ));
+        crate::pallet::StorageVersion::<Test>::put(Releases::LATEST);
+
+        let before = KnightModule::knights(1).unwrap();
+
+        migrations::migrate::<Test>();
+
+        assert_eq!(KnightModule::knights(1).unwrap(), before);
+        assert_eq!(KnightModule::storage_version(), Releases::LATEST);
+    });
+}
+
+#[test]
+fn cannot_create_knight_with_invalid_name() {
+    new_test_ext().execute_with(|| {
+        assert_noop!(
+            KnightModule::create_knight(Origin::signed(1), vec![], Perbill::zero(), None),
+            Error::<Test>::NameEmpty
+        );
+
+        assert_noop!(
+            KnightModule::create_knight(Origin::signed(1), vec![b'k'; 65], Perbill::zero(), None),
+            Error::<Test>::NameTooLong
+        );
+
+        assert_noop!(
+            KnightModule::create_knight(Origin::signed(1), vec![0xff, 0xfe], Perbill::zero(), None),
+            Error::<Test>::NameNotUtf8
+        );
+
+        assert_ok!(KnightModule::create_knight(
+            Origin::signed(1),
+            vec![b'k'; 64],
+            Perbill::zero(),
+            None
+        ));
+    });
+}
+
+#[test]
+fn cannot_knight_a_squire_with_invalid_name() {
+    new_test_ext().execute_with(|| {
+        assert_ok!(KnightModule::create_knight(
+            Origin::signed(1),
+            "Sir Evan".as_bytes().to_vec(),
+            Perbill::zero(),
+            None
+        ));
+
+        assert_ok!(KnightModule::create_knight(
+            Origin::signed(1),
+            "Sir Christian".as_bytes().to_vec(),
+            Perbill::zero(),
+            None
+        ));
+
+        assert_noop!(
+            KnightModule::knight_squire(Origin::signed(1), vec![], 1, 2),
+            Error::<Test>::NameEmpty
+        );
+
+        assert_noop!(
+            KnightModule::knight_squire(Origin::signed(1), vec![b'k'; 65], 1, 2),
+            Error::<Test>::NameTooLong
+        );
+
+        assert_noop!(
+            KnightModule::knight_squire(Origin::signed(1), vec![0xc0], 1, 2),
+            Error::<Test>::NameNotUtf8
+        );
+    });
+}
+
+#[test]
+fn can_transfer_knights_acquired_out_of_order() {
+    new_test_ext().execute_with(|| {
+        assert_ok!(KnightModule::create_knight(
+            Origin::signed(1),
+            "Sir Evan".as_bytes().to_vec(),
+            Perbill::zero(),
+            None
+        ));
+
+        assert_ok!(KnightModule::create_knight(
+            Origin::signed(2),
+            "Sir Christian".as_bytes().to_vec(),
+            Perbill::zero(),
+            None
+        ));
+
+        // account 2 now holds knight 2 followed by knight 1
+        assert_ok!(KnightModule::transfer_knight(Origin::signed(1), 1, 2));
+        assert_eq!(KnightModule::knight_ids_of_owner(&2), vec![1, 2]);
+
+        assert_ok!(KnightModule::transfer_knight(Origin::signed(2), 2, 3));
+
+        assert_eq!(KnightModule::knight_ids_of_owner(&2), vec![1]);
+        assert_eq!(KnightModule::knight_ids_of_owner(&3), vec![2]);
+        assert_eq!(KnightModule::owner_to_knight_count(&2), 1);
+        assert_eq!(KnightModule::owner_to_knight_count(&3), 1);
+    });
+}
+
+#[test]
+fn migration_to_v3_moves_owner_vectors_into_double_map() {
+    new_test_ext().execute_with(|| {
+        let owner_key = Blake2_128Concat::hash(&1u64.encode());
+
+        // an unsorted vector, plus a stale id that account 2 actually owns
+        put_storage_value(
+            b"KnightModule",
+            migrations::v3::OLD_ITEM,
+            &owner_key,
+            vec![2u64, 1u64, 3u64],
+        );
+        crate::KnightToOwner::<Test>::insert(1, 1);
+        crate::KnightToOwner::<Test>::insert(2, 1);
+        crate::KnightToOwner::<Test>::insert(3, 2);
+        crate::pallet::StorageVersion::<Test>::put(Releases::V2_0_0);
+
+        migrations::migrate::<Test>();
+
+        assert_eq!(KnightModule::storage_version(), Releases::LATEST);
+        assert_eq!(KnightModule::knight_ids_of_owner(&1), vec![1, 2]);
+        assert!(KnightModule::knight_ids_of_owner(&2).is_empty());
+        assert_eq!(
+            get_storage_value::<Vec<u64>>(b"KnightModule", migrations::v3::OLD_ITEM, &owner_key),
+            None
+        );
+    });
+}
+
+#[test]
+fn parents_must_rest_after_squiring() {
+    new_test_ext().execute_with(|| {
+        System::set_block_number(1);
+
+        assert_ok!(KnightModule::create_knight(
+            Origin::signed(1),
+            "Sir Evan".as_bytes().to_vec(),
+            Perbill::zero(),
+            None
+        ));
+
+        assert_ok!(KnightModule::create_knight(
+            Origin::signed(1),
+            "Sir Christian".as_bytes().to_vec(),
+            Perbill::zero(),
+            None
+        ));
+
+        assert_ok!(KnightModule::knight_squire(
+            Origin::signed(1),
+            "Sir Daniel".as_bytes().to_vec(),
+            1,
+            2
+        ));
+
+        // generation 1 parents rest for the base cooldown
+        assert_eq!(KnightModule::squire_ready_at(1), Some(11));
+        assert_eq!(KnightModule::squire_ready_at(2), Some(11));
+        assert_eq!(
+            last_event(),
+            Event::pallet_knights(crate::Event::KnightResting(2, 11))
+        );
+
+        assert_noop!(
+            KnightModule::knight_squire(Origin::signed(1), "Sir Rowan".as_bytes().to_vec(), 1, 2),
+            Error::<Test>::KnightStillResting
+        );
+
+        System::set_block_number(11);
+
+        assert_ok!(KnightModule::knight_squire(
+            Origin::signed(1),
+            "Sir Rowan".as_bytes().to_vec(),
+            1,
+            2
+        ));
+    });
+}
+
+#[test]
+fn later_generations_rest_longer() {
+    new_test_ext().execute_with(|| {
+        assert_eq!(KnightModule::squire_cooldown(1), 10);
+        assert_eq!(KnightModule::squire_cooldown(2), 15);
+        assert_eq!(KnightModule::squire_cooldown(4), 25);
+
+        System::set_block_number(1);
+
+        assert_ok!(KnightModule::create_knight(
+            Origin::signed(1),
+            "Sir Evan".as_bytes().to_vec(),
+            Perbill::zero(),
+            None
+        ));
+
+        assert_ok!(KnightModule::create_knight(
+            Origin::signed(1),
+            "Sir Christian".as_bytes().to_vec(),
+            Perbill::zero(),
+            None
+        ));
+
+        assert_ok!(KnightModule::knight_squire(
+            Origin::signed(1),
+            "Sir Daniel".as_bytes().to_vec(),
+            1,
+            2
+        ));
+
+        assert_ok!(KnightModule::create_knight(
+            Origin::signed(1),
+            "Sir Bedivere".as_bytes().to_vec(),
+            Perbill::zero(),
+            None
+        ));
+
+        System::set_block_number(11);
+
+        // Sir Daniel is generation 2
+        assert_ok!(KnightModule::knight_squire(
+            Origin::signed(1),
+            "Sir Rowan".as_bytes().to_vec(),
+            3,
+            4
+        ));
+
+        assert_eq!(KnightModule::squire_ready_at(3), Some(26));
+        assert_eq!(KnightModule::squire_ready_at(4), Some(21));
+    });
+}
+
+#[test]
+fn cannot_squire_with_knights_of_others_without_approval() {
+    new_test_ext().execute_with(|| {
+        assert_ok!(KnightModule::create_knight(
+            Origin::signed(1),
+            "Sir Evan".as_bytes().to_vec(),
+            Perbill::zero(),
+            None
+        ));
+
+        assert_ok!(KnightModule::create_knight(
+            Origin::signed(2),
+            "Sir Christian".as_bytes().to_vec(),
+            Perbill::zero(),
+            None
+        ));
+
+        assert_noop!(
+            KnightModule::knight_squire(Origin::signed(1), "Sir Daniel".as_bytes().to_vec(), 1, 2),
+            Error::<Test>::NotApprovedToSquire
+        );
+
+        assert_noop!(
+            KnightModule::knight_squire(Origin::signed(3), "Sir Daniel".as_bytes().to_vec(), 1, 2),
+            Error::<Test>::NotApprovedToSquire
+        );
+
+        assert_noop!(
+            KnightModule::knight_squire(Origin::signed(1), "Sir Daniel".as_bytes().to_vec(), 1, 9),
+            Error::<Test>::KnightNotFound
+        );
+    });
+}
+
+#[test]
+fn one_shot_squire_approval_is_used_up() {
+    new_test_ext().execute_with(|| {
+        assert_ok!(KnightModule::create_knight(
+            Origin::signed(1),
+            "Sir Evan".as_bytes().to_vec(),
+            Perbill::zero(),
+            None
+        ));
+
+        assert_ok!(KnightModule::create_knight(
+            Origin::signed(2),
+            "Sir Christian".as_bytes().to_vec(),
+            Perbill::zero(),
+            None
+        ));
+
+        assert_ok!(KnightModule::approve_squire(Origin::signed(2), 2, 1, true));
+        assert_eq!(
+            last_event(),
+            Event::pallet_knights(crate::Event::SquireApproved(2, 1, true))
+        );
+
+        assert_ok!(KnightModule::knight_squire(
+            Origin::signed(1),
+            "Sir Daniel".as_bytes().to_vec(),
+            1,
+            2
+        ));
+
+        assert_eq!(KnightModule::knight_to_owner(3), Some(1));
+        assert_eq!(KnightModule::squire_approval(2, 1), None);
+
+        System::set_block_number(100);
+
+        assert_noop!(
+            KnightModule::knight_squire(Origin::signed(1), "Sir Rowan".as_bytes().to_vec(), 1, 2),
+            Error::<Test>::NotApprovedToSquire
+        );
+    });
+}
+
+#[test]
+fn standing_squire_approval_lasts_until_revoked() {
+    new_test_ext().execute_with(|| {
+        assert_ok!(KnightModule::create_knight(
+            Origin::signed(1),
+            "Sir Evan".as_bytes().to_vec(),
+            Perbill::zero(),
+            None
+        ));
+
+        assert_ok!(KnightModule::create_knight(
+            Origin::signed(2),
+            "Sir Christian".as_bytes().to_vec(),
+            Perbill::zero(),
+            None
+        ));
+
+        assert_ok!(KnightModule::approve_squire(Origin::signed(2), 2, 1, false));
+
+        assert_ok!(KnightModule::knight_squire(
+            Origin::signed(1),
+            "Sir Daniel".as_bytes().to_vec(),
+            1,
+            2
+        ));
+
+        assert_eq!(KnightModule::squire_approval(2, 1), Some(false));
+
+        assert_ok!(KnightModule::revoke_squire_approval(
+            Origin::signed(2),
+            2,
+            1
+        ));
+        assert_eq!(KnightModule::squire_approval(2, 1), None);
+
+        System::set_block_number(100);
+
+        assert_noop!(
+            KnightModule::knight_squire(Origin::signed(1), "Sir Rowan".as_bytes().to_vec(), 1, 2),
+            Error::<Test>::NotApprovedToSquire
+        );
+
+        assert_noop!(
+            KnightModule::revoke_squire_approval(Origin::signed(2), 2, 1),
+            Error::<Test>::SquireApprovalNotFound
+        );
+    });
+}
+
+#[test]
+fn only_owner_can_manage_squire_approvals() {
+    new_test_ext().execute_with(|| {
+        assert_ok!(KnightModule::create_knight(
+            Origin::signed(2),
+            "Sir Christian".as_bytes().to_vec(),
+            Perbill::zero(),
+            None
+        ));
+
+        assert_noop!(
+            KnightModule::approve_squire(Origin::signed(1), 1, 1, false),
+            Error::<Test>::NotRightfulOwner
+        );
+
+        assert_ok!(KnightModule::approve_squire(Origin::signed(2), 1, 3, false));
+
+        assert_noop!(
+            KnightModule::revoke_squire_approval(Origin::signed(1), 1, 3),
+            Error::<Test>::NotRightfulOwner
+        );
+    });
+}
+
+#[test]
+fn transfer_clears_squire_approvals() {
+    new_test_ext().execute_with(|| {
+        assert_ok!(KnightModule::create_knight(
+            Origin::signed(2),
+            "Sir Christian".as_bytes().to_vec(),
+            Perbill::zero(),
+            None
+        ));
+
+        assert_ok!(KnightModule::approve_squire(Origin::signed(2), 1, 1, false));
+        assert_ok!(KnightModule::transfer_knight(Origin::signed(2), 1, 3));
+
+        assert_eq!(KnightModule::squire_approval(1, 1), None);
+    });
+}
+
+#[test]
+fn can_list_and_delist_sire() {
+    new_test_ext().execute_with(|| {
+        assert_ok!(KnightModule::create_knight(
+            Origin::signed(1),
+            "Sir Evan".as_bytes().to_vec(),
+            Perbill::zero(),
+            None
+        ));
+
+        assert_noop!(
+            KnightModule::list_sire(Origin::signed(2), 1, 10),
+            Error::<Test>::NotRightfulOwner
+        );
+
+        assert_ok!(KnightModule::list_sire(Origin::signed(1), 1, 10));
+        assert_eq!(KnightModule::sire_fee(1), Some(10));
+        assert_eq!(
+            last_event(),
+            Event::pallet_knights(crate::Event::SireListed(1, 10))
+        );
+
+        assert_ok!(KnightModule::delist_sire(Origin::signed(1), 1));
+        assert_eq!(KnightModule::sire_fee(1), None);
+
+        assert_noop!(
+            KnightModule::delist_sire(Origin::signed(1), 1),
+            Error::<Test>::SireNotListed
+        );
+    });
+}
+
+#[test]
+fn can_squire_with_hired_sire() {
+    new_test_ext().execute_with(|| {
+        assert_ok!(KnightModule::create_knight(
+            Origin::signed(1),
+            "Sir Evan".as_bytes().to_vec(),
+            Perbill::zero(),
+            None
+        ));
+
+        assert_ok!(KnightModule::create_knight(
+            Origin::signed(2),
+            "Sir Christian".as_bytes().to_vec(),
+            Perbill::zero(),
+            None
+        ));
+
+        Balances::make_free_balance_be(&1, 10);
+        Balances::make_free_balance_be(&2, 50);
+
+        assert_ok!(KnightModule::list_sire(Origin::signed(1), 1, 10));
+
+        assert_ok!(KnightModule::squire_with_sire(
+            Origin::signed(2),
+            "Sir Daniel".as_bytes().to_vec(),
+            2,
+            1,
+            10
+        ));
+
+        assert_eq!(Balances::free_balance(&1), 20);
+        assert_eq!(Balances::free_balance(&2), 40);
+
+        let sir_daniel = KnightModule::knights(3).unwrap();
+        assert_eq!(sir_daniel.gen, 2);
+        assert_eq!(sir_daniel.parents, Some((2, 1)));
+        assert_eq!(KnightModule::knight_to_owner(3), Some(2));
+
+        // the sire stays with its owner and stays listed
+        assert_eq!(KnightModule::knight_to_owner(1), Some(1));
+        assert_eq!(KnightModule::sire_fee(1), Some(10));
+
+        assert!(System::events().iter().any(|record| record.event
+            == Event::pallet_knights(crate::Event::SireHired(1, 2, 3, 1, 2, 10))));
+    });
+}
+
+#[test]
+fn cannot_hire_sire_with_bad_terms() {
+    new_test_ext().execute_with(|| {
+        assert_ok!(KnightModule::create_knight(
+            Origin::signed(1),
+            "Sir Evan".as_bytes().to_vec(),
+            Perbill::zero(),
+            None
+        ));
+
+        assert_ok!(KnightModule::create_knight(
+            Origin::signed(2),
+            "Sir Christian".as_bytes().to_vec(),
+            Perbill::zero(),
+            None
+        ));
+
+        assert_ok!(KnightModule::create_knight(
+            Origin::signed(1),
+            "Sir Rowan".as_bytes().to_vec(),
+            Perbill::zero(),
+            None
+        ));
+
+        Balances::make_free_balance_be(&2, 50);
+
+        assert_noop!(
+            KnightModule::squire_with_sire(
+                Origin::signed(2),
+                "Sir Daniel".as_bytes().to_vec(),
+                2,
+                1,
+                10
+            ),
+            Error::<Test>::SireNotListed
+        );
+
+        assert_ok!(KnightModule::list_sire(Origin::signed(1), 1, 10));
+
+        assert_noop!(
+            KnightModule::squire_with_sire(
+                Origin::signed(2),
+                "Sir Daniel".as_bytes().to_vec(),
+                2,
+                1,
+                9
+            ),
+            Error::<Test>::SireFeeTooHigh
+        );
+
+        assert_noop!(
+            KnightModule::squire_with_sire(
+                Origin::signed(2),
+                "Sir Daniel".as_bytes().to_vec(),
+                3,
+                1,
+                10
+            ),
+            Error::<Test>::NotRightfulOwner
+        );
+
+        assert_noop!(
+            KnightModule::squire_with_sire(
+                Origin::signed(1),
+                "Sir Daniel".as_bytes().to_vec(),
+                3,
+                1,
+                10
+            ),
+            Error::<Test>::CannotHireOwnSire
+        );
+    });
+}
+
+#[test]
+fn failed_sire_hire_refunds_fee() {
+    new_test_ext().execute_with(|| {
+        assert_ok!(KnightModule::create_knight(
+            Origin::signed(1),
+            "Sir Evan".as_bytes().to_vec(),
+            Perbill::zero(),
+            None
+        ));
+
+        assert_ok!(KnightModule::create_knight(
+            Origin::signed(2),
+            "Sir Christian".as_bytes().to_vec(),
+            Perbill::zero(),
+            None
+        ));
+
+        Balances::make_free_balance_be(&1, 10);
+        Balances::make_free_balance_be(&2, 50);
+
+        assert_ok!(KnightModule::list_sire(Origin::signed(1), 1, 10));
+        crate::SquireReadyAt::<Test>::insert(1, 100);
+
+        assert_noop!(
+            KnightModule::squire_with_sire(
+                Origin::signed(2),
+                "Sir Daniel".as_bytes().to_vec(),
+                2,
+                1,
+                10
+            ),
+            Error::<Test>::KnightStillResting
+        );
+    });
+}
+
+#[test]
+fn transfer_clears_sire_listing() {
+    new_test_ext().execute_with(|| {
+        assert_ok!(KnightModule::create_knight(
+            Origin::signed(1),
+            "Sir Evan".as_bytes().to_vec(),
+            Perbill::zero(),
+            None
+        ));
+
+        assert_ok!(KnightModule::list_sire(Origin::signed(1), 1, 10));
+        assert_ok!(KnightModule::transfer_knight(Origin::signed(1), 1, 2));
+
+        assert_eq!(KnightModule::sire_fee(1), None);
+    });
+}
+
+#[test]
+fn can_burn_knight() {
+    new_test_ext().execute_with(|| {
+        assert_ok!(KnightModule::create_knight(
+            Origin::signed(1),
+            "Sir Evan".as_bytes().to_vec(),
+            Perbill::zero(),
+            None
+        ));
+
+        assert_ok!(KnightModule::create_knight(
+            Origin::signed(1),
+            "Sir Christian".as_bytes().to_vec(),
+            Perbill::zero(),
+            None
+        ));
+
+        assert_ok!(KnightModule::approve_squire(Origin::signed(1), 1, 2, false));
+
+        assert_ok!(KnightModule::burn_knight(Origin::signed(1), 1));
+        assert_eq!(
+            last_event(),
+            Event::pallet_knights(crate::Event::KnightBurned(1, 1))
+        );
+
+        assert_eq!(KnightModule::knights(1), None);
+        assert_eq!(KnightModule::knight_to_owner(1), None);
+        assert_eq!(KnightModule::knight_ids_of_owner(&1), vec![2]);
+        assert_eq!(KnightModule::owner_to_knight_count(&1), 1);
+        assert_eq!(KnightModule::squire_approval(1, 2), None);
+        assert_eq!(KnightModule::knight_count(), 1);
+
+        // ids are never reused
+        assert_ok!(KnightModule::create_knight(
+            Origin::signed(1),
+            "Sir Daniel".as_bytes().to_vec(),
+            Perbill::zero(),
+            None
+        ));
+        assert_eq!(KnightModule::latest_knight_id(), 3);
+        assert_eq!(KnightModule::knight_count(), 2);
+        assert_ne!(
+            KnightModule::knights(2).unwrap().dna,
+            KnightModule::knights(3).unwrap().dna
+        );
+    });
+}
+
+#[test]
+fn cannot_burn_knight_of_another() {
+    new_test_ext().execute_with(|| {
+        assert_ok!(KnightModule::create_knight(
+            Origin::signed(1),
+            "Sir Evan".as_bytes().to_vec(),
+            Perbill::zero(),
+            None
+        ));
+
+        assert_noop!(
+            KnightModule::burn_knight(Origin::signed(2), 1),
+            Error::<Test>::NotRightfulOwner
+        );
+
+        assert_noop!(
+            KnightModule::burn_knight(Origin::signed(1), 2),
+            Error::<Test>::KnightNotFound
+        );
+    });
+}
+
+#[test]
+fn cannot_burn_listed_knight() {
+    new_test_ext().execute_with(|| {
+        assert_ok!(KnightModule::create_knight(
+            Origin::signed(1),
+            "Sir Evan".as_bytes().to_vec(),
+            Perbill::zero(),
+            None
+        ));
+
+        assert_ok!(KnightModule::set_price(Origin::signed(1), 1, 10));
+        assert_noop!(
+            KnightModule::burn_knight(Origin::signed(1), 1),
+            Error::<Test>::KnightForSale
+        );
+        assert_ok!(KnightModule::set_price(Origin::signed(1), 1, 0));
+
+        assert_ok!(KnightModule::list_sire(Origin::signed(1), 1, 10));
+        assert_noop!(
+            KnightModule::burn_knight(Origin::signed(1), 1),
+            Error::<Test>::KnightListedAsSire
+        );
+        assert_ok!(KnightModule::delist_sire(Origin::signed(1), 1));
+
+        assert_ok!(KnightModule::burn_knight(Origin::signed(1), 1));
+    });
+}
+
+#[test]
+fn can_win_auction() {
+    new_test_ext().execute_with(|| {
+        System::set_block_number(1);
+        assert_ok!(KnightModule::create_knight(
+            Origin::signed(1),
+            "Sir Percival".as_bytes().to_vec(),
+            Perbill::zero(),
+            None
+        ));
+        Balances::make_free_balance_be(&2, 100);
+        Balances::make_free_balance_be(&3, 100);
+
+        assert_ok!(KnightModule::start_auction(Origin::signed(1), 1, 10, 20));
+        assert_eq!(
+            KnightModule::knight_lock(1),
+            Some(crate::KnightLock::Auction)
+        );
+
+        assert_ok!(KnightModule::bid(Origin::signed(2), 1, 10));
+        assert_ok!(KnightModule::bid(Origin::signed(3), 1, 15));
+        // the outbid bidder gets their funds back
+        assert_eq!(Balances::reserved_balance(&2), 0);
+        assert_eq!(Balances::reserved_balance(&3), 15);
+
+        // raising your own bid only reserves the difference
+        assert_ok!(KnightModule::bid(Origin::signed(3), 1, 25));
+        assert_eq!(Balances::reserved_balance(&3), 25);
+        assert_eq!(Balances::free_balance(&3), 75);
+
+        run_to_block(19);
+        assert_eq!(KnightModule::knight_to_owner(1), Some(1));
+
+        run_to_block(20);
+        assert_eq!(KnightModule::knight_to_owner(1), Some(3));
+        assert_eq!(KnightModule::knight_lock(1), None);
+        assert_eq!(KnightModule::auction(1), None);
+        assert_eq!(Balances::reserved_balance(&3), 0);
+        assert_eq!(Balances::free_balance(&3), 75);
+        assert_eq!(Balances::free_balance(&1), 25);
+        assert_eq!(
+            last_event(),
+            Event::pallet_knights(crate::Event::AuctionWon(1, 1, 3, 25))
+        );
+    });
+}
+
+#[test]
+fn settling_auctions_is_charged_on_initialize() {
+    use crate::weights::WeightInfo;
+
+    new_test_ext().execute_with(|| {
+        System::set_block_number(1);
+        create_knights(2);
+        assert_ok!(KnightModule::start_auction(Origin::signed(1), 1, 10, 20));
+        assert_ok!(KnightModule::start_auction(Origin::signed(1), 2, 10, 20));
+
+        System::set_block_number(20);
+        assert_eq!(
+            KnightModule::on_initialize(20),
+            2 * <() as WeightInfo>::settle_auction()
+        );
+    });
+}
+
+#[test]
+fn auction_without_bids_returns_knight() {
+    new_test_ext().execute_with(|| {
+        System::set_block_number(1);
+        assert_ok!(KnightModule::create_knight(
+            Origin::signed(1),
+            "Sir Percival".as_bytes().to_vec(),
+            Perbill::zero(),
+            None
+        ));
+
+        assert_ok!(KnightModule::start_auction(Origin::signed(1), 1, 10, 20));
+        run_to_block(20);
+
+        assert_eq!(KnightModule::knight_to_owner(1), Some(1));
+        assert_eq!(KnightModule::knight_lock(1), None);
+        assert_eq!(
+            last_event(),
+            Event::pallet_knights(crate::Event::AuctionClosed(1, 1))
+        );
+    });
+}
+
+#[test]
+fn cannot_place_bad_bids() {
+    new_test_ext().execute_with(|| {
+        System::set_block_number(1);
+        assert_ok!(KnightModule::create_knight(
+            Origin::signed(1),
+            "Sir Percival".as_bytes().to_vec(),
+            Perbill::zero(),
+            None
+        ));
+        Balances::make_free_balance_be(&1, 100);
+        Balances::make_free_balance_be(&2, 100);
+        Balances::make_free_balance_be(&3, 5);
+
+        assert_noop!(
+            KnightModule::bid(Origin::signed(2), 1, 10),
+            Error::<Test>::AuctionNotFound
+        );
+
+        assert_ok!(KnightModule::start_auction(Origin::signed(1), 1, 10, 20));
+
+        assert_noop!(
+            KnightModule::bid(Origin::signed(1), 1, 10),
+            Error::<Test>::CannotBidOnOwnAuction
+        );
+        assert_noop!(
+            KnightModule::bid(Origin::signed(2), 1, 9),
+            Error::<Test>::BidTooLow
+        );
+
+        assert_ok!(KnightModule::bid(Origin::signed(2), 1, 10));
+        assert_noop!(
+            KnightModule::bid(Origin::signed(2), 1, 10),
+            Error::<Test>::BidTooLow
+        );
+        assert_noop!(
+            KnightModule::bid(Origin::signed(3), 1, 11),
+            BalancesError::<Test>::InsufficientBalance
+        );
+    });
+}
+
+#[test]
+fn cannot_start_auction_with_bad_terms() {
+    new_test_ext().execute_with(|| {
+        System::set_block_number(1);
+        for _ in 0..4 {
+            assert_ok!(KnightModule::create_knight(
+                Origin::signed(1),
+                "Sir Percival".as_bytes().to_vec(),
+                Perbill::zero(),
+                None
+            ));
+        }
+
+        assert_noop!(
+            KnightModule::start_auction(Origin::signed(2), 1, 10, 20),
+            Error::<Test>::NotRightfulOwner
+        );
+        assert_noop!(
+            KnightModule::start_auction(Origin::signed(1), 1, 10, 5),
+            Error::<Test>::InvalidAuctionEnd
+        );
+        assert_noop!(
+            KnightModule::start_auction(Origin::signed(1), 1, 10, 102),
+            Error::<Test>::InvalidAuctionEnd
+        );
+
+        assert_ok!(KnightModule::set_price(Origin::signed(1), 1, 10));
+        assert_noop!(
+            KnightModule::start_auction(Origin::signed(1), 1, 10, 20),
+            Error::<Test>::KnightForSale
+        );
+
+        assert_ok!(KnightModule::start_auction(Origin::signed(1), 2, 10, 20));
+        assert_ok!(KnightModule::start_auction(Origin::signed(1), 3, 10, 20));
+        assert_noop!(
+            KnightModule::start_auction(Origin::signed(1), 4, 10, 20),
+            Error::<Test>::TooManyAuctionsEnding
+        );
+        assert_noop!(
+            KnightModule::start_auction(Origin::signed(1), 2, 10, 30),
+            Error::<Test>::KnightLocked
+        );
+    });
+}
+
+#[test]
+fn knight_on_auction_is_locked() {
+    new_test_ext().execute_with(|| {
+        System::set_block_number(1);
+        assert_ok!(KnightModule::create_knight(
+            Origin::signed(1),
+            "Sir Percival".as_bytes().to_vec(),
+            Perbill::zero(),
+            None
+        ));
+        assert_ok!(KnightModule::start_auction(Origin::signed(1), 1, 10, 20));
+
+        assert_noop!(
+            KnightModule::transfer_knight(Origin::signed(1), 1, 2),
+            Error::<Test>::KnightLocked
+        );
+        assert_noop!(
+            KnightModule::set_price(Origin::signed(1), 1, 10),
+            Error::<Test>::KnightLocked
+        );
+        assert_noop!(
+            KnightModule::burn_knight(Origin::signed(1), 1),
+            Error::<Test>::KnightLocked
+        );
+    });
+}
+
+#[test]
+fn can_cancel_auction_without_bids() {
+    new_test_ext().execute_with(|| {
+        System::set_block_number(1);
+        assert_ok!(KnightModule::create_knight(
+            Origin::signed(1),
+            "Sir Percival".as_bytes().to_vec(),
+            Perbill::zero(),
+            None
+        ));
+        Balances::make_free_balance_be(&2, 100);
+
+        assert_ok!(KnightModule::start_auction(Origin::signed(1), 1, 10, 20));
+        assert_noop!(
+            KnightModule::cancel_auction(Origin::signed(2), 1),
+            Error::<Test>::NotRightfulOwner
+        );
+        assert_ok!(KnightModule::cancel_auction(Origin::signed(1), 1));
+
+        assert_eq!(KnightModule::auctions_ending_at(20), Vec::<u64>::new());
+        assert_eq!(KnightModule::knight_lock(1), None);
+        assert_ok!(KnightModule::transfer_knight(Origin::signed(1), 1, 2));
+
+        Balances::make_free_balance_be(&3, 100);
+        assert_ok!(KnightModule::start_auction(Origin::signed(2), 1, 10, 20));
+        assert_ok!(KnightModule::bid(Origin::signed(3), 1, 10));
+        assert_noop!(
+            KnightModule::cancel_auction(Origin::signed(2), 1),
+            Error::<Test>::AuctionHasBids
+        );
+    });
+}
+
+#[test]
+fn dutch_auction_price_falls_to_floor() {
+    new_test_ext().execute_with(|| {
+        System::set_block_number(1);
+        assert_ok!(KnightModule::create_knight(
+            Origin::signed(1),
+            "Sir Gawain".as_bytes().to_vec(),
+            Perbill::zero(),
+            None
+        ));
+        assert_eq!(KnightModule::dutch_auction_price(1), None);
+
+        assert_ok!(KnightModule::start_dutch_auction(
+            Origin::signed(1),
+            1,
+            100,
+            20,
+            10
+        ));
+        assert_eq!(KnightModule::dutch_auction_price(1), Some(100));
+
+        System::set_block_number(6);
+        assert_eq!(KnightModule::dutch_auction_price(1), Some(60));
+
+        System::set_block_number(11);
+        assert_eq!(KnightModule::dutch_auction_price(1), Some(20));
+
+        System::set_block_number(50);
+        assert_eq!(KnightModule::dutch_auction_price(1), Some(20));
+    });
+}
+
+#[test]
+fn can_buy_from_dutch_auction() {
+    new_test_ext().execute_with(|| {
+        System::set_block_number(1);
+        assert_ok!(KnightModule::create_knight(
+            Origin::signed(1),
+            "Sir Gawain".as_bytes().to_vec(),
+            Perbill::zero(),
+            None
+        ));
+        Balances::make_free_balance_be(&2, 100);
+
+        assert_ok!(KnightModule::start_dutch_auction(
+            Origin::signed(1),
+            1,
+            100,
+            20,
+            10
+        ));
+        assert_noop!(
+            KnightModule::transfer_knight(Origin::signed(1), 1, 3),
+            Error::<Test>::KnightLocked
+        );
+
+        System::set_block_number(6);
+        assert_noop!(
+            KnightModule::buy_from_dutch_auction(Origin::signed(2), 1, 59),
+            Error::<Test>::BidTooLow
+        );
+        assert_noop!(
+            KnightModule::buy_from_dutch_auction(Origin::signed(1), 1, 60),
+            Error::<Test>::CannotBidOnOwnAuction
+        );
+
+        // the buyer only pays the current price
+        assert_ok!(KnightModule::buy_from_dutch_auction(
+            Origin::signed(2),
+            1,
+            80
+        ));
+        assert_eq!(KnightModule::knight_to_owner(1), Some(2));
+        assert_eq!(Balances::free_balance(&1), 60);
+        assert_eq!(Balances::free_balance(&2), 40);
+        assert_eq!(KnightModule::dutch_auction(1), None);
+        assert_eq!(KnightModule::knight_lock(1), None);
+        assert_eq!(
+            last_event(),
+            Event::pallet_knights(crate::Event::AuctionWon(1, 1, 2, 60))
+        );
+    });
+}
+
+#[test]
+fn cannot_buy_from_dutch_auction_without_funds() {
+    new_test_ext().execute_with(|| {
+        System::set_block_number(1);
+        assert_ok!(KnightModule::create_knight(
+            Origin::signed(1),
+            "Sir Gawain".as_bytes().to_vec(),
+            Perbill::zero(),
+            None
+        ));
+        Balances::make_free_balance_be(&2, 50);
+
+        assert_ok!(KnightModule::start_dutch_auction(
+            Origin::signed(1),
+            1,
+            100,
+            20,
+            10
+        ));
+        assert_noop!(
+            KnightModule::buy_from_dutch_auction(Origin::signed(2), 1, 100),
+            BalancesError::<Test>::InsufficientBalance
+        );
+        assert_eq!(
+            KnightModule::knight_lock(1),
+            Some(crate::KnightLock::DutchAuction)
+        );
+    });
+}
+
+#[test]
+fn can_cancel_dutch_auction() {
+    new_test_ext().execute_with(|| {
+        System::set_block_number(1);
+        assert_ok!(KnightModule::create_knight(
+            Origin::signed(1),
+            "Sir Gawain".as_bytes().to_vec(),
+            Perbill::zero(),
+            None
+        ));
+
+        assert_noop!(
+            KnightModule::start_dutch_auction(Origin::signed(1), 1, 20, 100, 10),
+            Error::<Test>::FloorAboveStartPrice
+        );
+        assert_noop!(
+            KnightModule::start_dutch_auction(Origin::signed(1), 1, 100, 20, 1),
+            Error::<Test>::InvalidAuctionEnd
+        );
+
+        assert_ok!(KnightModule::start_dutch_auction(
+            Origin::signed(1),
+            1,
+            100,
+            20,
+            10
+        ));
+        assert_noop!(
+            KnightModule::start_auction(Origin::signed(1), 1, 10, 20),
+            Error::<Test>::KnightLocked
+        );
+        assert_noop!(
+            KnightModule::cancel_dutch_auction(Origin::signed(2), 1),
+            Error::<Test>::NotRightfulOwner
+        );
+
+        assert_ok!(KnightModule::cancel_dutch_auction(Origin::signed(1), 1));
+        assert_eq!(KnightModule::dutch_auction(1), None);
+        assert_ok!(KnightModule::transfer_knight(Origin::signed(1), 1, 2));
+    });
+}
+
+#[test]
+fn sealed_auction_winner_pays_second_price() {
+    new_test_ext().execute_with(|| {
+        System::set_block_number(1);
+        assert_ok!(KnightModule::create_knight(
+            Origin::signed(1),
+            "Sir Tristan".as_bytes().to_vec(),
+            Perbill::zero(),
+            None
+        ));
+        for bidder in 2..=4 {
+            Balances::make_free_balance_be(&bidder, 100);
+        }
+        let salt = H256::repeat_byte(7);
+
+        assert_ok!(KnightModule::start_sealed_auction(
+            Origin::signed(1),
+            1,
+            10,
+            10
+        ));
+        assert_ok!(KnightModule::commit_bid(
+            Origin::signed(2),
+            1,
+            commitment(30, salt),
+            50
+        ));
+        assert_ok!(KnightModule::commit_bid(
+            Origin::signed(3),
+            1,
+            commitment(20, salt),
+            20
+        ));
+        assert_ok!(KnightModule::commit_bid(
+            Origin::signed(4),
+            1,
+            commitment(40, salt),
+            40
+        ));
+        assert_eq!(Balances::reserved_balance(&2), 50);
+
+        run_to_block(10);
+        assert_ok!(KnightModule::reveal_bid(Origin::signed(3), 1, 20, salt));
+        assert_ok!(KnightModule::reveal_bid(Origin::signed(2), 1, 30, salt));
+
+        run_to_block(15);
+        assert_eq!(KnightModule::knight_to_owner(1), Some(2));
+        assert_eq!(KnightModule::knight_lock(1), None);
+        assert_eq!(KnightModule::sealed_auction(1), None);
+        assert_eq!(KnightModule::sealed_bid(1, 2), None);
+
+        // the winner pays the runner-up's bid
+        assert_eq!(Balances::free_balance(&1), 20);
+        assert_eq!(Balances::free_balance(&2), 80);
+        assert_eq!(Balances::reserved_balance(&2), 0);
+        // revealed deposits are released and unrevealed ones slashed
+        assert_eq!(Balances::free_balance(&3), 100);
+        assert_eq!(Balances::reserved_balance(&3), 0);
+        assert_eq!(Balances::free_balance(&4), 60);
+        assert_eq!(Balances::reserved_balance(&4), 0);
+
+        assert!(System::events()
+            .iter()
+            .any(|record| record.event
+                == Event::pallet_knights(crate::Event::AuctionWon(1, 1, 2, 20))));
+        assert!(System::events().iter().any(|record| record.event
+            == Event::pallet_knights(crate::Event::SealedBidSlashed(1, 4, 40))));
+    });
+}
+
+#[test]
+fn lone_sealed_bid_pays_reserve_price() {
+    new_test_ext().execute_with(|| {
+        System::set_block_number(1);
+        assert_ok!(KnightModule::create_knight(
+            Origin::signed(1),
+            "Sir Tristan".as_bytes().to_vec(),
+            Perbill::zero(),
+            None
+        ));
+        Balances::make_free_balance_be(&2, 100);
+        let salt = H256::repeat_byte(7);
+
+        assert_ok!(KnightModule::start_sealed_auction(
+            Origin::signed(1),
+            1,
+            10,
+            10
+        ));
+        assert_ok!(KnightModule::commit_bid(
+            Origin::signed(2),
+            1,
+            commitment(30, salt),
+            30
+        ));
+
+        run_to_block(10);
+        assert_ok!(KnightModule::reveal_bid(Origin::signed(2), 1, 30, salt));
+
+        run_to_block(15);
+        assert_eq!(KnightModule::knight_to_owner(1), Some(2));
+        assert_eq!(Balances::free_balance(&1), 10);
+        assert_eq!(Balances::free_balance(&2), 90);
+    });
+}
+
+#[test]
+fn sealed_auction_without_reveals_returns_knight() {
+    new_test_ext().execute_with(|| {
+        System::set_block_number(1);
+        assert_ok!(KnightModule::create_knight(
+            Origin::signed(1),
+            "Sir Tristan".as_bytes().to_vec(),
+            Perbill::zero(),
+            None
+        ));
+        Balances::make_free_balance_be(&2, 100);
+
+        assert_ok!(KnightModule::start_sealed_auction(
+            Origin::signed(1),
+            1,
+            10,
+            10
+        ));
+        assert_ok!(KnightModule::commit_bid(
+            Origin::signed(2),
+            1,
+            commitment(30, H256::zero()),
+            30
+        ));
+
+        run_to_block(15);
+        assert_eq!(KnightModule::knight_to_owner(1), Some(1));
+        assert_eq!(KnightModule::knight_lock(1), None);
+        assert_eq!(Balances::free_balance(&2), 70);
+        assert_eq!(Balances::reserved_balance(&2), 0);
+        assert_eq!(
+            last_event(),
+            Event::pallet_knights(crate::Event::AuctionClosed(1, 1))
+        );
+    });
+}
+
+#[test]
+fn cannot_commit_or_reveal_bad_sealed_bids() {
+    new_test_ext().execute_with(|| {
+        System::set_block_number(1);
+        assert_ok!(KnightModule::create_knight(
+            Origin::signed(1),
+            "Sir Tristan".as_bytes().to_vec(),
+            Perbill::zero(),
+            None
+        ));
+        for bidder in 2..=6 {
+            Balances::make_free_balance_be(&bidder, 100);
+        }
+        let salt = H256::repeat_byte(7);
+
+        assert_ok!(KnightModule::start_sealed_auction(
+            Origin::signed(1),
+            1,
+            10,
+            10
+        ));
+        assert_noop!(
+            KnightModule::commit_bid(Origin::signed(1), 1, commitment(30, salt), 30),
+            Error::<Test>::CannotBidOnOwnAuction
+        );
+        assert_ok!(KnightModule::commit_bid(
+            Origin::signed(2),
+            1,
+            commitment(30, salt),
+            20
+        ));
+        assert_noop!(
+            KnightModule::commit_bid(Origin::signed(2), 1, commitment(30, salt), 30),
+            Error::<Test>::AlreadyCommitted
+        );
+        assert_ok!(KnightModule::commit_bid(
+            Origin::signed(3),
+            1,
+            commitment(5, salt),
+            5
+        ));
+        assert_ok!(KnightModule::commit_bid(
+            Origin::signed(4),
+            1,
+            commitment(30, salt),
+            30
+        ));
+        assert_noop!(
+            KnightModule::commit_bid(Origin::signed(5), 1, commitment(30, salt), 30),
+            Error::<Test>::TooManySealedBids
+        );
+        assert_noop!(
+            KnightModule::cancel_sealed_auction(Origin::signed(1), 1),
+            Error::<Test>::AuctionHasBids
+        );
+
+        assert_noop!(
+            KnightModule::reveal_bid(Origin::signed(4), 1, 30, salt),
+            Error::<Test>::NotRevealPhase
+        );
+
+        run_to_block(10);
+        assert_noop!(
+            KnightModule::commit_bid(Origin::signed(6), 1, commitment(30, salt), 30),
+            Error::<Test>::CommitPhaseOver
+        );
+        assert_noop!(
+            KnightModule::reveal_bid(Origin::signed(6), 1, 30, salt),
+            Error::<Test>::SealedBidNotFound
+        );
+        assert_noop!(
+            KnightModule::reveal_bid(Origin::signed(4), 1, 31, salt),
+            Error::<Test>::CommitmentMismatch
+        );
+        assert_noop!(
+            KnightModule::reveal_bid(Origin::signed(2), 1, 30, salt),
+            Error::<Test>::BidExceedsDeposit
+        );
+        // a bid below the reserve is refunded on reveal but cannot win
+        assert_ok!(KnightModule::reveal_bid(Origin::signed(3), 1, 5, salt));
+        assert_eq!(Balances::reserved_balance(&3), 0);
+        assert_eq!(Balances::free_balance(&3), 100);
+        assert_eq!(KnightModule::sealed_auction(1).unwrap().highest_bid, None);
+        assert!(KnightModule::sealed_bid(1, 3).unwrap().revealed);
+        assert_ok!(KnightModule::reveal_bid(Origin::signed(4), 1, 30, salt));
+        assert_noop!(
+            KnightModule::reveal_bid(Origin::signed(4), 1, 30, salt),
+            Error::<Test>::AlreadyRevealed
+        );
+
+        run_to_block(15);
+        assert_noop!(
+            KnightModule::reveal_bid(Origin::signed(4), 1, 30, salt),
+            Error::<Test>::AuctionNotFound
+        );
+        // only the bid that was never revealed is slashed
+        assert_eq!(Balances::free_balance(&2), 80);
+        assert_eq!(Balances::free_balance(&3), 100);
+        assert!(!System::events().iter().any(|record| matches!(
+            record.event,
+            Event::pallet_knights(crate::Event::SealedBidSlashed(_, 3, _))
+        )));
+    });
+}
+
+#[test]
+fn can_cancel_sealed_auction_without_bids() {
+    new_test_ext().execute_with(|| {
+        System::set_block_number(1);
+        assert_ok!(KnightModule::create_knight(
+            Origin::signed(1),
+            "Sir Tristan".as_bytes().to_vec(),
+            Perbill::zero(),
+            None
+        ));
+
+        assert_ok!(KnightModule::start_sealed_auction(
+            Origin::signed(1),
+            1,
+            10,
+            10
+        ));
+        assert_noop!(
+            KnightModule::burn_knight(Origin::signed(1), 1),
+            Error::<Test>::KnightLocked
+        );
+        assert_noop!(
+            KnightModule::cancel_sealed_auction(Origin::signed(2), 1),
+            Error::<Test>::NotRightfulOwner
+        );
+
+        assert_ok!(KnightModule::cancel_sealed_auction(Origin::signed(1), 1));
+        assert_eq!(KnightModule::auctions_ending_at(15), Vec::<u64>::new());
+        assert_eq!(KnightModule::knight_lock(1), None);
+    });
+}
+
+#[test]
+fn can_accept_offer_on_unlisted_knight() {
+    new_test_ext().execute_with(|| {
+        System::set_block_number(1);
+        assert_ok!(KnightModule::create_knight(
+            Origin::signed(1),
+            "Sir Kay".as_bytes().to_vec(),
+            Perbill::zero(),
+            None
+        ));
+        Balances::make_free_balance_be(&2, 100);
+        Balances::make_free_balance_be(&3, 100);
+
+        assert_ok!(KnightModule::make_offer(Origin::signed(2), 1, 30));
+        assert_ok!(KnightModule::make_offer(Origin::signed(3), 1, 40));
+        assert_eq!(Balances::reserved_balance(&2), 30);
+        assert_eq!(
+            KnightModule::offer(1, 2),
+            Some(crate::Offer {
+                amount: 30,
+                expires: 21
+            })
+        );
+
+        assert_noop!(
+            KnightModule::accept_offer(Origin::signed(2), 1, 2),
+            Error::<Test>::NotRightfulOwner
+        );
+        assert_ok!(KnightModule::accept_offer(Origin::signed(1), 1, 2));
+
+        assert_eq!(KnightModule::knight_to_owner(1), Some(2));
+        assert_eq!(Balances::free_balance(&1), 30);
+        assert_eq!(Balances::free_balance(&2), 70);
+        assert_eq!(Balances::reserved_balance(&2), 0);
+        assert_eq!(KnightModule::offer(1, 2), None);
+        assert_eq!(KnightModule::offers_expiring_at(21), vec![(1, 3)]);
+        assert_eq!(
+            last_event(),
+            Event::pallet_knights(crate::Event::OfferAccepted(1, 1, 2, 30))
+        );
+
+        // the other offer stands and can be accepted by the new owner
+        assert_ok!(KnightModule::accept_offer(Origin::signed(2), 1, 3));
+        assert_eq!(KnightModule::knight_to_owner(1), Some(3));
+        assert_eq!(Balances::free_balance(&2), 110);
+    });
+}
+
+#[test]
+fn can_cancel_offer() {
+    new_test_ext().execute_with(|| {
+        System::set_block_number(1);
+        assert_ok!(KnightModule::create_knight(
+            Origin::signed(1),
+            "Sir Kay".as_bytes().to_vec(),
+            Perbill::zero(),
+            None
+        ));
+        Balances::make_free_balance_be(&2, 100);
+
+        assert_ok!(KnightModule::make_offer(Origin::signed(2), 1, 30));
+        assert_ok!(KnightModule::cancel_offer(Origin::signed(2), 1));
+
+        assert_eq!(Balances::free_balance(&2), 100);
+        assert_eq!(Balances::reserved_balance(&2), 0);
+        assert_eq!(
+            KnightModule::offers_expiring_at(21),
+            Vec::<(u64, u64)>::new()
+        );
+        assert_noop!(
+            KnightModule::accept_offer(Origin::signed(1), 1, 2),
+            Error::<Test>::OfferNotFound
+        );
+        assert_noop!(
+            KnightModule::cancel_offer(Origin::signed(2), 1),
+            Error::<Test>::OfferNotFound
+        );
+    });
+}
+
+#[test]
+fn offers_expire() {
+    new_test_ext().execute_with(|| {
+        System::set_block_number(1);
+        assert_ok!(KnightModule::create_knight(
+            Origin::signed(1),
+            "Sir Kay".as_bytes().to_vec(),
+            Perbill::zero(),
+            None
+        ));
+        Balances::make_free_balance_be(&2, 100);
+
+        assert_ok!(KnightModule::make_offer(Origin::signed(2), 1, 30));
+
+        run_to_block(20);
+        assert_eq!(Balances::reserved_balance(&2), 30);
+
+        run_to_block(21);
+        assert_eq!(KnightModule::offer(1, 2), None);
+        assert_eq!(Balances::reserved_balance(&2), 0);
+        assert_eq!(Balances::free_balance(&2), 100);
+        assert_eq!(
+            last_event(),
+            Event::pallet_knights(crate::Event::OfferExpired(1, 2))
+        );
+        assert_noop!(
+            KnightModule::accept_offer(Origin::signed(1), 1, 2),
+            Error::<Test>::OfferNotFound
+        );
+    });
+}
+
+#[test]
+fn cannot_make_bad_offers() {
+    new_test_ext().execute_with(|| {
+        System::set_block_number(1);
+        assert_ok!(KnightModule::create_knight(
+            Origin::signed(1),
+            "Sir Kay".as_bytes().to_vec(),
+            Perbill::zero(),
+            None
+        ));
+        for buyer in 2..=4 {
+            Balances::make_free_balance_be(&buyer, 100);
+        }
+
+        assert_noop!(
+            KnightModule::make_offer(Origin::signed(2), 2, 30),
+            Error::<Test>::KnightNotFound
+        );
+        assert_noop!(
+            KnightModule::make_offer(Origin::signed(1), 1, 30),
+            Error::<Test>::CannotOfferOnOwnKnight
+        );
+        assert_noop!(
+            KnightModule::make_offer(Origin::signed(2), 1, 300),
+            BalancesError::<Test>::InsufficientBalance
+        );
+        // free offers would let anyone fill a block's expiry slots
+        assert_noop!(
+            KnightModule::make_offer(Origin::signed(2), 1, 0),
+            Error::<Test>::OfferTooLow
+        );
+        assert_noop!(
+            KnightModule::make_offer(Origin::signed(2), 1, 4),
+            Error::<Test>::OfferTooLow
+        );
+
+        assert_ok!(KnightModule::make_offer(Origin::signed(2), 1, 30));
+        assert_noop!(
+            KnightModule::make_offer(Origin::signed(2), 1, 40),
+            Error::<Test>::AlreadyOffered
+        );
+        assert_ok!(KnightModule::make_offer(Origin::signed(3), 1, 30));
+        assert_noop!(
+            KnightModule::make_offer(Origin::signed(4), 1, 30),
+            Error::<Test>::TooManyOffersExpiring
+        );
+    });
+}
+
+#[test]
+fn cannot_accept_offer_on_locked_knight() {
+    new_test_ext().execute_with(|| {
+        System::set_block_number(1);
+        assert_ok!(KnightModule::create_knight(
+            Origin::signed(1),
+            "Sir Kay".as_bytes().to_vec(),
+            Perbill::zero(),
+            None
+        ));
+        Balances::make_free_balance_be(&2, 100);
+
+        assert_ok!(KnightModule::make_offer(Origin::signed(2), 1, 30));
+        assert_ok!(KnightModule::start_auction(Origin::signed(1), 1, 10, 20));
+
+        assert_noop!(
+            KnightModule::accept_offer(Origin::signed(1), 1, 2),
+            Error::<Test>::KnightLocked
+        );
+    });
+}
+
+#[test]
+fn cannot_accept_own_stale_offer() {
+    new_test_ext().execute_with(|| {
+        System::set_block_number(1);
+        assert_ok!(KnightModule::create_knight(
+            Origin::signed(1),
+            "Sir Kay".as_bytes().to_vec(),
+            Perbill::zero(),
+            None
+        ));
+        Balances::make_free_balance_be(&2, 100);
+
+        // the offer outlives the buyer getting the knight another way
+        assert_ok!(KnightModule::make_offer(Origin::signed(2), 1, 30));
+        assert_ok!(KnightModule::transfer_knight(Origin::signed(1), 1, 2));
+
+        assert_noop!(
+            KnightModule::accept_offer(Origin::signed(2), 1, 2),
+            Error::<Test>::CannotOfferOnOwnKnight
+        );
+        assert_eq!(Balances::reserved_balance(&2), 30);
+        assert_eq!(KnightModule::knight_to_owner(1), Some(2));
+    });
+}
+
+#[test]
+fn can_fill_collection_bid_by_generation() {
+    new_test_ext().execute_with(|| {
+        System::set_block_number(1);
+        for _ in 0..2 {
+            assert_ok!(KnightModule::create_knight(
+                Origin::signed(1),
+                "Sir Lionel".as_bytes().to_vec(),
+                Perbill::zero(),
+                None
+            ));
+        }
+        assert_ok!(KnightModule::knight_squire(
+            Origin::signed(1),
+            "Sir Bors".as_bytes().to_vec(),
+            1,
+            2
+        ));
+        Balances::make_free_balance_be(&2, 100);
+
+        assert_ok!(KnightModule::place_collection_bid(
+            Origin::signed(2),
+            2,
+            [0; 16],
+            [0; 16],
+            30,
+            2
+        ));
+        assert_eq!(Balances::reserved_balance(&2), 60);
+
+        assert_noop!(
+            KnightModule::fill_collection_bid(Origin::signed(1), 0, 1),
+            Error::<Test>::KnightDoesNotMatchBid
+        );
+        assert_ok!(KnightModule::fill_collection_bid(Origin::signed(1), 0, 3));
+
+        assert_eq!(KnightModule::knight_to_owner(3), Some(2));
+        assert_eq!(Balances::free_balance(&1), 30);
+        assert_eq!(Balances::reserved_balance(&2), 30);
+        assert_eq!(KnightModule::collection_bid(0).unwrap().remaining, 1);
+        assert_eq!(
+            last_event(),
+            Event::pallet_knights(crate::Event::CollectionBidFilled(0, 3, 1, 2, 30))
+        );
+
+        assert_ok!(KnightModule::cancel_collection_bid(Origin::signed(2), 0));
+        assert_eq!(KnightModule::collection_bid(0), None);
+        assert_eq!(Balances::reserved_balance(&2), 0);
+        assert_eq!(Balances::free_balance(&2), 70);
+    });
+}
+
+#[test]
+fn collection_bid_is_removed_once_filled() {
+    new_test_ext().execute_with(|| {
+        System::set_block_number(1);
+        assert_ok!(KnightModule::create_knight(
+            Origin::signed(1),
+            "Sir Lionel".as_bytes().to_vec(),
+            Perbill::zero(),
+            None
+        ));
+        Balances::make_free_balance_be(&2, 100);
+
+        assert_ok!(KnightModule::place_collection_bid(
+            Origin::signed(2),
+            1,
+            [0; 16],
+            [0; 16],
+            30,
+            1
+        ));
+        assert_ok!(KnightModule::fill_collection_bid(Origin::signed(1), 0, 1));
+
+        assert_eq!(KnightModule::collection_bid(0), None);
+        assert_eq!(Balances::reserved_balance(&2), 0);
+        assert_eq!(Balances::free_balance(&2), 70);
+    });
+}
+
+#[test]
+fn collection_bid_can_match_dna_traits() {
+    new_test_ext().execute_with(|| {
+        System::set_block_number(1);
+        assert_ok!(KnightModule::create_knight(
+            Origin::signed(1),
+            "Sir Lionel".as_bytes().to_vec(),
+            Perbill::zero(),
+            None
+        ));
+        Balances::make_free_balance_be(&2, 100);
+        let dna = KnightModule::knights(1).unwrap().dna;
+
+        let mut mask = [0; 16];
+        mask[0] = 0xf0;
+        let mut other = dna;
+        other[0] ^= 0x10;
+        assert_ok!(KnightModule::place_collection_bid(
+            Origin::signed(2),
+            1,
+            mask,
+            other,
+            30,
+            1
+        ));
+        assert_noop!(
+            KnightModule::fill_collection_bid(Origin::signed(1), 0, 1),
+            Error::<Test>::KnightDoesNotMatchBid
+        );
+
+        // bits outside the mask are ignored
+        let mut pattern = [0; 16];
+        pattern[0] = dna[0] | 0x0f;
+        assert_ok!(KnightModule::place_collection_bid(
+            Origin::signed(2),
+            1,
+            mask,
+            pattern,
+            30,
+            1
+        ));
+        assert_ok!(KnightModule::fill_collection_bid(Origin::signed(1), 1, 1));
+        assert_eq!(KnightModule::knight_to_owner(1), Some(2));
+    });
+}
+
+#[test]
+fn cannot_fill_collection_bid_without_rights() {
+    new_test_ext().execute_with(|| {
+        System::set_block_number(1);
+        assert_ok!(KnightModule::create_knight(
+            Origin::signed(1),
+            "Sir Lionel".as_bytes().to_vec(),
+            Perbill::zero(),
+            None
+        ));
+        assert_ok!(KnightModule::create_knight(
+            Origin::signed(2),
+            "Sir Lionel".as_bytes().to_vec(),
+            Perbill::zero(),
+            None
+        ));
+        Balances::make_free_balance_be(&2, 100);
+
+        assert_noop!(
+            KnightModule::place_collection_bid(Origin::signed(2), 1, [0; 16], [0; 16], 30, 0),
+            Error::<Test>::ZeroQuantity
+        );
+        assert_noop!(
+            KnightModule::place_collection_bid(Origin::signed(2), 1, [0; 16], [0; 16], 30, 5),
+            BalancesError::<Test>::InsufficientBalance
+        );
+        assert_ok!(KnightModule::place_collection_bid(
+            Origin::signed(2),
+            1,
+            [0; 16],
+            [0; 16],
+            30,
+            1
+        ));
+
+        assert_noop!(
+            KnightModule::fill_collection_bid(Origin::signed(1), 1, 1),
+            Error::<Test>::CollectionBidNotFound
+        );
+        assert_noop!(
+            KnightModule::fill_collection_bid(Origin::signed(3), 0, 1),
+            Error::<Test>::NotRightfulOwner
+        );
+        assert_noop!(
+            KnightModule::fill_collection_bid(Origin::signed(2), 0, 2),
+            Error::<Test>::CannotFillOwnBid
+        );
+        assert_noop!(
+            KnightModule::cancel_collection_bid(Origin::signed(1), 0),
+            Error::<Test>::NotRightfulOwner
+        );
+
+        assert_ok!(KnightModule::start_auction(Origin::signed(1), 1, 10, 20));
+        assert_noop!(
+            KnightModule::fill_collection_bid(Origin::signed(1), 0, 1),
+            Error::<Test>::KnightLocked
+        );
+    });
+}
+
+#[test]
+fn resales_pay_creator_royalty() {
+    new_test_ext().execute_with(|| {
+        System::set_block_number(1);
+        assert_ok!(KnightModule::create_knight(
+            Origin::signed(1),
+            "Sir Dinadan".as_bytes().to_vec(),
+            Perbill::from_percent(10),
+            None
+        ));
+        assert_eq!(
+            KnightModule::creator(1),
+            Some(crate::Creator {
+                account: 1,
+                royalty: Perbill::from_percent(10)
+            })
+        );
+        Balances::make_free_balance_be(&2, 200);
+        Balances::make_free_balance_be(&3, 200);
+
+        // the creator's own sale is not split
+        assert_ok!(KnightModule::set_price(Origin::signed(1), 1, 100));
+        assert_ok!(KnightModule::buy_knight(Origin::signed(2), 1, 100));
+        assert_eq!(Balances::free_balance(&1), 100);
+
+        assert_ok!(KnightModule::set_price(Origin::signed(2), 1, 50));
+        assert_ok!(KnightModule::buy_knight(Origin::signed(3), 1, 50));
+        assert_eq!(Balances::free_balance(&1), 105);
+        assert_eq!(Balances::free_balance(&2), 145);
+        assert_eq!(Balances::free_balance(&3), 150);
+        assert!(System::events()
+            .iter()
+            .any(|record| record.event
+                == Event::pallet_knights(crate::Event::SaleSplit(1, 2, 45, 1, 5))));
+    });
+}
+
+#[test]
+fn auction_sales_pay_creator_royalty() {
+    new_test_ext().execute_with(|| {
+        System::set_block_number(1);
+        assert_ok!(KnightModule::create_knight(
+            Origin::signed(1),
+            "Sir Dinadan".as_bytes().to_vec(),
+            Perbill::from_percent(20),
+            None
+        ));
+        assert_ok!(KnightModule::transfer_knight(Origin::signed(1), 1, 2));
+        Balances::make_free_balance_be(&3, 100);
+
+        assert_ok!(KnightModule::start_auction(Origin::signed(2), 1, 10, 20));
+        assert_ok!(KnightModule::bid(Origin::signed(3), 1, 50));
+        run_to_block(20);
+
+        assert_eq!(KnightModule::knight_to_owner(1), Some(3));
+        assert_eq!(Balances::free_balance(&1), 10);
+        assert_eq!(Balances::free_balance(&2), 40);
+        assert_eq!(Balances::free_balance(&3), 50);
+        assert_eq!(Balances::reserved_balance(&3), 0);
+    });
+}
+
+#[test]
+fn cannot_mint_with_royalty_above_max() {
+    new_test_ext().execute_with(|| {
+        assert_noop!(
+            KnightModule::create_knight(
+                Origin::signed(1),
+                "Sir Dinadan".as_bytes().to_vec(),
+                Perbill::from_percent(21),
+                None
+            ),
+            Error::<Test>::RoyaltyTooHigh
+        );
+    });
+}
+
+#[test]
+fn squired_knights_have_no_royalty() {
+    new_test_ext().execute_with(|| {
+        for _ in 0..2 {
+            assert_ok!(KnightModule::create_knight(
+                Origin::signed(1),
+                "Sir Dinadan".as_bytes().to_vec(),
+                Perbill::from_percent(10),
+                None
+            ));
+        }
+        assert_ok!(KnightModule::knight_squire(
+            Origin::signed(1),
+            "Sir Lamorak".as_bytes().to_vec(),
+            1,
+            2
+        ));
+
+        assert_eq!(
+            KnightModule::creator(3),
+            Some(crate::Creator {
+                account: 1,
+                royalty: Perbill::zero()
+            })
+        );
+
+        assert_ok!(KnightModule::burn_knight(Origin::signed(1), 3));
+        assert_eq!(KnightModule::creator(3), None);
+    });
+}
+
+#[test]
+fn buy_knight_pays_marketplace_fee() {
+    new_test_ext().execute_with(|| {
+        System::set_block_number(1);
+        MARKETPLACE_FEE.with(|fee| *fee.borrow_mut() = Perbill::from_percent(10));
+        assert_ok!(KnightModule::create_knight(
+            Origin::signed(1),
+            "Sir Safer".as_bytes().to_vec(),
+            Perbill::from_percent(10),
+            None
+        ));
+        assert_ok!(KnightModule::transfer_knight(Origin::signed(1), 1, 2));
+        Balances::make_free_balance_be(&3, 200);
+
+        assert_ok!(KnightModule::set_price(Origin::signed(2), 1, 100));
+        assert_ok!(KnightModule::buy_knight(Origin::signed(3), 1, 100));
+
+        assert_eq!(Balances::free_balance(&TREASURY), 10);
+        assert_eq!(Balances::free_balance(&1), 10);
+        assert_eq!(Balances::free_balance(&2), 80);
+        assert_eq!(Balances::free_balance(&3), 100);
+        assert!(System::events()
+            .iter()
+            .any(|record| record.event
+                == Event::pallet_knights(crate::Event::MarketplaceFeePaid(1, 10))));
+        assert!(System::events().iter().any(|record| record.event
+            == Event::pallet_knights(crate::Event::SaleSplit(1, 2, 80, 1, 10))));
+    });
+}
+
+#[test]
+fn marketplace_fee_is_only_taken_on_buy_knight() {
+    new_test_ext().execute_with(|| {
+        System::set_block_number(1);
+        MARKETPLACE_FEE.with(|fee| *fee.borrow_mut() = Perbill::from_percent(10));
+        assert_ok!(KnightModule::create_knight(
+            Origin::signed(1),
+            "Sir Safer".as_bytes().to_vec(),
+            Perbill::zero(),
+            None
+        ));
+        Balances::make_free_balance_be(&2, 200);
+
+        assert_ok!(KnightModule::make_offer(Origin::signed(2), 1, 50));
+        assert_ok!(KnightModule::accept_offer(Origin::signed(1), 1, 2));
+
+        assert_eq!(Balances::free_balance(&TREASURY), 0);
+        assert_eq!(Balances::free_balance(&1), 50);
+    });
+}
+
+#[test]
+fn approved_spender_can_transfer_once() {
+    new_test_ext().execute_with(|| {
+        System::set_block_number(1);
+        assert_ok!(KnightModule::create_knight(
+            Origin::signed(1),
+            "Sir Palamedes".as_bytes().to_vec(),
+            Perbill::zero(),
+            None
+        ));
+
+        assert_noop!(
+            KnightModule::transfer_from(Origin::signed(2), 1, 1, 3),
+            Error::<Test>::NotApprovedToTransfer
+        );
+
+        assert_ok!(KnightModule::approve(Origin::signed(1), 1, 2));
+        assert_eq!(KnightModule::approved(1), Some(2));
+        assert_eq!(
+            last_event(),
+            Event::pallet_knights(crate::Event::Approval(1, 1, 2))
+        );
+
+        assert_noop!(
+            KnightModule::transfer_from(Origin::signed(2), 1, 3, 4),
+            Error::<Test>::NotRightfulOwner
+        );
+        assert_ok!(KnightModule::transfer_from(Origin::signed(2), 1, 1, 3));
+        assert_eq!(KnightModule::knight_to_owner(1), Some(3));
+
+        // the approval was cleared by the transfer
+        assert_eq!(KnightModule::approved(1), None);
+        assert_noop!(
+            KnightModule::transfer_from(Origin::signed(2), 1, 3, 2),
+            Error::<Test>::NotApprovedToTransfer
+        );
+    });
+}
+
+#[test]
+fn only_owner_or_operator_can_manage_approvals() {
+    new_test_ext().execute_with(|| {
+        System::set_block_number(1);
+        assert_ok!(KnightModule::create_knight(
+            Origin::signed(1),
+            "Sir Palamedes".as_bytes().to_vec(),
+            Perbill::zero(),
+            None
+        ));
+
+        assert_noop!(
+            KnightModule::approve(Origin::signed(2), 1, 3),
+            Error::<Test>::NotRightfulOwner
+        );
+        assert_noop!(
+            KnightModule::approve(Origin::signed(1), 1, 1),
+            Error::<Test>::CannotApproveSelf
+        );
+        assert_noop!(
+            KnightModule::approve(Origin::signed(1), 2, 3),
+            Error::<Test>::KnightNotFound
+        );
+
+        assert_ok!(KnightModule::set_approval_for_all(
+            Origin::signed(1),
+            2,
+            true
+        ));
+        assert_ok!(KnightModule::approve(Origin::signed(2), 1, 3));
+        assert_eq!(KnightModule::approved(1), Some(3));
+
+        assert_noop!(
+            KnightModule::cancel_approval(Origin::signed(3), 1),
+            Error::<Test>::NotRightfulOwner
+        );
+        assert_ok!(KnightModule::cancel_approval(Origin::signed(2), 1));
+        assert_eq!(KnightModule::approved(1), None);
+    });
+}
+
+#[test]
+fn operator_can_transfer_all_knights() {
+    new_test_ext().execute_with(|| {
+        System::set_block_number(1);
+        for _ in 0..2 {
+            assert_ok!(KnightModule::create_knight(
+                Origin::signed(1),
+                "Sir Palamedes".as_bytes().to_vec(),
+                Perbill::zero(),
+                None
+            ));
+        }
+
+        assert_noop!(
+            KnightModule::set_approval_for_all(Origin::signed(1), 1, true),
+            Error::<Test>::CannotApproveSelf
+        );
+        assert_ok!(KnightModule::set_approval_for_all(
+            Origin::signed(1),
+            2,
+            true
+        ));
+        assert!(KnightModule::is_approved_for_all(1, 2));
+
+        assert_ok!(KnightModule::transfer_from(Origin::signed(2), 1, 1, 3));
+        assert_ok!(KnightModule::transfer_from(Origin::signed(2), 2, 1, 3));
+        assert_eq!(KnightModule::knight_ids_of_owner(&3), vec![1, 2]);
+
+        // operators act for the owner, not for whoever holds the knight next
+        assert_noop!(
+            KnightModule::transfer_from(Origin::signed(2), 1, 3, 2),
+            Error::<Test>::NotApprovedToTransfer
+        );
+
+        assert_ok!(KnightModule::set_approval_for_all(
+            Origin::signed(3),
+            2,
+            true
+        ));
+        assert_ok!(KnightModule::set_approval_for_all(
+            Origin::signed(3),
+            2,
+            false
+        ));
+        assert!(!KnightModule::is_approved_for_all(3, 2));
+        assert_noop!(
+            KnightModule::transfer_from(Origin::signed(2), 1, 3, 2),
+            Error::<Test>::NotApprovedToTransfer
+        );
+    });
+}
+
+#[test]
+fn transferred_knight_is_no_longer_for_sale() {
+    new_test_ext().execute_with(|| {
+        create_knights(1);
+        Balances::make_free_balance_be(&3, 100);
+        assert_ok!(KnightModule::set_price(Origin::signed(1), 1, 10));
+        assert_ok!(KnightModule::approve(Origin::signed(1), 1, 2));
+
+        assert_ok!(KnightModule::transfer_from(Origin::signed(2), 1, 1, 4));
+
+        assert_eq!(KnightModule::knights(1).unwrap().price, 0);
+        assert_noop!(
+            KnightModule::buy_knight(Origin::signed(3), 1, 10),
+            Error::<Test>::KnightNotForSale
+        );
+    });
+}
+
+#[test]
+fn cannot_transfer_locked_knight_from() {
+    new_test_ext().execute_with(|| {
+        System::set_block_number(1);
+        assert_ok!(KnightModule::create_knight(
+            Origin::signed(1),
+            "Sir Palamedes".as_bytes().to_vec(),
+            Perbill::zero(),
+            None
+        ));
+        assert_ok!(KnightModule::approve(Origin::signed(1), 1, 2));
+        assert_ok!(KnightModule::start_auction(Origin::signed(1), 1, 10, 20));
+
+        assert_noop!(
+            KnightModule::transfer_from(Origin::signed(2), 1, 1, 2),
+            Error::<Test>::KnightLocked
+        );
+    });
+}
+
+#[test]
+fn anyone_can_deposit_wealth_into_a_knight() {
+    new_test_ext().execute_with(|| {
+        System::set_block_number(1);
+        assert_ok!(KnightModule::create_knight(
+            Origin::signed(1),
+            "Sir Lamorak".as_bytes().to_vec(),
+            Perbill::zero(),
+            None
+        ));
+        Balances::make_free_balance_be(&2, 100);
+
+        assert_ok!(KnightModule::deposit_wealth(Origin::signed(2), 1, 40));
+        assert_eq!(KnightModule::knights(1).unwrap().wealth, 40);
+        assert_eq!(Balances::free_balance(&KnightModule::vault_account(1)), 40);
+        assert_eq!(Balances::free_balance(&2), 60);
+        assert_eq!(
+            last_event(),
+            Event::pallet_knights(crate::Event::WealthDeposited(1, 2, 40))
+        );
+
+        assert_noop!(
+            KnightModule::deposit_wealth(Origin::signed(2), 2, 10),
+            Error::<Test>::KnightNotFound
+        );
+    });
+}
+
+#[test]
+fn only_owner_can_withdraw_wealth() {
+    new_test_ext().execute_with(|| {
+        System::set_block_number(1);
+        assert_ok!(KnightModule::create_knight(
+            Origin::signed(1),
+            "Sir Lamorak".as_bytes().to_vec(),
+            Perbill::zero(),
+            None
+        ));
+        Balances::make_free_balance_be(&1, 100);
+        assert_ok!(KnightModule::deposit_wealth(Origin::signed(1), 1, 40));
+
+        assert_noop!(
+            KnightModule::withdraw_wealth(Origin::signed(2), 1, 10),
+            Error::<Test>::NotRightfulOwner
+        );
+
+        assert_ok!(KnightModule::withdraw_wealth(Origin::signed(1), 1, 15));
+        assert_eq!(KnightModule::knights(1).unwrap().wealth, 25);
+        assert_eq!(Balances::free_balance(&1), 75);
+        assert_eq!(
+            last_event(),
+            Event::pallet_knights(crate::Event::WealthWithdrawn(1, 1, 15))
+        );
+
+        // the wealth is part of the lot while the knight is up for auction
+        assert_ok!(KnightModule::start_auction(Origin::signed(1), 1, 10, 20));
+        assert_noop!(
+            KnightModule::withdraw_wealth(Origin::signed(1), 1, 10),
+            Error::<Test>::KnightLocked
+        );
+    });
+}
+
+#[test]
+fn wealth_travels_with_the_knight() {
+    new_test_ext().execute_with(|| {
+        System::set_block_number(1);
+        assert_ok!(KnightModule::create_knight(
+            Origin::signed(1),
+            "Sir Lamorak".as_bytes().to_vec(),
+            Perbill::zero(),
+            None
+        ));
+        Balances::make_free_balance_be(&1, 100);
+        Balances::make_free_balance_be(&2, 100);
+        assert_ok!(KnightModule::deposit_wealth(Origin::signed(1), 1, 40));
+
+        assert_ok!(KnightModule::set_price(Origin::signed(1), 1, 50));
+        // the seller can't empty the vault out from under a buyer
+        assert_noop!(
+            KnightModule::withdraw_wealth(Origin::signed(1), 1, 40),
+            Error::<Test>::KnightForSale
+        );
+        assert_ok!(KnightModule::buy_knight(Origin::signed(2), 1, 50));
+        assert_eq!(KnightModule::knights(1).unwrap().wealth, 40);
+        assert_eq!(Balances::free_balance(&KnightModule::vault_account(1)), 40);
+
+        assert_noop!(
+            KnightModule::withdraw_wealth(Origin::signed(1), 1, 40),
+            Error::<Test>::NotRightfulOwner
+        );
+        assert_ok!(KnightModule::withdraw_wealth(Origin::signed(2), 1, 40));
+        assert_eq!(Balances::free_balance(&2), 90);
+        assert_eq!(KnightModule::knights(1).unwrap().wealth, 0);
+    });
+}
+
+#[test]
+fn burning_a_knight_returns_its_wealth() {
+    new_test_ext().execute_with(|| {
+        System::set_block_number(1);
+        assert_ok!(KnightModule::create_knight(
+            Origin::signed(1),
+            "Sir Lamorak".as_bytes().to_vec(),
+            Perbill::zero(),
+            None
+        ));
+        Balances::make_free_balance_be(&2, 100);
+        assert_ok!(KnightModule::deposit_wealth(Origin::signed(2), 1, 40));
+
+        assert_ok!(KnightModule::burn_knight(Origin::signed(1), 1));
+        assert_eq!(Balances::free_balance(&1), 40);
+        assert_eq!(Balances::free_balance(&KnightModule::vault_account(1)), 0);
+    });
+}
+
+/// Mints a knight each for accounts 1 and 2, funds both, and gives them the DNA given.
+fn duellists(dna_1: [u8; 16], dna_2: [u8; 16]) {
+    for (owner, dna) in &[(1, dna_1), (2, dna_2)] {
+        assert_ok!(KnightModule::create_knight(
+            Origin::signed(*owner),
+            "Sir Gareth".as_bytes().to_vec(),
+            Perbill::zero(),
+            None
+        ));
+        let knight_id = KnightModule::latest_knight_id();
+        crate::Knights::<Test>::mutate(knight_id, |knight| knight.as_mut().unwrap().dna = *dna);
+        Balances::make_free_balance_be(owner, 100);
+    }
+}
+
+#[test]
+fn duel_winner_takes_the_pot() {
+    new_test_ext().execute_with(|| {
+        System::set_block_number(1);
+        // a knight with no fighting strength cannot win
+        duellists([200; 16], [0; 16]);
+
+        assert_ok!(KnightModule::challenge(Origin::signed(1), 1, 2, 30));
+        assert_eq!(Balances::reserved_balance(&1), 30);
+        assert_eq!(
+            last_event(),
+            Event::pallet_knights(crate::Event::DuelChallenged(1, 2, 1, 30))
+        );
+
+        assert_ok!(KnightModule::accept_challenge(Origin::signed(2), 1, 30));
+        assert_eq!(
+            last_event(),
+            Event::pallet_knights(crate::Event::DuelAccepted(1, 2, 2, 2))
+        );
+
+        run_to_block(2);
+        assert_eq!(Balances::free_balance(&1), 130);
+        assert_eq!(Balances::free_balance(&2), 70);
+        assert_eq!(Balances::reserved_balance(&1), 0);
+        assert_eq!(Balances::reserved_balance(&2), 0);
+        assert_eq!(
+            last_event(),
+            Event::pallet_knights(crate::Event::DuelWon(1, 2, 1, 60))
+        );
+
+        assert_eq!(KnightModule::duel_record(1).wins, 1);
+        assert_eq!(KnightModule::duel_record(2).losses, 1);
+        assert_eq!(KnightModule::duel_challenge(1), None);
+        assert_eq!(KnightModule::accepted_duel(1), None);
+        assert_eq!(KnightModule::knight_lock(1), None);
+        assert_eq!(KnightModule::knight_lock(2), None);
+    });
+}
+
+#[test]
+fn duel_is_fought_after_it_is_accepted() {
+    new_test_ext().execute_with(|| {
+        System::set_block_number(1);
+        duellists([100; 16], [100; 16]);
+
+        assert_ok!(KnightModule::challenge(Origin::signed(1), 1, 2, 30));
+        assert_ok!(KnightModule::accept_challenge(Origin::signed(2), 1, 30));
+
+        // nothing is decided yet: both wagers are staked and both knights held
+        assert!(!System::events().iter().any(|record| matches!(
+            record.event,
+            Event::pallet_knights(crate::Event::DuelWon(..))
+        )));
+        assert_eq!(Balances::reserved_balance(&1), 30);
+        assert_eq!(Balances::reserved_balance(&2), 30);
+        assert_eq!(KnightModule::duel_record(1), Default::default());
+        assert_eq!(KnightModule::knight_lock(2), Some(crate::KnightLock::Duel));
+        assert_eq!(KnightModule::duels_fought_at(2), vec![1]);
+        assert_noop!(
+            KnightModule::cancel_challenge(Origin::signed(1), 1),
+            Error::<Test>::ChallengeAccepted
+        );
+        assert_noop!(
+            KnightModule::accept_challenge(Origin::signed(2), 1, 30),
+            Error::<Test>::ChallengeAccepted
+        );
+        assert_noop!(
+            KnightModule::transfer_knight(Origin::signed(2), 2, 3),
+            Error::<Test>::KnightLocked
+        );
+
+        // the fight rolls with the randomness of the block it is fought in
+        System::set_block_number(2);
+        let challenger_wins = KnightModule::_fight(
+            &KnightModule::knights(1).unwrap(),
+            &KnightModule::knights(2).unwrap(),
+        );
+        KnightModule::on_initialize(2);
+
+        let (winner_id, loser_id, winner) = if challenger_wins {
+            (1, 2, 1)
+        } else {
+            (2, 1, 2)
+        };
+        assert_eq!(
+            last_event(),
+            Event::pallet_knights(crate::Event::DuelWon(winner_id, loser_id, winner, 60))
+        );
+        assert_eq!(Balances::free_balance(&winner), 130);
+        assert_eq!(Balances::reserved_balance(&1), 0);
+        assert_eq!(Balances::reserved_balance(&2), 0);
+        assert_eq!(KnightModule::duels_fought_at(2), Vec::<u64>::new());
+    });
+}
+
+#[test]
+fn challenger_can_lose_a_duel() {
+    new_test_ext().execute_with(|| {
+        System::set_block_number(1);
+        duellists([0; 16], [200; 16]);
+
+        assert_ok!(KnightModule::challenge(Origin::signed(1), 1, 2, 30));
+        assert_ok!(KnightModule::accept_challenge(Origin::signed(2), 1, 30));
+        run_to_block(2);
+
+        assert_eq!(Balances::free_balance(&1), 70);
+        assert_eq!(Balances::free_balance(&2), 130);
+        assert_eq!(
+            KnightModule::duel_record(1),
+            crate::DuelRecord { wins: 0, losses: 1 }
+        );
+        assert_eq!(
+            KnightModule::duel_record(2),
+            crate::DuelRecord { wins: 1, losses: 0 }
+        );
+    });
+}
+
+#[test]
+fn challenging_knight_is_locked_until_cancelled() {
+    new_test_ext().execute_with(|| {
+        System::set_block_number(1);
+        duellists([1; 16], [1; 16]);
+
+        assert_noop!(
+            KnightModule::challenge(Origin::signed(1), 1, 1, 30),
+            Error::<Test>::CannotDuelOwnKnight
+        );
+        assert_ok!(KnightModule::challenge(Origin::signed(1), 1, 2, 30));
+        assert_noop!(
+            KnightModule::transfer_knight(Origin::signed(1), 1, 3),
+            Error::<Test>::KnightLocked
+        );
+        assert_noop!(
+            KnightModule::challenge(Origin::signed(1), 1, 2, 30),
+            Error::<Test>::KnightLocked
+        );
+
+        assert_noop!(
+            KnightModule::cancel_challenge(Origin::signed(2), 1),
+            Error::<Test>::NotRightfulOwner
+        );
+        assert_ok!(KnightModule::cancel_challenge(Origin::signed(1), 1));
+        assert_eq!(Balances::free_balance(&1), 100);
+        assert_eq!(KnightModule::knight_lock(1), None);
+        assert_noop!(
+            KnightModule::accept_challenge(Origin::signed(2), 1, 30),
+            Error::<Test>::ChallengeNotFound
+        );
+    });
+}
+
+#[test]
+fn cannot_accept_bad_challenges() {
+    new_test_ext().execute_with(|| {
+        System::set_block_number(1);
+        duellists([1; 16], [1; 16]);
+
+        assert_ok!(KnightModule::challenge(Origin::signed(1), 1, 2, 30));
+
+        assert_noop!(
+            KnightModule::accept_challenge(Origin::signed(3), 1, 30),
+            Error::<Test>::NotRightfulOwner
+        );
+        assert_noop!(
+            KnightModule::accept_challenge(Origin::signed(2), 1, 29),
+            Error::<Test>::WagerTooHigh
+        );
+
+        // nothing is staked or recorded when the opponent cannot cover the wager
+        Balances::make_free_balance_be(&2, 10);
+        assert_noop!(
+            KnightModule::accept_challenge(Origin::signed(2), 1, 30),
+            BalancesError::<Test>::InsufficientBalance
+        );
+        assert_eq!(Balances::reserved_balance(&1), 30);
+        assert_eq!(KnightModule::knight_lock(1), Some(crate::KnightLock::Duel));
+        assert_eq!(KnightModule::knight_lock(2), None);
+        assert_eq!(KnightModule::accepted_duel(1), None);
+    });
+}
+
+#[test]
+fn tournament_final_pays_champion_and_runner_up() {
+    new_test_ext().execute_with(|| {
+        System::set_block_number(1);
+        duellists([200; 16], [0; 16]);
+
+        assert_ok!(KnightModule::open_tournament(Origin::signed(3), 40, 2, 20));
+        assert_eq!(
+            last_event(),
+            Event::pallet_knights(crate::Event::TournamentOpened(0, 3, 40, 2))
+        );
+        assert_ok!(KnightModule::enter_tournament(Origin::signed(1), 0, 1));
+        assert_eq!(KnightModule::active_tournaments(), Vec::<u64>::new());
+        assert_ok!(KnightModule::enter_tournament(Origin::signed(2), 0, 2));
+        assert_eq!(KnightModule::active_tournaments(), vec![0]);
+        // a full bracket no longer waits on its registration deadline
+        assert_eq!(
+            KnightModule::registrations_closing_at(20),
+            Vec::<u64>::new()
+        );
+        assert_eq!(
+            Balances::free_balance(&KnightModule::tournament_account(0)),
+            80
+        );
+        assert_eq!(
+            KnightModule::knight_lock(2),
+            Some(crate::KnightLock::Tournament)
+        );
+
+        // the final is fought at the first round boundary
+        run_to_block(9);
+        assert_eq!(KnightModule::tournament(0).unwrap().round, 1);
+        run_to_block(10);
+
+        assert_eq!(Balances::free_balance(&1), 120);
+        assert_eq!(Balances::free_balance(&2), 80);
+        assert!(System::events().iter().any(|record| record.event
+            == Event::pallet_knights(crate::Event::TournamentPrizePaid(0, 2, 2, 20))));
+        assert_eq!(
+            last_event(),
+            Event::pallet_knights(crate::Event::TournamentWon(0, 1, 1, 60))
+        );
+
+        assert_eq!(KnightModule::tournament(0), None);
+        assert_eq!(KnightModule::active_tournaments(), Vec::<u64>::new());
+        assert_eq!(KnightModule::knight_lock(1), None);
+        assert_eq!(KnightModule::knight_lock(2), None);
+    });
+}
+
+#[test]
+fn tournament_rounds_are_fought_at_intervals() {
+    new_test_ext().execute_with(|| {
+        System::set_block_number(1);
+        assert_ok!(KnightModule::open_tournament(Origin::signed(9), 0, 4, 20));
+        for owner in 1..=4 {
+            assert_ok!(KnightModule::create_knight(
+                Origin::signed(owner),
+                "Sir Tristan".as_bytes().to_vec(),
+                Perbill::zero(),
+                None
+            ));
+            // only the first knight has any fighting strength
+            let dna = if owner == 1 { [200; 16] } else { [0; 16] };
+            crate::Knights::<Test>::mutate(owner, |knight| knight.as_mut().unwrap().dna = dna);
+            assert_ok!(KnightModule::enter_tournament(
+                Origin::signed(owner),
+                0,
+                owner
+            ));
+        }
+
+        run_to_block(10);
+        let tournament = KnightModule::tournament(0).unwrap();
+        assert_eq!(tournament.round, 2);
+        assert_eq!(tournament.contenders.len(), 2);
+        assert!(tournament.contenders.contains(&1));
+        assert!(System::events().iter().any(|record| matches!(
+            record.event,
+            Event::pallet_knights(crate::Event::TournamentSeeded(0, _))
+        )));
+        assert!(System::events().iter().any(|record| matches!(
+            record.event,
+            Event::pallet_knights(crate::Event::TournamentRoundFought(0, 1, _))
+        )));
+        let knocked_out = (1..=4)
+            .filter(|knight_id| KnightModule::knight_lock(knight_id).is_none())
+            .count();
+        assert_eq!(knocked_out, 2);
+
+        run_to_block(20);
+        assert_eq!(KnightModule::tournament(0), None);
+        assert!(System::events().iter().any(|record| matches!(
+            record.event,
+            Event::pallet_knights(crate::Event::TournamentWon(0, 1, 1, _))
+        )));
+    });
+}
+
+#[test]
+fn cannot_enter_or_open_bad_tournaments() {
+    new_test_ext().execute_with(|| {
+        System::set_block_number(1);
+        duellists([1; 16], [1; 16]);
+
+        for size in &[0, 1, 3, 16] {
+            assert_noop!(
+                KnightModule::open_tournament(Origin::signed(3), 10, *size, 20),
+                Error::<Test>::InvalidBracketSize
+            );
+        }
+        for end in &[0, 1, 52] {
+            assert_noop!(
+                KnightModule::open_tournament(Origin::signed(3), 10, 2, *end),
+                Error::<Test>::InvalidRegistrationEnd
+            );
+        }
+        assert_ok!(KnightModule::open_tournament(Origin::signed(3), 10, 2, 20));
+        assert_ok!(KnightModule::open_tournament(Origin::signed(3), 10, 2, 20));
+        assert_noop!(
+            KnightModule::open_tournament(Origin::signed(3), 10, 2, 20),
+            Error::<Test>::TooManyRegistrationsClosing
+        );
+
+        assert_noop!(
+            KnightModule::enter_tournament(Origin::signed(2), 0, 1),
+            Error::<Test>::NotRightfulOwner
+        );
+        assert_noop!(
+            KnightModule::enter_tournament(Origin::signed(1), 5, 1),
+            Error::<Test>::TournamentNotFound
+        );
+        assert_ok!(KnightModule::enter_tournament(Origin::signed(1), 0, 1));
+        assert_noop!(
+            KnightModule::enter_tournament(Origin::signed(1), 1, 1),
+            Error::<Test>::KnightLocked
+        );
+        assert_ok!(KnightModule::enter_tournament(Origin::signed(2), 0, 2));
+
+        assert_ok!(KnightModule::create_knight(
+            Origin::signed(1),
+            "Sir Kay".as_bytes().to_vec(),
+            Perbill::zero(),
+            None
+        ));
+        assert_noop!(
+            KnightModule::enter_tournament(Origin::signed(1), 0, 3),
+            Error::<Test>::TournamentUnderway
+        );
+        assert_noop!(
+            KnightModule::cancel_tournament(Origin::signed(3), 0),
+            Error::<Test>::TournamentUnderway
+        );
+
+        // only one tournament may be underway in the mock
+        assert_ok!(KnightModule::enter_tournament(Origin::signed(1), 1, 3));
+        assert_ok!(KnightModule::create_knight(
+            Origin::signed(2),
+            "Sir Bors".as_bytes().to_vec(),
+            Perbill::zero(),
+            None
+        ));
+        assert_noop!(
+            KnightModule::enter_tournament(Origin::signed(2), 1, 4),
+            Error::<Test>::TooManyActiveTournaments
+        );
+    });
+}
+
+#[test]
+fn cancelled_tournament_refunds_entrants() {
+    new_test_ext().execute_with(|| {
+        System::set_block_number(1);
+        duellists([1; 16], [1; 16]);
+
+        assert_ok!(KnightModule::open_tournament(Origin::signed(3), 10, 4, 20));
+        assert_ok!(KnightModule::enter_tournament(Origin::signed(1), 0, 1));
+        assert_ok!(KnightModule::enter_tournament(Origin::signed(2), 0, 2));
+        assert_eq!(Balances::free_balance(&1), 90);
+
+        assert_noop!(
+            KnightModule::cancel_tournament(Origin::signed(1), 0),
+            Error::<Test>::NotRightfulOwner
+        );
+        assert_ok!(KnightModule::cancel_tournament(Origin::signed(3), 0));
+
+        assert_eq!(Balances::free_balance(&1), 100);
+        assert_eq!(Balances::free_balance(&2), 100);
+        assert_eq!(KnightModule::knight_lock(1), None);
+        assert_eq!(KnightModule::knight_lock(2), None);
+        assert_eq!(KnightModule::tournament(0), None);
+        assert_eq!(
+            last_event(),
+            Event::pallet_knights(crate::Event::TournamentCancelled(0))
+        );
+    });
+}
+
+#[test]
+fn unfilled_tournament_is_cancelled_at_its_registration_deadline() {
+    new_test_ext().execute_with(|| {
+        System::set_block_number(1);
+        duellists([1; 16], [1; 16]);
+
+        assert_ok!(KnightModule::open_tournament(Origin::signed(3), 10, 4, 15));
+        assert_ok!(KnightModule::enter_tournament(Origin::signed(1), 0, 1));
+        assert_ok!(KnightModule::enter_tournament(Origin::signed(2), 0, 2));
+        assert_eq!(KnightModule::registrations_closing_at(15), vec![0]);
+
+        run_to_block(14);
+        assert_eq!(KnightModule::tournament(0).unwrap().contenders, vec![1, 2]);
+
+        run_to_block(15);
+        assert_eq!(KnightModule::tournament(0), None);
+        assert_eq!(
+            KnightModule::registrations_closing_at(15),
+            Vec::<u64>::new()
+        );
+        assert_eq!(Balances::free_balance(&1), 100);
+        assert_eq!(Balances::free_balance(&2), 100);
+        assert_eq!(KnightModule::knight_lock(1), None);
+        assert_eq!(KnightModule::knight_lock(2), None);
+        assert_eq!(
+            last_event(),
+            Event::pallet_knights(crate::Event::TournamentCancelled(0))
+        );
+    });
+}
+
+#[test]
+fn entrant_can_leave_before_the_bracket_fills() {
+    new_test_ext().execute_with(|| {
+        System::set_block_number(1);
+        duellists([1; 16], [1; 16]);
+
+        assert_ok!(KnightModule::open_tournament(Origin::signed(3), 10, 2, 20));
+        assert_ok!(KnightModule::enter_tournament(Origin::signed(1), 0, 1));
+        assert_eq!(Balances::free_balance(&1), 90);
+
+        assert_noop!(
+            KnightModule::leave_tournament(Origin::signed(2), 0, 1),
+            Error::<Test>::NotRightfulOwner
+        );
+        assert_noop!(
+            KnightModule::leave_tournament(Origin::signed(2), 0, 2),
+            Error::<Test>::NotEntered
+        );
+        assert_ok!(KnightModule::leave_tournament(Origin::signed(1), 0, 1));
+        assert_eq!(
+            last_event(),
+            Event::pallet_knights(crate::Event::TournamentLeft(0, 1, 1))
+        );
+        assert_eq!(Balances::free_balance(&1), 100);
+        assert_eq!(KnightModule::knight_lock(1), None);
+        assert_eq!(
+            KnightModule::tournament(0).unwrap().contenders,
+            Vec::<u64>::new()
+        );
+
+        // once the bracket is full, nobody may leave
+        assert_ok!(KnightModule::enter_tournament(Origin::signed(1), 0, 1));
+        assert_ok!(KnightModule::enter_tournament(Origin::signed(2), 0, 2));
+        assert_noop!(
+            KnightModule::leave_tournament(Origin::signed(1), 0, 1),
+            Error::<Test>::TournamentUnderway
+        );
+    });
+}
+
+#[test]
+fn base_stats_are_read_from_dna() {
+    let mut dna = [7; 16];
+    dna[..8].copy_from_slice(&[10, 20, 30, 31, 0, 255, 100, 100]);
+
+    assert_eq!(
+        KnightModule::base_stats(&dna),
+        crate::Stats {
+            strength: 15,
+            agility: 30,
+            valor: 127,
+            endurance: 100,
+        }
+    );
+    assert_eq!(KnightModule::base_stats(&[0; 16]), crate::Stats::default());
+}
+
+#[test]
+fn levels_follow_experience() {
+    assert_eq!(KnightModule::level_for(0), 1);
+    assert_eq!(KnightModule::level_for(99), 1);
+    assert_eq!(KnightModule::level_for(100), 2);
+    assert_eq!(KnightModule::level_for(399), 2);
+    assert_eq!(KnightModule::level_for(400), 3);
+    assert_eq!(KnightModule::level_for(900), 4);
+    assert_eq!(KnightModule::level_for(u64::max_value()), 429_496_730);
+}
+
+#[test]
+fn class_and_level_raise_stats() {
+    new_test_ext().execute_with(|| {
+        System::set_block_number(1);
+        assert_ok!(KnightModule::create_knight(
+            Origin::signed(1),
+            "Sir Percival".as_bytes().to_vec(),
+            Perbill::zero(),
+            Some(crate::KnightClass::Paladin)
+        ));
+        crate::Knights::<Test>::mutate(1, |knight| knight.as_mut().unwrap().dna = [50; 16]);
+        assert_eq!(KnightModule::class(1), Some(crate::KnightClass::Paladin));
+
+        assert_eq!(
+            KnightModule::knight_stats(1),
+            Some(crate::Stats {
+                strength: 50,
+                agility: 50,
+                valor: 70,
+                endurance: 50,
+            })
+        );
+
+        crate::Experience::<Test>::insert(1, 400);
+        assert_eq!(KnightModule::level(1), 3);
+        assert_eq!(
+            KnightModule::knight_stats(1),
+            Some(crate::Stats {
+                strength: 60,
+                agility: 60,
+                valor: 80,
+                endurance: 60,
+            })
+        );
+
+        assert_eq!(KnightModule::knight_stats(2), None);
+    });
+}
+
+#[test]
+fn duel_winner_gains_experience() {
+    new_test_ext().execute_with(|| {
+        System::set_block_number(1);
+        duellists([200; 16], [0; 16]);
+
+        assert_ok!(KnightModule::challenge(Origin::signed(1), 1, 2, 10));
+        assert_ok!(KnightModule::accept_challenge(Origin::signed(2), 1, 10));
+        run_to_block(2);
+
+        assert_eq!(KnightModule::experience(1), 100);
+        assert_eq!(KnightModule::level(1), 2);
+        assert_eq!(KnightModule::experience(2), 0);
+        assert!(System::events()
+            .iter()
+            .any(|record| record.event == Event::pallet_knights(crate::Event::LevelledUp(1, 2))));
+    });
+}
+
+#[test]
+fn traits_are_read_from_dna() {
+    use crate::dna::*;
+
+    let mut dna = [0; 16];
+    dna[8..12].copy_from_slice(&[0, 240, 245, 100]);
+    assert_eq!(
+        Traits::from_dna(&dna),
+        Traits {
+            helm: Helm::Kettle,
+            crest: Crest::Dragon,
+            steed: Steed::Unicorn,
+            colour: Colour::Gules,
+        }
+    );
+
+    assert_eq!(Helm::from_gene(99), Helm::Kettle);
+    assert_eq!(Helm::from_gene(100), Helm::Barbute);
+    assert_eq!(Helm::from_gene(249), Helm::GreatHelm);
+    assert_eq!(Helm::from_gene(250), Helm::Crowned);
+    assert_eq!(Crest::from_gene(89), Crest::Plain);
+    assert_eq!(Crest::from_gene(199), Crest::Eagle);
+    assert_eq!(Steed::from_gene(110), Steed::Courser);
+    assert_eq!(Steed::from_gene(244), Steed::Destrier);
+    assert_eq!(Colour::from_gene(175), Colour::Azure);
+    assert_eq!(Colour::from_gene(216), Colour::Sable);
+    assert_eq!(Colour::from_gene(255), Colour::Or);
+}
+
+#[test]
+fn trait_counts_follow_mint_and_burn() {
+    new_test_ext().execute_with(|| {
+        System::set_block_number(1);
+        assert_ok!(KnightModule::create_knight(
+            Origin::signed(1),
+            "Sir Galahad".as_bytes().to_vec(),
+            Perbill::zero(),
+            None
+        ));
+        let traits = KnightModule::knight_traits(1).unwrap();
+        for knight_trait in traits.all().iter() {
+            assert_eq!(KnightModule::trait_count(knight_trait), 1);
+        }
+        // the only knight has every trait to itself
+        assert_eq!(KnightModule::rarity(1), Some(400));
+
+        assert_ok!(KnightModule::burn_knight(Origin::signed(1), 1));
+        for knight_trait in traits.all().iter() {
+            assert_eq!(KnightModule::trait_count(knight_trait), 0);
+        }
+        assert_eq!(KnightModule::rarity(1), None);
+        assert_eq!(KnightModule::knight_traits(1), None);
+    });
+}
+
+#[test]
+fn migration_to_v4_counts_traits_for_rarity() {
+    use crate::dna::{Helm, KnightTrait};
+
+    new_test_ext().execute_with(|| {
+        System::set_block_number(1);
+        for knight_id in 1..=4 {
+            assert_ok!(KnightModule::create_knight(
+                Origin::signed(1),
+                "Sir Galahad".as_bytes().to_vec(),
+                Perbill::zero(),
+                None
+            ));
+            let dna = if knight_id == 4 { [255; 16] } else { [0; 16] };
+            crate::Knights::<Test>::mutate(knight_id, |knight| knight.as_mut().unwrap().dna = dna);
+        }
+        crate::TraitCounts::<Test>::remove_all();
+        crate::pallet::StorageVersion::<Test>::put(Releases::V3_0_0);
+
+        migrations::migrate::<Test>();
+
+        assert_eq!(KnightModule::storage_version(), Releases::LATEST);
+        assert_eq!(
+            KnightModule::trait_count(KnightTrait::Helm(Helm::Kettle)),
+            3
+        );
+        assert_eq!(
+            KnightModule::trait_count(KnightTrait::Helm(Helm::Crowned)),
+            1
+        );
+
+        // 4 * (400 / 3) for the common knights, 4 * (400 / 1) for the odd one out
+        assert_eq!(KnightModule::rarity(1), Some(532));
+        assert_eq!(KnightModule::rarity(4), Some(1600));
+
+        assert_ok!(KnightModule::burn_knight(Origin::signed(1), 4));
+        assert_eq!(
+            KnightModule::trait_count(KnightTrait::Helm(Helm::Crowned)),
+            0
+        );
+        assert_eq!(KnightModule::rarity(1), Some(400));
+    });
+}
+
+/// Creates knights 1 to `count` for account 1.
+fn create_knights(count: u64) {
+    for _ in 0..count {
+        assert_ok!(KnightModule::create_knight(
+            Origin::signed(1),
+            "Sir Lancelot".as_bytes().to_vec(),
+            Perbill::zero(),
+            None
+        ));
+    }
+}
+
+fn squire(knight_id_1: u64, knight_id_2: u64) -> DispatchResultWithPostInfo {
+    KnightModule::knight_squire(
+        Origin::signed(1),
+        "Sir Galahad".as_bytes().to_vec(),
+        knight_id_1,
+        knight_id_2,
+    )
+}
+
+#[test]
+fn squire_generation_follows_the_older_line() {
+    new_test_ext().execute_with(|| {
+        System::set_block_number(1);
+        create_knights(3);
+
+        // knight 4 is generation 2 and knight 5 generation 3
+        assert_ok!(squire(1, 2));
+        System::set_block_number(100);
+        assert_ok!(squire(4, 3));
+        assert_eq!(KnightModule::knights(5).unwrap().gen, 3);
+
+        // the younger parent is listed first, but the older line decides
+        create_knights(1);
+        System::set_block_number(200);
+        assert_ok!(squire(6, 5));
+        assert_eq!(KnightModule::knights(7).unwrap().gen, 4);
+    });
+}
+
+#[test]
+fn cannot_squire_close_relatives() {
+    new_test_ext().execute_with(|| {
+        System::set_block_number(1);
+        create_knights(3);
+        assert_ok!(squire(1, 2));
+        System::set_block_number(100);
+        assert_ok!(squire(1, 3));
+        System::set_block_number(200);
+
+        // knights 4 and 5 are half-siblings through knight 1
+        for (knight_id_1, knight_id_2) in &[(1, 1), (4, 1), (2, 4), (4, 5), (5, 4)] {
+            assert_noop!(
+                squire(*knight_id_1, *knight_id_2),
+                Error::<Test>::CloseRelatives
+            );
+        }
+
+        // an uncle is not a close relative
+        assert_ok!(squire(4, 3));
+    });
+}
+
+#[test]
+fn lineage_queries_are_bounded_by_depth() {
+    new_test_ext().execute_with(|| {
+        System::set_block_number(1);
+        create_knights(3);
+        assert_ok!(squire(1, 2)); // 4
+        System::set_block_number(100);
+        assert_ok!(squire(4, 3)); // 5
+
+        assert_eq!(KnightModule::ancestors(5, 1), vec![4, 3]);
+        assert_eq!(KnightModule::ancestors(5, 2), vec![4, 3, 1, 2]);
+        assert_eq!(KnightModule::ancestors(5, 0), Vec::<u64>::new());
+        assert_eq!(KnightModule::ancestors(1, 5), Vec::<u64>::new());
+
+        assert_eq!(KnightModule::descendants(1, 1), vec![4]);
+        assert_eq!(KnightModule::descendants(1, 2), vec![4, 5]);
+        assert_eq!(KnightModule::descendants(5, 5), Vec::<u64>::new());
+
+        // burned knights stay in the family tree
+        assert_ok!(KnightModule::burn_knight(Origin::signed(1), 5));
+        assert_eq!(KnightModule::descendants(1, 2), vec![4, 5]);
+    });
+}
+
+#[test]
+fn migration_to_v5_indexes_children() {
+    new_test_ext().execute_with(|| {
+        System::set_block_number(1);
+        create_knights(2);
+        assert_ok!(squire(1, 2));
+        crate::Children::<Test>::remove_all();
+        assert_eq!(KnightModule::descendants(1, 1), Vec::<u64>::new());
+        crate::pallet::StorageVersion::<Test>::put(Releases::V4_0_0);
+
+        migrations::migrate::<Test>();
+
+        assert_eq!(KnightModule::storage_version(), Releases::LATEST);
+        assert_eq!(KnightModule::descendants(1, 1), vec![3]);
+        assert_eq!(KnightModule::descendants(2, 1), vec![3]);
+    });
+}
+
+fn order_rules(dues: u128) -> crate::OrderRules<u128> {
+    crate::OrderRules {
+        dues,
+        min_level: 0,
+        class: None,
+    }
+}
+
+/// Has account 9 found an order with dues of 10, then gives accounts 1 to `count`
+/// a knight each and enrolls it.
+fn found_order_with_members(count: u64) {
+    let order_id = KnightModule::next_order_id();
+    assert_ok!(KnightModule::found_order(
+        Origin::signed(9),
+        "Knights of the Round Table".as_bytes().to_vec(),
+        order_rules(10)
+    ));
+    for owner in 1..=count {
+        assert_ok!(KnightModule::create_knight(
+            Origin::signed(owner),
+            "Sir Percival".as_bytes().to_vec(),
+            Perbill::zero(),
+            None
+        ));
+        Balances::make_free_balance_be(&owner, 100);
+        assert_ok!(KnightModule::enroll_knight(
+            Origin::signed(owner),
+            order_id,
+            KnightModule::latest_knight_id()
+        ));
+    }
+}
+
+#[test]
+fn enrolling_pays_dues_into_the_treasury() {
+    new_test_ext().execute_with(|| {
+        System::set_block_number(1);
+        assert_ok!(KnightModule::found_order(
+            Origin::signed(9),
+            "Knights of the Round Table".as_bytes().to_vec(),
+            order_rules(10)
+        ));
+        assert_eq!(
+            last_event(),
+            Event::pallet_knights(crate::Event::OrderFounded(0, 9))
+        );
+        assert_noop!(
+            KnightModule::found_order(Origin::signed(9), Vec::new(), order_rules(10)),
+            Error::<Test>::NameEmpty
+        );
+
+        create_knights(1);
+        Balances::make_free_balance_be(&1, 100);
+        assert_noop!(
+            KnightModule::enroll_knight(Origin::signed(2), 0, 1),
+            Error::<Test>::NotRightfulOwner
+        );
+        assert_noop!(
+            KnightModule::enroll_knight(Origin::signed(1), 5, 1),
+            Error::<Test>::OrderNotFound
+        );
+
+        assert_ok!(KnightModule::enroll_knight(Origin::signed(1), 0, 1));
+        assert_eq!(
+            last_event(),
+            Event::pallet_knights(crate::Event::KnightEnrolled(0, 1, 1, 10))
+        );
+        assert_eq!(Balances::free_balance(&1), 90);
+        assert_eq!(Balances::free_balance(&KnightModule::order_account(0)), 10);
+        assert_eq!(KnightModule::order(0).unwrap().members, 1);
+        assert_eq!(KnightModule::knight_order(1), Some(0));
+        assert_eq!(KnightModule::order_member(0, 1), Some(1));
+
+        assert_noop!(
+            KnightModule::enroll_knight(Origin::signed(1), 0, 1),
+            Error::<Test>::AlreadyInOrder
+        );
+
+        // the knight is the member, so its new owner may take it out
+        assert_ok!(KnightModule::transfer_knight(Origin::signed(1), 1, 2));
+        assert_eq!(KnightModule::knight_order(1), Some(0));
+        assert_noop!(
+            KnightModule::leave_order(Origin::signed(1), 1),
+            Error::<Test>::NotRightfulOwner
+        );
+        assert_ok!(KnightModule::leave_order(Origin::signed(2), 1));
+        assert_eq!(
+            last_event(),
+            Event::pallet_knights(crate::Event::KnightLeftOrder(0, 1))
+        );
+        assert_eq!(KnightModule::knight_order(1), None);
+        assert_eq!(KnightModule::order_member(0, 1), None);
+        assert_eq!(KnightModule::order(0).unwrap().members, 0);
+        // dues are not refunded
+        assert_eq!(Balances::free_balance(&KnightModule::order_account(0)), 10);
+        assert_noop!(
+            KnightModule::leave_order(Origin::signed(2), 1),
+            Error::<Test>::NotInOrder
+        );
+    });
+}
+
+#[test]
+fn order_rules_limit_membership() {
+    new_test_ext().execute_with(|| {
+        System::set_block_number(1);
+        assert_ok!(KnightModule::found_order(
+            Origin::signed(9),
+            "Order of the Grail".as_bytes().to_vec(),
+            crate::OrderRules {
+                dues: 0,
+                min_level: 2,
+                class: Some(crate::KnightClass::Paladin),
+            }
+        ));
+        create_knights(1);
+        assert_ok!(KnightModule::create_knight(
+            Origin::signed(1),
+            "Sir Galahad".as_bytes().to_vec(),
+            Perbill::zero(),
+            Some(crate::KnightClass::Paladin)
+        ));
+
+        crate::Experience::<Test>::insert(1, 100);
+        assert_noop!(
+            KnightModule::enroll_knight(Origin::signed(1), 0, 1),
+            Error::<Test>::KnightDoesNotMeetOrderRules
+        );
+        assert_noop!(
+            KnightModule::enroll_knight(Origin::signed(1), 0, 2),
+            Error::<Test>::KnightDoesNotMeetOrderRules
+        );
+        crate::Experience::<Test>::insert(2, 100);
+        assert_ok!(KnightModule::enroll_knight(Origin::signed(1), 0, 2));
+
+        // orders hold at most `MaxOrderMembers` knights
+        found_order_with_members(4);
+        assert_ok!(KnightModule::create_knight(
+            Origin::signed(5),
+            "Sir Bors".as_bytes().to_vec(),
+            Perbill::zero(),
+            None
+        ));
+        Balances::make_free_balance_be(&5, 100);
+        assert_noop!(
+            KnightModule::enroll_knight(Origin::signed(5), 1, 7),
+            Error::<Test>::OrderFull
+        );
+    });
+}
+
+#[test]
+fn founder_appoints_and_dismisses_officers() {
+    new_test_ext().execute_with(|| {
+        System::set_block_number(1);
+        found_order_with_members(3);
+        assert_ok!(KnightModule::create_knight(
+            Origin::signed(4),
+            "Sir Kay".as_bytes().to_vec(),
+            Perbill::zero(),
+            None
+        ));
+
+        assert_noop!(
+            KnightModule::appoint_officer(Origin::signed(1), 0, 1),
+            Error::<Test>::NotRightfulOwner
+        );
+        assert_noop!(
+            KnightModule::appoint_officer(Origin::signed(9), 0, 4),
+            Error::<Test>::NotInOrder
+        );
+
+        assert_ok!(KnightModule::appoint_officer(Origin::signed(9), 0, 1));
+        assert_eq!(
+            last_event(),
+            Event::pallet_knights(crate::Event::OfficerAppointed(0, 1))
+        );
+        assert_noop!(
+            KnightModule::appoint_officer(Origin::signed(9), 0, 1),
+            Error::<Test>::AlreadyOfficer
+        );
+        assert_ok!(KnightModule::appoint_officer(Origin::signed(9), 0, 2));
+        assert_noop!(
+            KnightModule::appoint_officer(Origin::signed(9), 0, 3),
+            Error::<Test>::TooManyOfficers
+        );
+        assert_eq!(KnightModule::order(0).unwrap().officers, vec![1, 2]);
+
+        assert_noop!(
+            KnightModule::dismiss_officer(Origin::signed(9), 0, 3),
+            Error::<Test>::NotOfficer
+        );
+        assert_ok!(KnightModule::dismiss_officer(Origin::signed(9), 0, 1));
+        assert_eq!(
+            last_event(),
+            Event::pallet_knights(crate::Event::OfficerDismissed(0, 1))
+        );
+        assert_eq!(KnightModule::order(0).unwrap().officers, vec![2]);
+        assert_eq!(KnightModule::knight_order(1), Some(0));
+
+        // burned knights leave their order, giving up any office
+        assert_ok!(KnightModule::burn_knight(Origin::signed(2), 2));
+        let order = KnightModule::order(0).unwrap();
+        assert!(order.officers.is_empty());
+        assert_eq!(order.members, 2);
+        assert_eq!(KnightModule::knight_order(2), None);
+        assert_eq!(KnightModule::order_member(0, 2), None);
+    });
+}
+
+#[test]
+fn member_knights_vote_on_spends() {
+    new_test_ext().execute_with(|| {
+        System::set_block_number(1);
+        found_order_with_members(3);
+        assert_ok!(KnightModule::appoint_officer(Origin::signed(9), 0, 1));
+
+        assert_noop!(
+            KnightModule::propose_spend(Origin::signed(2), 2, 7, 25),
+            Error::<Test>::NotOfficer
+        );
+        assert_noop!(
+            KnightModule::propose_spend(Origin::signed(2), 1, 7, 25),
+            Error::<Test>::NotRightfulOwner
+        );
+        assert_ok!(KnightModule::propose_spend(Origin::signed(1), 1, 7, 25));
+        assert_eq!(
+            last_event(),
+            Event::pallet_knights(crate::Event::SpendProposed(0, 0, 1, 7, 25))
+        );
+
+        assert_ok!(KnightModule::vote_on_spend(Origin::signed(1), 0, 1, true));
+        assert_ok!(KnightModule::vote_on_spend(Origin::signed(2), 0, 2, false));
+        assert_ok!(KnightModule::vote_on_spend(Origin::signed(3), 0, 3, false));
+        // a knight may change its vote
+        assert_ok!(KnightModule::vote_on_spend(Origin::signed(2), 0, 2, true));
+        assert_eq!(
+            last_event(),
+            Event::pallet_knights(crate::Event::SpendVoted(0, 2, true))
+        );
+        let proposal = KnightModule::spend_proposal(0).unwrap();
+        assert_eq!((proposal.ayes, proposal.nays), (2, 1));
+        assert_noop!(
+            KnightModule::vote_on_spend(Origin::signed(1), 0, 2, true),
+            Error::<Test>::NotRightfulOwner
+        );
+
+        run_to_block(11);
+        assert_noop!(
+            KnightModule::close_spend(Origin::signed(5), 0),
+            Error::<Test>::VotingNotOver
+        );
+
+        run_to_block(12);
+        assert_noop!(
+            KnightModule::vote_on_spend(Origin::signed(3), 0, 3, true),
+            Error::<Test>::VotingClosed
+        );
+        assert_ok!(KnightModule::close_spend(Origin::signed(5), 0));
+        assert_eq!(
+            last_event(),
+            Event::pallet_knights(crate::Event::SpendApproved(0, 7, 25))
+        );
+        assert_eq!(Balances::free_balance(&7), 25);
+        assert_eq!(Balances::free_balance(&KnightModule::order_account(0)), 5);
+        assert_eq!(KnightModule::spend_proposal(0), None);
+        assert_eq!(KnightModule::spend_vote(0, 1), None);
+    });
+}
+
+#[test]
+fn spends_without_a_majority_or_funds_are_not_paid() {
+    new_test_ext().execute_with(|| {
+        System::set_block_number(1);
+        found_order_with_members(2);
+        assert_ok!(KnightModule::appoint_officer(Origin::signed(9), 0, 1));
+
+        // a tie is not a majority
+        assert_ok!(KnightModule::propose_spend(Origin::signed(1), 1, 7, 5));
+        assert_ok!(KnightModule::vote_on_spend(Origin::signed(1), 0, 1, true));
+        assert_ok!(KnightModule::vote_on_spend(Origin::signed(2), 0, 2, false));
+
+        // more than the treasury holds
+        assert_ok!(KnightModule::propose_spend(Origin::signed(1), 1, 7, 50));
+        assert_ok!(KnightModule::vote_on_spend(Origin::signed(1), 1, 1, true));
+
+        // knights enrolled after a spend is proposed cannot vote on it
+        run_to_block(2);
+        assert_ok!(KnightModule::create_knight(
+            Origin::signed(3),
+            "Sir Lamorak".as_bytes().to_vec(),
+            Perbill::zero(),
+            None
+        ));
+        Balances::make_free_balance_be(&3, 100);
+        assert_ok!(KnightModule::enroll_knight(Origin::signed(3), 0, 3));
+        assert_noop!(
+            KnightModule::vote_on_spend(Origin::signed(3), 1, 3, false),
+            Error::<Test>::EnrolledAfterProposal
+        );
+
+        run_to_block(12);
+        assert_ok!(KnightModule::close_spend(Origin::signed(5), 0));
+        assert_eq!(
+            last_event(),
+            Event::pallet_knights(crate::Event::SpendRejected(0))
+        );
+        assert_eq!(KnightModule::spend_proposal(0), None);
+
+        assert_noop!(
+            KnightModule::close_spend(Origin::signed(5), 1),
+            BalancesError::<Test>::InsufficientBalance
+        );
+        assert!(KnightModule::spend_proposal(1).is_some());
+        assert_eq!(Balances::free_balance(&7), 0);
+    });
+}
+
+#[test]
+fn leaving_knights_take_back_their_votes() {
+    new_test_ext().execute_with(|| {
+        System::set_block_number(1);
+        found_order_with_members(3);
+        assert_ok!(KnightModule::appoint_officer(Origin::signed(9), 0, 1));
+        assert_ok!(KnightModule::propose_spend(Origin::signed(1), 1, 7, 25));
+        assert_eq!(KnightModule::open_spends(0), vec![0]);
+
+        assert_ok!(KnightModule::vote_on_spend(Origin::signed(1), 0, 1, true));
+        assert_ok!(KnightModule::vote_on_spend(Origin::signed(2), 0, 2, true));
+        assert_ok!(KnightModule::vote_on_spend(Origin::signed(3), 0, 3, false));
+
+        assert_ok!(KnightModule::leave_order(Origin::signed(2), 2));
+        let proposal = KnightModule::spend_proposal(0).unwrap();
+        assert_eq!((proposal.ayes, proposal.nays), (1, 1));
+        assert_eq!(KnightModule::spend_vote(0, 2), None);
+
+        // burning a knight withdraws its votes too
+        assert_ok!(KnightModule::burn_knight(Origin::signed(3), 3));
+        let proposal = KnightModule::spend_proposal(0).unwrap();
+        assert_eq!((proposal.ayes, proposal.nays), (1, 0));
+
+        run_to_block(12);
+        assert_ok!(KnightModule::close_spend(Origin::signed(5), 0));
+        assert_eq!(
+            last_event(),
+            Event::pallet_knights(crate::Event::SpendApproved(0, 7, 25))
+        );
+        assert_eq!(KnightModule::open_spends(0), Vec::<u64>::new());
+    });
+}
+
+#[test]
+fn spends_of_dismissed_officers_cannot_pass() {
+    new_test_ext().execute_with(|| {
+        System::set_block_number(1);
+        found_order_with_members(2);
+        assert_ok!(KnightModule::appoint_officer(Origin::signed(9), 0, 1));
+        assert_ok!(KnightModule::propose_spend(Origin::signed(1), 1, 7, 5));
+        assert_ok!(KnightModule::propose_spend(Origin::signed(1), 1, 7, 5));
+        // orders vote on at most `MaxOpenSpends` spends at once
+        assert_noop!(
+            KnightModule::propose_spend(Origin::signed(1), 1, 7, 5),
+            Error::<Test>::TooManyOpenSpends
+        );
+        for proposal_id in 0..2 {
+            for knight_id in 1..=2 {
+                assert_ok!(KnightModule::vote_on_spend(
+                    Origin::signed(knight_id),
+                    proposal_id,
+                    knight_id,
+                    true
+                ));
+            }
+        }
+
+        assert_ok!(KnightModule::dismiss_officer(Origin::signed(9), 0, 1));
+
+        run_to_block(12);
+        assert_ok!(KnightModule::close_spend(Origin::signed(5), 0));
+        assert_eq!(
+            last_event(),
+            Event::pallet_knights(crate::Event::SpendRejected(0))
+        );
+
+        // reappointed, the officer's spends may pass again
+        assert_ok!(KnightModule::appoint_officer(Origin::signed(9), 0, 1));
+        assert_ok!(KnightModule::close_spend(Origin::signed(5), 1));
+        assert_eq!(
+            last_event(),
+            Event::pallet_knights(crate::Event::SpendApproved(1, 7, 5))
+        );
+        assert_eq!(Balances::free_balance(&7), 5);
+    });
+}
@@ -0,0 +1,667 @@
+//! Placeholder weights for pallet_knights.
+//!
+//! These are NOT benchmark results. Each weight is a hand-picked base cost plus
+//! the storage reads and writes counted from the call's code, so they are only
+//! good enough for a development chain. Before going to production, replace
+//! this whole file by running, on reference hardware:
+//!
+//! ./scripts/benchmark_knights.sh
+//!
+//! which runs the benchmarks in `benchmarking.rs` and writes this file from
+//! `.maintain/frame-weight-template.hbs`.
+
+#![allow(unused_parens)]
+#![allow(unused_imports)]
+
+use frame_support::{
+    traits::Get,
+    weights::{constants::RocksDbWeight, Weight},
+};
+use sp_std::marker::PhantomData;
+
+/// Weight functions needed for pallet_knights.
+pub trait WeightInfo {
+    fn create_knight(n: u32) -> Weight;
+    fn transfer_knight() -> Weight;
+    fn set_price() -> Weight;
+    fn buy_knight() -> Weight;
+    fn knight_squire(n: u32) -> Weight;
+    fn approve_squire() -> Weight;
+    fn revoke_squire_approval() -> Weight;
+    fn list_sire() -> Weight;
+    fn delist_sire() -> Weight;
+    fn squire_with_sire(n: u32) -> Weight;
+    fn burn_knight() -> Weight;
+    fn start_auction() -> Weight;
+    fn bid() -> Weight;
+    fn cancel_auction() -> Weight;
+    fn settle_auction() -> Weight;
+    fn start_dutch_auction() -> Weight;
+    fn buy_from_dutch_auction() -> Weight;
+    fn cancel_dutch_auction() -> Weight;
+    fn start_sealed_auction() -> Weight;
+    fn commit_bid() -> Weight;
+    fn reveal_bid() -> Weight;
+    fn cancel_sealed_auction() -> Weight;
+    fn settle_sealed_auction(n: u32) -> Weight;
+    fn make_offer() -> Weight;
+    fn cancel_offer() -> Weight;
+    fn accept_offer() -> Weight;
+    fn expire_offer() -> Weight;
+    fn place_collection_bid() -> Weight;
+    fn cancel_collection_bid() -> Weight;
+    fn fill_collection_bid() -> Weight;
+    fn approve() -> Weight;
+    fn cancel_approval() -> Weight;
+    fn set_approval_for_all() -> Weight;
+    fn transfer_from() -> Weight;
+    fn deposit_wealth() -> Weight;
+    fn withdraw_wealth() -> Weight;
+    fn challenge() -> Weight;
+    fn cancel_challenge() -> Weight;
+    fn accept_challenge() -> Weight;
+    fn open_tournament() -> Weight;
+    fn enter_tournament() -> Weight;
+    fn cancel_tournament(n: u32) -> Weight;
+    fn fight_tournament_round(n: u32) -> Weight;
+    fn found_order(n: u32) -> Weight;
+    fn enroll_knight() -> Weight;
+    fn leave_order(n: u32) -> Weight;
+    fn appoint_officer() -> Weight;
+    fn dismiss_officer() -> Weight;
+    fn propose_spend() -> Weight;
+    fn vote_on_spend() -> Weight;
+    fn close_spend(n: u32) -> Weight;
+    fn fight_duel() -> Weight;
+    fn leave_tournament(n: u32) -> Weight;
+    fn close_registration(n: u32) -> Weight;
+}
+
+/// Placeholder weights for pallet_knights, pending real benchmark results.
+pub struct SubstrateWeight<T>(PhantomData<T>);
+impl<T: frame_system::Config> WeightInfo for SubstrateWeight<T> {
+    fn create_knight(n: u32) -> Weight {
+        (48_200_000 as Weight)
+            .saturating_add((3_000 as Weight).saturating_mul(n as Weight))
+            .saturating_add(T::DbWeight::get().reads(8 as Weight))
+            .saturating_add(T::DbWeight::get().writes(11 as Weight))
+    }
+    fn transfer_knight() -> Weight {
+        (46_800_000 as Weight)
+            .saturating_add(T::DbWeight::get().reads(5 as Weight))
+            .saturating_add(T::DbWeight::get().writes(8 as Weight))
+    }
+    fn set_price() -> Weight {
+        (29_700_000 as Weight)
+            .saturating_add(T::DbWeight::get().reads(2 as Weight))
+            .saturating_add(T::DbWeight::get().writes(1 as Weight))
+    }
+    fn buy_knight() -> Weight {
+        (99_100_000 as Weight)
+            .saturating_add(T::DbWeight::get().reads(7 as Weight))
+            .saturating_add(T::DbWeight::get().writes(10 as Weight))
+    }
+    fn knight_squire(n: u32) -> Weight {
+        (81_200_000 as Weight)
+            .saturating_add((3_000 as Weight).saturating_mul(n as Weight))
+            .saturating_add(T::DbWeight::get().reads(17 as Weight))
+            .saturating_add(T::DbWeight::get().writes(16 as Weight))
+    }
+    fn approve_squire() -> Weight {
+        (27_400_000 as Weight)
+            .saturating_add(T::DbWeight::get().reads(1 as Weight))
+            .saturating_add(T::DbWeight::get().writes(1 as Weight))
+    }
+    fn revoke_squire_approval() -> Weight {
+        (29_100_000 as Weight)
+            .saturating_add(T::DbWeight::get().reads(2 as Weight))
+            .saturating_add(T::DbWeight::get().writes(1 as Weight))
+    }
+    fn list_sire() -> Weight {
+        (26_800_000 as Weight)
+            .saturating_add(T::DbWeight::get().reads(1 as Weight))
+            .saturating_add(T::DbWeight::get().writes(1 as Weight))
+    }
+    fn delist_sire() -> Weight {
+        (28_500_000 as Weight)
+            .saturating_add(T::DbWeight::get().reads(2 as Weight))
+            .saturating_add(T::DbWeight::get().writes(1 as Weight))
+    }
+    fn squire_with_sire(n: u32) -> Weight {
+        (132_700_000 as Weight)
+            .saturating_add((3_000 as Weight).saturating_mul(n as Weight))
+            .saturating_add(T::DbWeight::get().reads(18 as Weight))
+            .saturating_add(T::DbWeight::get().writes(16 as Weight))
+    }
+    fn burn_knight() -> Weight {
+        (91_600_000 as Weight)
+            .saturating_add(T::DbWeight::get().reads(11 as Weight))
+            .saturating_add(T::DbWeight::get().writes(19 as Weight))
+    }
+    fn start_auction() -> Weight {
+        (38_400_000 as Weight)
+            .saturating_add(T::DbWeight::get().reads(4 as Weight))
+            .saturating_add(T::DbWeight::get().writes(3 as Weight))
+    }
+    fn bid() -> Weight {
+        (61_700_000 as Weight)
+            .saturating_add(T::DbWeight::get().reads(3 as Weight))
+            .saturating_add(T::DbWeight::get().writes(3 as Weight))
+    }
+    fn cancel_auction() -> Weight {
+        (33_900_000 as Weight)
+            .saturating_add(T::DbWeight::get().reads(2 as Weight))
+            .saturating_add(T::DbWeight::get().writes(3 as Weight))
+    }
+    fn settle_auction() -> Weight {
+        (92_500_000 as Weight)
+            .saturating_add(T::DbWeight::get().reads(7 as Weight))
+            .saturating_add(T::DbWeight::get().writes(10 as Weight))
+    }
+    fn start_dutch_auction() -> Weight {
+        (36_100_000 as Weight)
+            .saturating_add(T::DbWeight::get().reads(3 as Weight))
+            .saturating_add(T::DbWeight::get().writes(2 as Weight))
+    }
+    fn buy_from_dutch_auction() -> Weight {
+        (88_300_000 as Weight)
+            .saturating_add(T::DbWeight::get().reads(6 as Weight))
+            .saturating_add(T::DbWeight::get().writes(10 as Weight))
+    }
+    fn cancel_dutch_auction() -> Weight {
+        (27_600_000 as Weight)
+            .saturating_add(T::DbWeight::get().reads(1 as Weight))
+            .saturating_add(T::DbWeight::get().writes(2 as Weight))
+    }
+    fn start_sealed_auction() -> Weight {
+        (39_000_000 as Weight)
+            .saturating_add(T::DbWeight::get().reads(4 as Weight))
+            .saturating_add(T::DbWeight::get().writes(3 as Weight))
+    }
+    fn commit_bid() -> Weight {
+        (47_500_000 as Weight)
+            .saturating_add(T::DbWeight::get().reads(3 as Weight))
+            .saturating_add(T::DbWeight::get().writes(3 as Weight))
+    }
+    fn reveal_bid() -> Weight {
+        (41_200_000 as Weight)
+            .saturating_add(T::DbWeight::get().reads(3 as Weight))
+            .saturating_add(T::DbWeight::get().writes(3 as Weight))
+    }
+    fn cancel_sealed_auction() -> Weight {
+        (33_100_000 as Weight)
+            .saturating_add(T::DbWeight::get().reads(2 as Weight))
+            .saturating_add(T::DbWeight::get().writes(3 as Weight))
+    }
+    fn settle_sealed_auction(n: u32) -> Weight {
+        (84_900_000 as Weight)
+            .saturating_add((29_700_000 as Weight).saturating_mul(n as Weight))
+            .saturating_add(T::DbWeight::get().reads(8 as Weight))
+            .saturating_add(T::DbWeight::get().writes(11 as Weight))
+            .saturating_add(T::DbWeight::get().reads((1 as Weight).saturating_mul(n as Weight)))
+            .saturating_add(T::DbWeight::get().writes((1 as Weight).saturating_mul(n as Weight)))
+    }
+    fn make_offer() -> Weight {
+        (45_800_000 as Weight)
+            .saturating_add(T::DbWeight::get().reads(4 as Weight))
+            .saturating_add(T::DbWeight::get().writes(3 as Weight))
+    }
+    fn cancel_offer() -> Weight {
+        (39_300_000 as Weight)
+            .saturating_add(T::DbWeight::get().reads(2 as Weight))
+            .saturating_add(T::DbWeight::get().writes(3 as Weight))
+    }
+    fn accept_offer() -> Weight {
+        (94_600_000 as Weight)
+            .saturating_add(T::DbWeight::get().reads(8 as Weight))
+            .saturating_add(T::DbWeight::get().writes(11 as Weight))
+    }
+    fn expire_offer() -> Weight {
+        (30_200_000 as Weight)
+            .saturating_add(T::DbWeight::get().reads(1 as Weight))
+            .saturating_add(T::DbWeight::get().writes(2 as Weight))
+    }
+    fn place_collection_bid() -> Weight {
+        (42_700_000 as Weight)
+            .saturating_add(T::DbWeight::get().reads(2 as Weight))
+            .saturating_add(T::DbWeight::get().writes(3 as Weight))
+    }
+    fn cancel_collection_bid() -> Weight {
+        (37_400_000 as Weight)
+            .saturating_add(T::DbWeight::get().reads(2 as Weight))
+            .saturating_add(T::DbWeight::get().writes(2 as Weight))
+    }
+    fn fill_collection_bid() -> Weight {
+        (97_100_000 as Weight)
+            .saturating_add(T::DbWeight::get().reads(9 as Weight))
+            .saturating_add(T::DbWeight::get().writes(11 as Weight))
+    }
+    fn approve() -> Weight {
+        (24_900_000 as Weight)
+            .saturating_add(T::DbWeight::get().reads(2 as Weight))
+            .saturating_add(T::DbWeight::get().writes(1 as Weight))
+    }
+    fn cancel_approval() -> Weight {
+        (23_800_000 as Weight)
+            .saturating_add(T::DbWeight::get().reads(2 as Weight))
+            .saturating_add(T::DbWeight::get().writes(1 as Weight))
+    }
+    fn set_approval_for_all() -> Weight {
+        (19_600_000 as Weight).saturating_add(T::DbWeight::get().writes(1 as Weight))
+    }
+    fn transfer_from() -> Weight {
+        (54_900_000 as Weight)
+            .saturating_add(T::DbWeight::get().reads(7 as Weight))
+            .saturating_add(T::DbWeight::get().writes(9 as Weight))
+    }
+    fn deposit_wealth() -> Weight {
+        (63_800_000 as Weight)
+            .saturating_add(T::DbWeight::get().reads(3 as Weight))
+            .saturating_add(T::DbWeight::get().writes(3 as Weight))
+    }
+    fn withdraw_wealth() -> Weight {
+        (66_100_000 as Weight)
+            .saturating_add(T::DbWeight::get().reads(5 as Weight))
+            .saturating_add(T::DbWeight::get().writes(3 as Weight))
+    }
+    fn challenge() -> Weight {
+        (58_400_000 as Weight)
+            .saturating_add(T::DbWeight::get().reads(5 as Weight))
+            .saturating_add(T::DbWeight::get().writes(3 as Weight))
+    }
+    fn cancel_challenge() -> Weight {
+        (41_200_000 as Weight)
+            .saturating_add(T::DbWeight::get().reads(2 as Weight))
+            .saturating_add(T::DbWeight::get().writes(3 as Weight))
+    }
+    fn accept_challenge() -> Weight {
+        (48_600_000 as Weight)
+            .saturating_add(T::DbWeight::get().reads(6 as Weight))
+            .saturating_add(T::DbWeight::get().writes(4 as Weight))
+    }
+    fn open_tournament() -> Weight {
+        (31_200_000 as Weight)
+            .saturating_add(T::DbWeight::get().reads(2 as Weight))
+            .saturating_add(T::DbWeight::get().writes(3 as Weight))
+    }
+    fn enter_tournament() -> Weight {
+        (74_100_000 as Weight)
+            .saturating_add(T::DbWeight::get().reads(7 as Weight))
+            .saturating_add(T::DbWeight::get().writes(6 as Weight))
+    }
+    fn cancel_tournament(n: u32) -> Weight {
+        (25_400_000 as Weight)
+            .saturating_add((48_800_000 as Weight).saturating_mul(n as Weight))
+            .saturating_add(T::DbWeight::get().reads(3 as Weight))
+            .saturating_add(T::DbWeight::get().writes(2 as Weight))
+            .saturating_add(T::DbWeight::get().reads((1 as Weight).saturating_mul(n as Weight)))
+            .saturating_add(T::DbWeight::get().writes((2 as Weight).saturating_mul(n as Weight)))
+    }
+    fn fight_tournament_round(n: u32) -> Weight {
+        (38_500_000 as Weight)
+            .saturating_add((36_200_000 as Weight).saturating_mul(n as Weight))
+            .saturating_add(T::DbWeight::get().reads(3 as Weight))
+            .saturating_add(T::DbWeight::get().writes(3 as Weight))
+            .saturating_add(T::DbWeight::get().reads((1 as Weight).saturating_mul(n as Weight)))
+            .saturating_add(T::DbWeight::get().writes((1 as Weight).saturating_mul(n as Weight)))
+    }
+    fn found_order(n: u32) -> Weight {
+        (30_400_000 as Weight)
+            .saturating_add((3_000 as Weight).saturating_mul(n as Weight))
+            .saturating_add(T::DbWeight::get().reads(1 as Weight))
+            .saturating_add(T::DbWeight::get().writes(2 as Weight))
+    }
+    fn enroll_knight() -> Weight {
+        (62_300_000 as Weight)
+            .saturating_add(T::DbWeight::get().reads(6 as Weight))
+            .saturating_add(T::DbWeight::get().writes(5 as Weight))
+    }
+    fn leave_order(n: u32) -> Weight {
+        (29_700_000 as Weight)
+            .saturating_add((9_800_000 as Weight).saturating_mul(n as Weight))
+            .saturating_add(T::DbWeight::get().reads(4 as Weight))
+            .saturating_add(T::DbWeight::get().writes(3 as Weight))
+            .saturating_add(T::DbWeight::get().reads((2 as Weight).saturating_mul(n as Weight)))
+            .saturating_add(T::DbWeight::get().writes((2 as Weight).saturating_mul(n as Weight)))
+    }
+    fn appoint_officer() -> Weight {
+        (24_100_000 as Weight)
+            .saturating_add(T::DbWeight::get().reads(2 as Weight))
+            .saturating_add(T::DbWeight::get().writes(1 as Weight))
+    }
+    fn dismiss_officer() -> Weight {
+        (22_800_000 as Weight)
+            .saturating_add(T::DbWeight::get().reads(1 as Weight))
+            .saturating_add(T::DbWeight::get().writes(1 as Weight))
+    }
+    fn propose_spend() -> Weight {
+        (31_500_000 as Weight)
+            .saturating_add(T::DbWeight::get().reads(5 as Weight))
+            .saturating_add(T::DbWeight::get().writes(3 as Weight))
+    }
+    fn vote_on_spend() -> Weight {
+        (33_200_000 as Weight)
+            .saturating_add(T::DbWeight::get().reads(4 as Weight))
+            .saturating_add(T::DbWeight::get().writes(2 as Weight))
+    }
+    fn close_spend(n: u32) -> Weight {
+        (47_900_000 as Weight)
+            .saturating_add((1_900_000 as Weight).saturating_mul(n as Weight))
+            .saturating_add(T::DbWeight::get().reads(5 as Weight))
+            .saturating_add(T::DbWeight::get().writes(4 as Weight))
+            .saturating_add(T::DbWeight::get().writes((1 as Weight).saturating_mul(n as Weight)))
+    }
+    fn fight_duel() -> Weight {
+        (104_300_000 as Weight)
+            .saturating_add(T::DbWeight::get().reads(12 as Weight))
+            .saturating_add(T::DbWeight::get().writes(10 as Weight))
+    }
+    fn leave_tournament(n: u32) -> Weight {
+        (52_700_000 as Weight)
+            .saturating_add((180_000 as Weight).saturating_mul(n as Weight))
+            .saturating_add(T::DbWeight::get().reads(4 as Weight))
+            .saturating_add(T::DbWeight::get().writes(4 as Weight))
+    }
+    fn close_registration(n: u32) -> Weight {
+        (19_800_000 as Weight)
+            .saturating_add((48_800_000 as Weight).saturating_mul(n as Weight))
+            .saturating_add(T::DbWeight::get().reads(1 as Weight))
+            .saturating_add(T::DbWeight::get().writes(1 as Weight))
+            .saturating_add(T::DbWeight::get().reads((1 as Weight).saturating_mul(n as Weight)))
+            .saturating_add(T::DbWeight::get().writes((2 as Weight).saturating_mul(n as Weight)))
+    }
+}
+
+// For backwards compatibility and tests
+impl WeightInfo for () {
+    fn create_knight(n: u32) -> Weight {
+        (48_200_000 as Weight)
+            .saturating_add((3_000 as Weight).saturating_mul(n as Weight))
+            .saturating_add(RocksDbWeight::get().reads(11 as Weight))
+            .saturating_add(RocksDbWeight::get().writes(14 as Weight))
+    }
+    fn transfer_knight() -> Weight {
+        (46_800_000 as Weight)
+            .saturating_add(RocksDbWeight::get().reads(5 as Weight))
+            .saturating_add(RocksDbWeight::get().writes(8 as Weight))
+    }
+    fn set_price() -> Weight {
+        (29_700_000 as Weight)
+            .saturating_add(RocksDbWeight::get().reads(2 as Weight))
+            .saturating_add(RocksDbWeight::get().writes(1 as Weight))
+    }
+    fn buy_knight() -> Weight {
+        (99_100_000 as Weight)
+            .saturating_add(RocksDbWeight::get().reads(7 as Weight))
+            .saturating_add(RocksDbWeight::get().writes(10 as Weight))
+    }
+    fn knight_squire(n: u32) -> Weight {
+        (81_200_000 as Weight)
+            .saturating_add((3_000 as Weight).saturating_mul(n as Weight))
+            .saturating_add(RocksDbWeight::get().reads(17 as Weight))
+            .saturating_add(RocksDbWeight::get().writes(16 as Weight))
+    }
+    fn approve_squire() -> Weight {
+        (27_400_000 as Weight)
+            .saturating_add(RocksDbWeight::get().reads(1 as Weight))
+            .saturating_add(RocksDbWeight::get().writes(1 as Weight))
+    }
+    fn revoke_squire_approval() -> Weight {
+        (29_100_000 as Weight)
+            .saturating_add(RocksDbWeight::get().reads(2 as Weight))
+            .saturating_add(RocksDbWeight::get().writes(1 as Weight))
+    }
+    fn list_sire() -> Weight {
+        (26_800_000 as Weight)
+            .saturating_add(RocksDbWeight::get().reads(1 as Weight))
+            .saturating_add(RocksDbWeight::get().writes(1 as Weight))
+    }
+    fn delist_sire() -> Weight {
+        (28_500_000 as Weight)
+            .saturating_add(RocksDbWeight::get().reads(2 as Weight))
+            .saturating_add(RocksDbWeight::get().writes(1 as Weight))
+    }
+    fn squire_with_sire(n: u32) -> Weight {
+        (132_700_000 as Weight)
+            .saturating_add((3_000 as Weight).saturating_mul(n as Weight))
+            .saturating_add(RocksDbWeight::get().reads(18 as Weight))
+            .saturating_add(RocksDbWeight::get().writes(16 as Weight))
+    }
+    fn burn_knight() -> Weight {
+        (91_600_000 as Weight)
+            .saturating_add(RocksDbWeight::get().reads(11 as Weight))
+            .saturating_add(RocksDbWeight::get().writes(19 as Weight))
+    }
+    fn start_auction() -> Weight {
+        (38_400_000 as Weight)
+            .saturating_add(RocksDbWeight::get().reads(4 as Weight))
+            .saturating_add(RocksDbWeight::get().writes(3 as Weight))
+            .saturating_add(RocksDbWeight::get().writes((1 as Weight).saturating_mul(n as Weight)))
+    }
+    fn bid() -> Weight {
+        (61_700_000 as Weight)
+            .saturating_add(RocksDbWeight::get().reads(3 as Weight))
+            .saturating_add(RocksDbWeight::get().writes(3 as Weight))
+    }
+    fn cancel_auction() -> Weight {
+        (33_900_000 as Weight)
+            .saturating_add(RocksDbWeight::get().reads(2 as Weight))
+            .saturating_add(RocksDbWeight::get().writes(3 as Weight))
+    }
+    fn settle_auction() -> Weight {
+        (92_500_000 as Weight)
+            .saturating_add(RocksDbWeight::get().reads(7 as Weight))
+            .saturating_add(RocksDbWeight::get().writes(10 as Weight))
+    }
+    fn start_dutch_auction() -> Weight {
+        (36_100_000 as Weight)
+            .saturating_add(RocksDbWeight::get().reads(3 as Weight))
+            .saturating_add(RocksDbWeight::get().writes(2 as Weight))
+    }
+    fn buy_from_dutch_auction() -> Weight {
+        (88_300_000 as Weight)
+            .saturating_add(RocksDbWeight::get().reads(6 as Weight))
+            .saturating_add(RocksDbWeight::get().writes(10 as Weight))
+    }
+    fn cancel_dutch_auction() -> Weight {
+        (27_600_000 as Weight)
+            .saturating_add(RocksDbWeight::get().reads(1 as Weight))
+            .saturating_add(RocksDbWeight::get().writes(2 as Weight))
+    }
+    fn start_sealed_auction() -> Weight {
+        (39_000_000 as Weight)
+            .saturating_add(RocksDbWeight::get().reads(4 as Weight))
+            .saturating_add(RocksDbWeight::get().writes(3 as Weight))
+    }
+    fn commit_bid() -> Weight {
+        (47_500_000 as Weight)
+            .saturating_add(RocksDbWeight::get().reads(3 as Weight))
+            .saturating_add(RocksDbWeight::get().writes(3 as Weight))
+    }
+    fn reveal_bid() -> Weight {
+        (41_200_000 as Weight)
+            .saturating_add(RocksDbWeight::get().reads(3 as Weight))
+            .saturating_add(RocksDbWeight::get().writes(3 as Weight))
+    }
+    fn cancel_sealed_auction() -> Weight {
+        (33_100_000 as Weight)
+            .saturating_add(RocksDbWeight::get().reads(2 as Weight))
+            .saturating_add(RocksDbWeight::get().writes(3 as Weight))
+    }
+    fn settle_sealed_auction(n: u32) -> Weight {
+        (84_900_000 as Weight)
+            .saturating_add((29_700_000 as Weight).saturating_mul(n as Weight))
+            .saturating_add(RocksDbWeight::get().reads(8 as Weight))
+            .saturating_add(RocksDbWeight::get().writes(11 as Weight))
+            .saturating_add(RocksDbWeight::get().reads((1 as Weight).saturating_mul(n as Weight)))
+            .saturating_add(RocksDbWeight::get().writes((1 as Weight).saturating_mul(n as Weight)))
+    }
+    fn make_offer() -> Weight {
+        (45_800_000 as Weight)
+            .saturating_add(RocksDbWeight::get().reads(4 as Weight))
+            .saturating_add(RocksDbWeight::get().writes(3 as Weight))
+    }
+    fn cancel_offer() -> Weight {
+        (39_300_000 as Weight)
+            .saturating_add(RocksDbWeight::get().reads(2 as Weight))
+            .saturating_add(RocksDbWeight::get().writes(3 as Weight))
+    }
+    fn accept_offer() -> Weight {
+        (94_600_000 as Weight)
+            .saturating_add(RocksDbWeight::get().reads(8 as Weight))
+            .saturating_add(RocksDbWeight::get().writes(11 as Weight))
+    }
+    fn expire_offer() -> Weight {
+        (30_200_000 as Weight)
+            .saturating_add(RocksDbWeight::get().reads(1 as Weight))
+            .saturating_add(RocksDbWeight::get().writes(2 as Weight))
+    }
+    fn place_collection_bid() -> Weight {
+        (42_700_000 as Weight)
+            .saturating_add(RocksDbWeight::get().reads(2 as Weight))
+            .saturating_add(RocksDbWeight::get().writes(3 as Weight))
+    }
+    fn cancel_collection_bid() -> Weight {
+        (37_400_000 as Weight)
+            .saturating_add(RocksDbWeight::get().reads(2 as Weight))
+            .saturating_add(RocksDbWeight::get().writes(2 as Weight))
+    }
+    fn fill_collection_bid() -> Weight {
+        (97_100_000 as Weight)
+            .saturating_add(RocksDbWeight::get().reads(9 as Weight))
+            .saturating_add(RocksDbWeight::get().writes(11 as Weight))
+    }
+    fn approve() -> Weight {
+        (24_900_000 as Weight)
+            .saturating_add(RocksDbWeight::get().reads(2 as Weight))
+            .saturating_add(RocksDbWeight::get().writes(1 as Weight))
+    }
+    fn cancel_approval() -> Weight {
+        (23_800_000 as Weight)
+            .saturating_add(RocksDbWeight::get().reads(2 as Weight))
+            .saturating_add(RocksDbWeight::get().writes(1 as Weight))
+    }
+    fn set_approval_for_all() -> Weight {
+        (19_600_000 as Weight).saturating_add(RocksDbWeight::get().writes(1 as Weight))
+    }
+    fn transfer_from() -> Weight {
+        (54_900_000 as Weight)
+            .saturating_add(RocksDbWeight::get().reads(7 as Weight))
+            .saturating_add(RocksDbWeight::get().writes(9 as Weight))
+    }
+    fn deposit_wealth() -> Weight {
+        (63_800_000 as Weight)
+            .saturating_add(RocksDbWeight::get().reads(3 as Weight))
+            .saturating_add(RocksDbWeight::get().writes(3 as Weight))
+    }
+    fn withdraw_wealth() -> Weight {
+        (66_100_000 as Weight)
+            .saturating_add(RocksDbWeight::get().reads(5 as Weight))
+            .saturating_add(RocksDbWeight::get().writes(3 as Weight))
+    }
+    fn challenge() -> Weight {
+        (58_400_000 as Weight)
+            .saturating_add(RocksDbWeight::get().reads(5 as Weight))
+            .saturating_add(RocksDbWeight::get().writes(3 as Weight))
+    }
+    fn cancel_challenge() -> Weight {
+        (41_200_000 as Weight)
+            .saturating_add(RocksDbWeight::get().reads(2 as Weight))
+            .saturating_add(RocksDbWeight::get().writes(3 as Weight))
+    }
+    fn accept_challenge() -> Weight {
+        (48_600_000 as Weight)
+            .saturating_add(RocksDbWeight::get().reads(6 as Weight))
+            .saturating_add(RocksDbWeight::get().writes(4 as Weight))
+    }
+    fn open_tournament() -> Weight {
+        (27_900_000 as Weight)
+            .saturating_add(RocksDbWeight::get().reads(1 as Weight))
+            .saturating_add(RocksDbWeight::get().writes(2 as Weight))
+    }
+    fn enter_tournament() -> Weight {
+        (71_300_000 as Weight)
+            .saturating_add(RocksDbWeight::get().reads(6 as Weight))
+            .saturating_add(RocksDbWeight::get().writes(5 as Weight))
+    }
+    fn cancel_tournament(n: u32) -> Weight {
+        (22_600_000 as Weight)
+            .saturating_add((48_800_000 as Weight).saturating_mul(n as Weight))
+            .saturating_add(RocksDbWeight::get().reads(2 as Weight))
+            .saturating_add(RocksDbWeight::get().writes(1 as Weight))
+            .saturating_add(RocksDbWeight::get().reads((1 as Weight).saturating_mul(n as Weight)))
+            .saturating_add(RocksDbWeight::get().writes((2 as Weight).saturating_mul(n as Weight)))
+    }
+    fn fight_tournament_round(n: u32) -> Weight {
+        (38_500_000 as Weight)
+            .saturating_add((36_200_000 as Weight).saturating_mul(n as Weight))
+            .saturating_add(RocksDbWeight::get().reads(3 as Weight))
+            .saturating_add(RocksDbWeight::get().writes(3 as Weight))
+            .saturating_add(RocksDbWeight::get().reads((1 as Weight).saturating_mul(n as Weight)))
+            .saturating_add(RocksDbWeight::get().writes((1 as Weight).saturating_mul(n as Weight)))
+    }
+    fn found_order(n: u32) -> Weight {
+        (30_400_000 as Weight)
+            .saturating_add((3_000 as Weight).saturating_mul(n as Weight))
+            .saturating_add(RocksDbWeight::get().reads(1 as Weight))
+            .saturating_add(RocksDbWeight::get().writes(2 as Weight))
+    }
+    fn enroll_knight() -> Weight {
+        (62_300_000 as Weight)
+            .saturating_add(RocksDbWeight::get().reads(6 as Weight))
+            .saturating_add(RocksDbWeight::get().writes(5 as Weight))
+    }
+    fn leave_order(n: u32) -> Weight {
+        (29_700_000 as Weight)
+            .saturating_add((9_800_000 as Weight).saturating_mul(n as Weight))
+            .saturating_add(RocksDbWeight::get().reads(4 as Weight))
+            .saturating_add(RocksDbWeight::get().writes(3 as Weight))
+            .saturating_add(RocksDbWeight::get().reads((2 as Weight).saturating_mul(n as Weight)))
+            .saturating_add(RocksDbWeight::get().writes((2 as Weight).saturating_mul(n as Weight)))
+    }
+    fn appoint_officer() -> Weight {
+        (24_100_000 as Weight)
+            .saturating_add(RocksDbWeight::get().reads(2 as Weight))
+            .saturating_add(RocksDbWeight::get().writes(1 as Weight))
+    }
+    fn dismiss_officer() -> Weight {
+        (22_800_000 as Weight)
+            .saturating_add(RocksDbWeight::get().reads(1 as Weight))
+            .saturating_add(RocksDbWeight::get().writes(1 as Weight))
+    }
+    fn propose_spend() -> Weight {
+        (31_500_000 as Weight)
+            .saturating_add(RocksDbWeight::get().reads(5 as Weight))
+            .saturating_add(RocksDbWeight::get().writes(3 as Weight))
+    }
+    fn vote_on_spend() -> Weight {
+        (33_200_000 as Weight)
+            .saturating_add(RocksDbWeight::get().reads(4 as Weight))
+            .saturating_add(RocksDbWeight::get().writes(2 as Weight))
+    }
+    fn close_spend(n: u32) -> Weight {
+        (47_900_000 as Weight)
+            .saturating_add((1_900_000 as Weight).saturating_mul(n as Weight))
+            .saturating_add(RocksDbWeight::get().reads(5 as Weight))
+            .saturating_add(RocksDbWeight::get().writes(4 as Weight))
+    }
+    fn fight_duel() -> Weight {
+        (104_300_000 as Weight)
+            .saturating_add(RocksDbWeight::get().reads(12 as Weight))
+            .saturating_add(RocksDbWeight::get().writes(10 as Weight))
+    }
+    fn leave_tournament(n: u32) -> Weight {
+        (52_700_000 as Weight)
+            .saturating_add((180_000 as Weight).saturating_mul(n as Weight))
+            .saturating_add(RocksDbWeight::get().reads(4 as Weight))
+            .saturating_add(RocksDbWeight::get().writes(4 as Weight))
+    }
+    fn close_registration(n: u32) -> Weight {
+        (19_800_000 as Weight)
+            .saturating_add((48_800_000 as Weight).saturating_mul(n as Weight))
+            .saturating_add(RocksDbWeight::get().reads(1 as Weight))
+            .saturating_add(RocksDbWeight::get().writes(1 as Weight))
+            .saturating_add(RocksDbWeight::get().reads((1 as Weight).saturating_mul(n as Weight)))
+            .saturating_add(RocksDbWeight::get().writes((2 as Weight).saturating_mul(n as Weight)))
+    }
+}
@@ -17,7 +17,7 @@ use sp_runtime::traits::{
 use sp_runtime::{
     create_runtime_str, generic, impl_opaque_keys,
     transaction_validity::{TransactionSource, TransactionValidity},
-    ApplyExtrinsicResult, MultiSignature,
+    ApplyExtrinsicResult, ModuleId, MultiSignature,
 };
 use sp_std::prelude::*;
 #[cfg(feature = "std")]
@@ -27,7 +27,7 @@ use sp_version::RuntimeVersion;
 // A few exports that help ease life for downstream crates.
 pub use frame_support::{
     construct_runtime, parameter_types,
-    traits::{KeyOwnerProofSystem, Randomness},
+    traits::{Currency, Imbalance, KeyOwnerProofSystem, OnUnbalanced, Randomness},
     weights::{
         constants::{BlockExecutionWeight, ExtrinsicBaseWeight, RocksDbWeight, WEIGHT_PER_SECOND},
         IdentityFee, Weight,
@@ -71,6 +71,15 @@ pub type Hash = sp_core::H256;
 /// Digest item type.
 pub type DigestItem = generic::DigestItem<Hash>;
 
+/// Identifier of a knight in pallet-knights.
+pub type KnightIndex = u64;
+
+/// A knight as stored by pallet-knights.
+pub type Knight = pallet_knights::Knight<Balance, KnightIndex, BlockNumber>;
+
+/// A knight's looks, as decoded from its DNA by pallet-knights.
+pub type KnightTraits = pallet_knights::dna::Traits;
+
 /// Opaque types. These are used by the CLI to instantiate machinery that don't need to know
 /// the specifics of the runtime. They can then be made to be agnostic over specific formats
 /// of data like extrinsics, allowing for them to continue syncing the network through upgrades
@@ -106,10 +115,10 @@ pub const VERSION: RuntimeVersion = RuntimeVersion {
     //   `spec_version`, and `authoring_version` are the same between Wasm and native.
     // This value is set to 100 to notify Polkadot-JS App (https://polkadot.js.org/apps) to use
     //   the compatible custom types.
-    spec_version: 100,
+    spec_version: 105,
     impl_version: 1,
     apis: RUNTIME_API_VERSIONS,
-    transaction_version: 1, // helps hardware wallets understand if the call methods have been updated, thus changing the API
+    transaction_version: 8, // helps hardware wallets understand if the call methods have been updated, thus changing the API
 };
 
 /// This determines the average expected block time that we are targeting.
@@ -256,8 +265,23 @@ parameter_types! {
     pub const TransactionByteFee: Balance = 1;
 }
 
+type NegativeImbalance = <Balances as Currency<AccountId>>::NegativeImbalance;
+
+/// Sends transaction fees and tips to the treasury.
+pub struct DealWithFees;
+impl OnUnbalanced<NegativeImbalance> for DealWithFees {
+    fn on_unbalanceds<B>(mut fees_then_tips: impl Iterator<Item = NegativeImbalance>) {
+        if let Some(mut fees) = fees_then_tips.next() {
+            if let Some(tips) = fees_then_tips.next() {
+                tips.merge_into(&mut fees);
+            }
+            Treasury::on_unbalanced(fees);
+        }
+    }
+}
+
 impl pallet_transaction_payment::Config for Runtime {
-    type OnChargeTransaction = CurrencyAdapter<Balances, ()>;
+    type OnChargeTransaction = CurrencyAdapter<Balances, DealWithFees>;
     type TransactionByteFee = TransactionByteFee;
     type WeightToFee = IdentityFee<Balance>;
     type FeeMultiplierUpdate = ();
@@ -268,16 +292,99 @@ impl pallet_sudo::Config for Runtime {
     type Call = Call;
 }
 
+parameter_types! {
+    pub const TreasuryModuleId: ModuleId = ModuleId(*b"py/trsry");
+    pub const ProposalBond: Permill = Permill::from_percent(5);
+    pub const ProposalBondMinimum: Balance = 50_000;
+    pub const SpendPeriod: BlockNumber = 1 * DAYS;
+    pub const Burn: Permill = Permill::zero();
+}
+
+impl pallet_treasury::Config for Runtime {
+    type ModuleId = TreasuryModuleId;
+    type Currency = Balances;
+    type ApproveOrigin = frame_system::EnsureRoot<AccountId>;
+    type RejectOrigin = frame_system::EnsureRoot<AccountId>;
+    type Event = Event;
+    type OnSlash = Treasury;
+    type ProposalBond = ProposalBond;
+    type ProposalBondMinimum = ProposalBondMinimum;
+    type SpendPeriod = SpendPeriod;
+    type Burn = Burn;
+    type BurnDestination = ();
+    type SpendFunds = ();
+    type WeightInfo = pallet_treasury::weights::SubstrateWeight<Runtime>;
+}
+
 /// Configure the pallet-template in pallets/template.
 impl pallet_template::Config for Runtime {
     type Event = Event;
 }
 
+parameter_types! {
+    pub const BaseSquireCooldown: BlockNumber = 10 * MINUTES;
+    pub const SquireCooldownPerGeneration: BlockNumber = 5 * MINUTES;
+    pub const MaxKnightNameLength: u32 = 64;
+    pub const MinAuctionDuration: BlockNumber = 10 * MINUTES;
+    pub const MaxAuctionDuration: BlockNumber = 30 * DAYS;
+    pub const MaxAuctionsPerBlock: u32 = 50;
+    pub const RevealPeriod: BlockNumber = 1 * DAYS;
+    pub const MaxSealedBids: u32 = 100;
+    pub const OfferDuration: BlockNumber = 7 * DAYS;
+    pub const MaxOffersPerBlock: u32 = 50;
+    pub const MinOfferAmount: Balance = 10_000;
+    pub const MaxRoyalty: Perbill = Perbill::from_percent(10);
+    pub const MarketplaceFee: Perbill = Perbill::from_percent(2);
+    pub const KnightsModuleId: ModuleId = ModuleId(*b"py/knigt");
+    pub const MaxTournamentSize: u32 = 64;
+    pub const TournamentRoundInterval: BlockNumber = 1 * HOURS;
+    pub const MaxActiveTournaments: u32 = 20;
+    pub const MaxRegistrationPeriod: BlockNumber = 7 * DAYS;
+    pub const MaxRegistrationsPerBlock: u32 = 50;
+    pub const RunnerUpPrize: Perbill = Perbill::from_percent(25);
+    pub const DuelExperience: u64 = 10;
+    pub const MaxDuelsPerBlock: u32 = 50;
+    pub const MaxLineageDepth: u32 = 10;
+    pub const MaxOrderMembers: u32 = 100;
+    pub const MaxOrderOfficers: u32 = 5;
+    pub const OrderVotingPeriod: BlockNumber = 2 * DAYS;
+    pub const MaxOpenSpends: u32 = 10;
+}
+
 impl pallet_knights::Config for Runtime {
     type Event = Event;
     type Currency = Balances;
     type RandomnessSource = RandomnessCollectiveFlip;
-    type KnightIndex = u64;
+    type KnightIndex = KnightIndex;
+    type BaseSquireCooldown = BaseSquireCooldown;
+    type SquireCooldownPerGeneration = SquireCooldownPerGeneration;
+    type MaxNameLength = MaxKnightNameLength;
+    type MinAuctionDuration = MinAuctionDuration;
+    type MaxAuctionDuration = MaxAuctionDuration;
+    type MaxAuctionsPerBlock = MaxAuctionsPerBlock;
+    type RevealPeriod = RevealPeriod;
+    type MaxSealedBids = MaxSealedBids;
+    type OfferDuration = OfferDuration;
+    type MaxOffersPerBlock = MaxOffersPerBlock;
+    type MinOfferAmount = MinOfferAmount;
+    type MaxRoyalty = MaxRoyalty;
+    type MarketplaceFee = MarketplaceFee;
+    type OnMarketplaceFee = Treasury;
+    type ModuleId = KnightsModuleId;
+    type MaxTournamentSize = MaxTournamentSize;
+    type TournamentRoundInterval = TournamentRoundInterval;
+    type MaxActiveTournaments = MaxActiveTournaments;
+    type MaxRegistrationPeriod = MaxRegistrationPeriod;
+    type MaxRegistrationsPerBlock = MaxRegistrationsPerBlock;
+    type RunnerUpPrize = RunnerUpPrize;
+    type DuelExperience = DuelExperience;
+    type MaxDuelsPerBlock = MaxDuelsPerBlock;
+    type MaxLineageDepth = MaxLineageDepth;
+    type MaxOrderMembers = MaxOrderMembers;
+    type MaxOrderOfficers = MaxOrderOfficers;
+    type OrderVotingPeriod = OrderVotingPeriod;
+    type MaxOpenSpends = MaxOpenSpends;
+    type WeightInfo = pallet_knights::weights::SubstrateWeight<Runtime>;
 }
 
 // Create the runtime by composing the FRAME pallets that were previously configured.
@@ -298,6 +405,7 @@ construct_runtime!(
         // Include the custom logic from the pallet-template in the runtime.
         TemplateModule: pallet_template::{Module, Call, Storage, Event<T>},
         KnightModule: pallet_knights::{Module, Call, Storage, Event<T>, Config<T>},
+        Treasury: pallet_treasury::{Module, Call, Storage, Config, Event<T>},
     }
 );
 
@@ -334,6 +442,31 @@ pub type Executive = frame_executive::Executive<
     AllModules,
 >;
 
+sp_api::decl_runtime_apis! {
+    /// Read-only access to pallet-knights so clients don't need to decode
+    /// its storage layout by hand.
+    pub trait KnightsApi {
+        /// The knight with the given id, if it exists.
+        fn knight(knight_id: KnightIndex) -> Option<Knight>;
+        /// The owner of the given knight, if it exists.
+        fn knight_owner(knight_id: KnightIndex) -> Option<AccountId>;
+        /// All knights owned by `owner`.
+        fn knights_by_owner(owner: AccountId) -> Vec<Knight>;
+        /// The number of knights in existence.
+        fn knight_count() -> u64;
+        /// The id of the most recently minted knight.
+        fn latest_knight_id() -> KnightIndex;
+        /// The traits decoded from the given knight's DNA, if it exists.
+        fn knight_traits(knight_id: KnightIndex) -> Option<KnightTraits>;
+        /// How rare the given knight's traits are, if it exists. Higher is rarer.
+        fn knight_rarity(knight_id: KnightIndex) -> Option<u64>;
+        /// The given knight's ancestors up to `depth` generations back, parents first.
+        fn knight_ancestors(knight_id: KnightIndex, depth: u32) -> Vec<KnightIndex>;
+        /// The given knight's descendants up to `depth` generations on, children first.
+        fn knight_descendants(knight_id: KnightIndex, depth: u32) -> Vec<KnightIndex>;
+    }
+}
+
 impl_runtime_apis! {
     impl sp_api::Core<Block> for Runtime {
         fn version() -> RuntimeVersion {
@@ -464,6 +597,44 @@ impl_runtime_apis! {
         }
     }
 
+    impl self::KnightsApi<Block> for Runtime {
+        fn knight(knight_id: KnightIndex) -> Option<Knight> {
+            KnightModule::knights(knight_id)
+        }
+
+        fn knight_owner(knight_id: KnightIndex) -> Option<AccountId> {
+            KnightModule::knight_to_owner(knight_id)
+        }
+
+        fn knights_by_owner(owner: AccountId) -> Vec<Knight> {
+            KnightModule::knights_of_owner(&owner)
+        }
+
+        fn knight_count() -> u64 {
+            KnightModule::knight_count()
+        }
+
+        fn latest_knight_id() -> KnightIndex {
+            KnightModule::latest_knight_id()
+        }
+
+        fn knight_traits(knight_id: KnightIndex) -> Option<KnightTraits> {
+            KnightModule::knight_traits(knight_id)
+        }
+
+        fn knight_rarity(knight_id: KnightIndex) -> Option<u64> {
+            KnightModule::rarity(knight_id)
+        }
+
+        fn knight_ancestors(knight_id: KnightIndex, depth: u32) -> Vec<KnightIndex> {
+            KnightModule::ancestors(knight_id, depth)
+        }
+
+        fn knight_descendants(knight_id: KnightIndex, depth: u32) -> Vec<KnightIndex> {
+            KnightModule::descendants(knight_id, depth)
+        }
+    }
+
     #[cfg(feature = "runtime-benchmarks")]
     impl frame_benchmarking::Benchmark<Block> for Runtime {
         fn dispatch_benchmark(
@@ -495,6 +666,7 @@ impl_runtime_apis! {
             add_benchmark!(params, batches, pallet_timestamp, Timestamp);
             add_benchmark!(params, batches, pallet_template, TemplateModule);
             add_benchmark!(params, batches, pallet_knights, KnightModule);
+            add_benchmark!(params, batches, pallet_treasury, Treasury);
 
             if batches.is_empty() { return Err("Benchmark not found for this pallet.".into()) }
             Ok(batches)
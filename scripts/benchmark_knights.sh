@@ -0,0 +1,22 @@
+#!/usr/bin/env bash
+# This script meant to be run on Unix/Linux based systems
+# Run it on reference hardware: the weights it writes are what the chain charges.
+set -e
+
+echo "*** Generating pallet_knights weights ***"
+
+cd $(dirname ${BASH_SOURCE[0]})/..
+
+cargo build --release --features runtime-benchmarks
+
+./target/release/node-template benchmark \
+    --chain=dev \
+    --steps=50 \
+    --repeat=20 \
+    --pallet=pallet_knights \
+    --extrinsic='*' \
+    --execution=wasm \
+    --wasm-execution=compiled \
+    --heap-pages=4096 \
+    --template=./.maintain/frame-weight-template.hbs \
+    --output=./pallets/knights/src/weights.rs